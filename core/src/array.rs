@@ -63,7 +63,7 @@ impl<'vm> JNIEnv<'vm> {
     /// - `arr` must be a valid object array.
     /// - `index` must be a valid index of `arr`.
     /// - `value` must be a valid object of `cls` if it is `Some`.
-    pub unsafe fn set_object_array_element<R: StrongRef, VR: StrongRef>(
+    pub unsafe fn set_object_array_element<R: StrongRef, VR: StrongRef + ?Sized>(
         &self,
         arr: &R,
         index: i32,
@@ -192,14 +192,14 @@ macro_rules! define_primitive_array_ops {
                     ArrayElementsGuard {
                         env: self,
                         arr,
-                        ptr,
+                        ptr: ptr.cast(),
                         len: length,
-                        release: |guard, commit| {
+                        release: |guard: &mut ArrayElementsGuard<'a, $typ, R>, commit| {
                             call!(
                                 guard.env.as_raw_ptr(),
                                 $release_elements,
                                 guard.arr.as_raw_ptr(),
-                                guard.ptr,
+                                guard.ptr.cast(),
                                 if commit { sys::JNI_COMMIT } else { sys::JNI_ABORT }
                             )
                         },
@@ -228,7 +228,7 @@ macro_rules! define_primitive_array_ops {
                         arr.as_raw_ptr(),
                         offset,
                         buf.len() as _,
-                        buf.as_mut_ptr()
+                        buf.as_mut_ptr().cast()
                     )
                 })
             }
@@ -249,7 +249,7 @@ macro_rules! define_primitive_array_ops {
                         arr.as_raw_ptr(),
                         offset,
                         buf.len() as _,
-                        buf.as_ptr()
+                        buf.as_ptr().cast()
                     )
                 })
             }
@@ -257,12 +257,13 @@ macro_rules! define_primitive_array_ops {
     };
 }
 
+// booleans are raw `jboolean` bytes, since the Java VM may hold values other than `0` and `1`
 define_primitive_array_ops!((
     new_boolean_array,
     get_boolean_array_elements,
     get_boolean_array_region,
     set_boolean_array_region,
-    bool,
+    u8,
     NewBooleanArray,
     GetBooleanArrayElements,
     ReleaseBooleanArrayElements,
@@ -16,6 +16,9 @@ impl<'vm> JNIEnv<'vm> {
             call!(self.as_raw_ptr(), PushLocalFrame, capacity);
         })?;
 
+        #[cfg(feature = "track-local-refs")]
+        crate::LocalRefTracker::push_frame(capacity.max(0) as usize);
+
         let (ret, ret_ref) = f(self);
 
         #[cfg(debug_assertions)]
@@ -26,6 +29,9 @@ impl<'vm> JNIEnv<'vm> {
         let ret_obj = self.run_catch(|| unsafe {
             let ret_ref = ret_ref.map(|r| r.into_trampoline());
 
+            #[cfg(feature = "track-local-refs")]
+            crate::LocalRefTracker::pop_frame();
+
             call!(
                 self.as_raw_ptr(),
                 PopLocalFrame,
@@ -43,6 +49,11 @@ impl<'vm> JNIEnv<'vm> {
     pub fn ensure_local_capacity(&self, capacity: i32) -> Result<(), LocalRef<'_>> {
         self.run_catch(|| unsafe {
             call!(self.as_raw_ptr(), EnsureLocalCapacity, capacity);
-        })
+        })?;
+
+        #[cfg(feature = "track-local-refs")]
+        crate::LocalRefTracker::ensure_capacity(capacity.max(0) as usize);
+
+        Ok(())
     }
 }
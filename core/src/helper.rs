@@ -1,9 +1,23 @@
+/// Calls a JNI function through the function table.
+///
+/// With `tracing`, each call is wrapped in a `jni` span that records only the JNI function name;
+/// class, member name and signature are recorded by the `find_class`, `get_method_id` and `get_field_id` spans.
 macro_rules! call {
     ($env_ptr:expr, $func_name:ident) => {
-        { (**$env_ptr).$func_name.expect(concat!("BROKEN: function JNIEnv::", stringify!($func_name), " undefined"))($env_ptr as *const _ as *mut _) }
+        {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("jni", function = stringify!($func_name)).entered();
+
+            (**$env_ptr).$func_name.expect(concat!("BROKEN: function JNIEnv::", stringify!($func_name), " undefined"))($env_ptr as *const _ as *mut _)
+        }
     };
     ($env_ptr:expr, $func_name:ident, $($args:expr),*) => {
-        { (**$env_ptr).$func_name.expect(concat!("BROKEN: function JNIEnv::", stringify!($func_name), " undefined"))($env_ptr as *const _ as *mut _, $($args),*) }
+        {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("jni", function = stringify!($func_name)).entered();
+
+            (**$env_ptr).$func_name.expect(concat!("BROKEN: function JNIEnv::", stringify!($func_name), " undefined"))($env_ptr as *const _ as *mut _, $($args),*)
+        }
     };
 }
 
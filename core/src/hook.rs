@@ -0,0 +1,93 @@
+use alloc::{sync::Arc, vec::Vec};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::JavaVM;
+
+type Hook = dyn Fn(&JavaVM) + Send + Sync;
+
+struct Registry {
+    next_id: u64,
+    hooks: Vec<(u64, Arc<Hook>)>,
+}
+
+impl Registry {
+    const fn new() -> Self {
+        Self {
+            next_id: 0,
+            hooks: Vec::new(),
+        }
+    }
+}
+
+static ATTACH_HOOKS: Mutex<Registry> = Mutex::new(Registry::new());
+static DETACH_HOOKS: Mutex<Registry> = Mutex::new(Registry::new());
+
+fn lock(registry: &'static Mutex<Registry>) -> MutexGuard<'static, Registry> {
+    registry.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn register(registry: &'static Mutex<Registry>, hook: Arc<Hook>) -> HookRegistration {
+    let mut registry_guard = lock(registry);
+
+    let id = registry_guard.next_id;
+    registry_guard.next_id += 1;
+    registry_guard.hooks.push((id, hook));
+
+    HookRegistration { registry, id }
+}
+
+fn snapshot(registry: &'static Mutex<Registry>) -> Vec<Arc<Hook>> {
+    lock(registry).hooks.iter().map(|(_, hook)| hook.clone()).collect()
+}
+
+/// A registration of an attach or detach hook.
+///
+/// The hook will be unregistered when the registration is dropped.
+#[must_use = "hook will be unregistered immediately if the registration is dropped"]
+pub struct HookRegistration {
+    registry: &'static Mutex<Registry>,
+    id: u64,
+}
+
+impl HookRegistration {
+    /// Keeps the hook registered forever.
+    pub fn forget(self) {
+        core::mem::forget(self)
+    }
+}
+
+impl Drop for HookRegistration {
+    fn drop(&mut self) {
+        lock(self.registry).hooks.retain(|(id, _)| *id != self.id);
+    }
+}
+
+impl JavaVM {
+    /// Add an attach hook. Given hook will be called after a new thread is attached to the Java VM.
+    ///
+    /// Attach hooks are called in the order of registration.
+    pub fn add_attach_hook<F: Fn(&JavaVM) + Send + Sync + 'static>(hook: F) -> HookRegistration {
+        register(&ATTACH_HOOKS, Arc::new(hook))
+    }
+
+    /// Add a detach hook. Given hook will be called before a thread is detached from the Java VM.
+    ///
+    /// Detach hooks are called in the reverse order of registration,
+    /// so that a hook registered later can still rely on the state of hooks registered earlier.
+    pub fn add_detach_hook<F: Fn(&JavaVM) + Send + Sync + 'static>(hook: F) -> HookRegistration {
+        register(&DETACH_HOOKS, Arc::new(hook))
+    }
+
+    pub(crate) fn run_attach_hooks(&self) {
+        // hooks are called without holding the lock, so they can register or unregister hooks.
+        for hook in snapshot(&ATTACH_HOOKS) {
+            hook(self);
+        }
+    }
+
+    pub(crate) fn run_detach_hooks(&self) {
+        for hook in snapshot(&DETACH_HOOKS).into_iter().rev() {
+            hook(self);
+        }
+    }
+}
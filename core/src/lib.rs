@@ -31,29 +31,46 @@
 //! ## Features
 //!
 //! - `alloc`: Enables the use of `alloc` crate for dynamic memory allocation. (default)
+//! - `std`: Enables the use of standard library, e.g. thread-local persistent attachment and multiple attach/detach hooks.
 //! - `print-throwable`: Enables the printing of throwable objects.
+//! - `track-local-refs`: Enables [`LocalRefTracker`] to detect local reference overflows and leaks, intended for debug builds. (requires `std`)
+//! - `tracing`: Enables [`tracing`](https://docs.rs/tracing) spans for JNI calls (recording the JNI function name), class lookups and member lookups (recording class, member name and signature), and events for thrown exceptions.
+//! - `testing`: Enables [`testing::FakeJvm`], an in-process fake JNI environment for unit tests without a Java VM, and [`testing::FaultInjector`] for injecting faults into JNI calls. (requires `std`)
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod array;
 mod call;
 mod field;
 mod frame;
 mod helper;
+#[cfg(feature = "std")]
+mod hook;
 mod member;
 mod monitor;
+mod mutf8;
 mod object;
 mod reference;
 mod register;
 mod string;
 pub mod sys;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod throwable;
+#[cfg(feature = "track-local-refs")]
+mod tracker;
 mod vm;
 
 use core::{marker::PhantomData, ptr::NonNull};
 
-pub use self::{array::*, call::*, member::*, reference::*, register::*, string::*, vm::*};
+#[cfg(feature = "std")]
+pub use self::hook::*;
+#[cfg(feature = "track-local-refs")]
+pub use self::tracker::*;
+pub use self::{array::*, call::*, member::*, monitor::*, mutf8::*, reference::*, register::*, string::*, vm::*};
 use crate::helper::call;
 
 /// A wrapper of raw JNI environment pointer.
@@ -43,11 +43,49 @@ impl_common_member_id!(MethodID, sys::jmethodID);
 impl<'vm> JNIEnv<'vm> {
     /// Finds a class by name.
     pub fn find_class(&self, name: impl AsRef<CStr>) -> Result<LocalRef<'_>, LocalRef<'_>> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("find_class", class = ?name.as_ref()).entered();
+
         let cls = self.run_catch(|| unsafe { call!(self.as_raw_ptr(), FindClass, name.as_ref().as_ptr()) })?;
 
         unsafe { Ok(LocalRef::from_raw(self, cls)) }
     }
 
+    /// Defines a class from the raw class file data.
+    ///
+    /// If `loader` is `None`, the class is defined in the bootstrap class loader.
+    ///
+    /// # Safety
+    ///
+    /// `loader` must be a valid class loader.
+    pub unsafe fn define_class<R: StrongRef>(
+        &self,
+        name: impl AsRef<CStr>,
+        loader: Option<&R>,
+        data: &[u8],
+    ) -> Result<LocalRef<'_>, LocalRef<'_>> {
+        #[cfg(debug_assertions)]
+        if let Some(loader) = loader {
+            loader.enforce_valid_runtime(self);
+        }
+
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("define_class", class = ?name.as_ref(), len = data.len()).entered();
+
+        let cls = self.run_catch(|| unsafe {
+            call!(
+                self.as_raw_ptr(),
+                DefineClass,
+                name.as_ref().as_ptr(),
+                loader.map(|l| l.as_raw_ptr()).unwrap_or(core::ptr::null_mut()),
+                data.as_ptr().cast(),
+                data.len() as _
+            )
+        })?;
+
+        unsafe { Ok(LocalRef::from_raw(self, cls)) }
+    }
+
     /// Finds a method ID by name and signature.
     ///
     /// If `STATIC` is `true`, the method is static. Otherwise, the method is instance.
@@ -64,6 +102,10 @@ impl<'vm> JNIEnv<'vm> {
         #[cfg(debug_assertions)]
         cls.enforce_valid_runtime(self);
 
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("get_method_id", name = ?name.as_ref(), sig = ?sig.as_ref(), is_static = STATIC).entered();
+
         let id = self.run_catch(|| unsafe {
             if STATIC {
                 call!(
@@ -103,6 +145,10 @@ impl<'vm> JNIEnv<'vm> {
         #[cfg(debug_assertions)]
         cls.enforce_valid_runtime(self);
 
+        #[cfg(feature = "tracing")]
+        let _span =
+            tracing::debug_span!("get_field_id", name = ?name.as_ref(), sig = ?sig.as_ref(), is_static = STATIC).entered();
+
         let id = self.run_catch(|| unsafe {
             if STATIC {
                 call!(
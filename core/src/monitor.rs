@@ -1,16 +1,46 @@
-use crate::{JNIEnv, StrongRef, helper::call};
+use crate::{JNIEnv, LocalRef, StrongRef, helper::call};
 
 /// A guard that releases the monitor of the object when dropped.
+///
+/// Failures of releasing the monitor are ignored when dropped, use [`MonitorGuard::exit`] to handle them.
 pub struct MonitorGuard<'a, 'vm, R: StrongRef + 'a> {
     env: &'a JNIEnv<'vm>,
     obj: &'a R,
 }
 
+impl<'a, 'vm, R: StrongRef + 'a> MonitorGuard<'a, 'vm, R> {
+    /// Returns the object whose monitor is held.
+    pub fn object(&self) -> &'a R {
+        self.obj
+    }
+
+    /// Returns the env that entered the monitor.
+    pub fn env(&self) -> &'a JNIEnv<'vm> {
+        self.env
+    }
+
+    fn exit_monitor(&self) -> Result<(), LocalRef<'a>> {
+        self.env.run_catch(|| unsafe {
+            call!(self.env.as_raw_ptr(), MonitorExit, self.obj.as_raw_ptr());
+        })
+    }
+
+    /// Releases the monitor.
+    ///
+    /// Returns `Err(IllegalMonitorStateException)` if the current thread does not own the monitor.
+    pub fn exit(self) -> Result<(), LocalRef<'a>> {
+        let ret = self.exit_monitor();
+
+        core::mem::forget(self);
+
+        ret
+    }
+}
+
 impl<'a, 'vm, R: StrongRef + 'a> Drop for MonitorGuard<'a, 'vm, R> {
     fn drop(&mut self) {
-        unsafe {
-            call!(self.env.as_raw_ptr(), MonitorExit, self.obj.as_raw_ptr());
-        }
+        // panicking here may abort the process while unwinding.
+        let _ = self.exit_monitor();
     }
 }
 
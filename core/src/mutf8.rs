@@ -0,0 +1,205 @@
+//! Modified UTF-8, the string encoding used by JNI.
+//!
+//! Modified UTF-8 encodes each UTF-16 code unit separately (supplementary characters become two
+//! 3-byte surrogates, like CESU-8) and encodes NUL as `0xC0 0x80`, so encoded strings never contain zero bytes.
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// An error of decoding invalid modified UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidModifiedUTF8 {
+    /// The length of the longest valid prefix.
+    pub valid_up_to: usize,
+}
+
+impl Display for InvalidModifiedUTF8 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "invalid modified UTF-8 at byte {}", self.valid_up_to)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidModifiedUTF8 {}
+
+fn unit_len(unit: u16) -> usize {
+    match unit {
+        0x0001..=0x007F => 1,
+        0x0000 | 0x0080..=0x07FF => 2,
+        _ => 3,
+    }
+}
+
+pub(crate) fn encode_unit(unit: u16, mut push: impl FnMut(u8)) {
+    match unit_len(unit) {
+        1 => push(unit as u8),
+        2 => {
+            push(0xC0 | (unit >> 6) as u8);
+            push(0x80 | (unit & 0x3F) as u8);
+        }
+        _ => {
+            push(0xE0 | (unit >> 12) as u8);
+            push(0x80 | ((unit >> 6) & 0x3F) as u8);
+            push(0x80 | (unit & 0x3F) as u8);
+        }
+    }
+}
+
+/// Decodes UTF-16 code units from modified UTF-8, surrogates are not paired.
+pub(crate) fn decode_units(bytes: &[u8]) -> impl Iterator<Item = Result<u16, InvalidModifiedUTF8>> + '_ {
+    let mut offset = 0;
+
+    core::iter::from_fn(move || {
+        let start = offset;
+        let err = InvalidModifiedUTF8 { valid_up_to: start };
+
+        let continuation = |index: usize| match bytes.get(index) {
+            Some(b) if b & 0xC0 == 0x80 => Ok((b & 0x3F) as u16),
+            _ => Err(err),
+        };
+
+        let unit = match *bytes.get(start)? {
+            b @ 0x01..=0x7F => Ok((b as u16, 1)),
+            b @ 0xC0..=0xDF => continuation(start + 1)
+                .map(|c| (((b & 0x1F) as u16) << 6 | c, 2))
+                .and_then(|(unit, len)| {
+                    if unit == 0 || unit >= 0x80 {
+                        Ok((unit, len))
+                    } else {
+                        Err(err)
+                    }
+                }),
+            b @ 0xE0..=0xEF => continuation(start + 1)
+                .and_then(|c1| Ok(((b & 0x0F) as u16) << 12 | c1 << 6 | continuation(start + 2)?))
+                .and_then(|unit| if unit >= 0x800 { Ok((unit, 3)) } else { Err(err) }),
+            _ => Err(err),
+        };
+
+        Some(match unit {
+            Ok((unit, len)) => {
+                offset += len;
+                Ok(unit)
+            }
+            Err(err) => {
+                offset = bytes.len();
+                Err(err)
+            }
+        })
+    })
+}
+
+/// Returns the length of `s` encoded in modified UTF-8, excluding the NUL terminator.
+pub fn modified_utf8_len(s: &str) -> usize {
+    s.encode_utf16().map(unit_len).sum()
+}
+
+/// Encodes `s` into `buf` in modified UTF-8 and returns the encoded length, excluding the NUL terminator.
+///
+/// Returns `None` if `buf` is shorter than [`modified_utf8_len`].
+pub fn encode_modified_utf8_into(s: &str, buf: &mut [u8]) -> Option<usize> {
+    let mut len = 0;
+    let mut overflow = false;
+
+    for unit in s.encode_utf16() {
+        encode_unit(unit, |b| match buf.get_mut(len) {
+            Some(slot) => {
+                *slot = b;
+                len += 1;
+            }
+            None => overflow = true,
+        });
+    }
+
+    (!overflow).then_some(len)
+}
+
+/// Encodes `s` in modified UTF-8, excluding the NUL terminator.
+#[cfg(feature = "alloc")]
+pub fn encode_modified_utf8(s: &str) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(modified_utf8_len(s) + 1);
+
+    for unit in s.encode_utf16() {
+        encode_unit(unit, |b| buf.push(b));
+    }
+
+    buf
+}
+
+/// Decodes modified UTF-8 `bytes` without the NUL terminator.
+///
+/// Borrows `bytes` if they are also valid UTF-8, i.e. there are no NULs and supplementary characters.
+/// Unpaired surrogates are invalid since they are not representable in `str`.
+#[cfg(feature = "alloc")]
+pub fn decode_modified_utf8(bytes: &[u8]) -> Result<Cow<'_, str>, InvalidModifiedUTF8> {
+    if !bytes.iter().any(|&b| b == 0 || b >= 0xF0) {
+        // valid UTF-8 without NULs and 4-byte sequences is the same in both encodings
+        if let Ok(s) = core::str::from_utf8(bytes) {
+            return Ok(Cow::Borrowed(s));
+        }
+    }
+
+    let mut ret = String::with_capacity(bytes.len());
+    let mut units = decode_units(bytes).peekable();
+    let mut offset = 0;
+
+    while let Some(unit) = units.next() {
+        let unit = unit?;
+        let err = InvalidModifiedUTF8 { valid_up_to: offset };
+
+        let c = match unit {
+            0xD800..=0xDBFF => match units.peek() {
+                Some(Ok(low @ 0xDC00..=0xDFFF)) => {
+                    let c = 0x10000 + (((unit as u32) - 0xD800) << 10 | ((*low as u32) - 0xDC00));
+                    units.next();
+                    offset += 3;
+                    char::from_u32(c).ok_or(err)?
+                }
+                _ => return Err(err),
+            },
+            0xDC00..=0xDFFF => return Err(err),
+            unit => char::from_u32(unit as u32).ok_or(err)?,
+        };
+
+        offset += unit_len(unit);
+        ret.push(c);
+    }
+
+    Ok(Cow::Owned(ret))
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modified_utf8_round_trip() {
+        for s in ["", "hello", "a\0b", "ÿ中文", "emoji 😀!", "\0\u{10FFFF}"] {
+            let encoded = encode_modified_utf8(s);
+
+            assert_eq!(encoded.len(), modified_utf8_len(s));
+            assert!(!encoded.contains(&0));
+            assert_eq!(decode_modified_utf8(&encoded).unwrap(), s);
+        }
+
+        assert_eq!(encode_modified_utf8("\0"), [0xC0, 0x80]);
+        assert_eq!(encode_modified_utf8("😀"), [0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]);
+        assert!(matches!(decode_modified_utf8(b"plain"), Ok(Cow::Borrowed("plain"))));
+    }
+
+    #[test]
+    fn test_modified_utf8_invalid() {
+        assert_eq!(decode_modified_utf8(b"a\0").unwrap_err().valid_up_to, 1);
+        assert_eq!(decode_modified_utf8(&[b'a', 0xC1, 0x81]).unwrap_err().valid_up_to, 1);
+        assert_eq!(decode_modified_utf8(&[0xF0, 0x9F, 0x98, 0x80]).unwrap_err().valid_up_to, 0);
+        assert_eq!(
+            decode_modified_utf8(&[b'a', b'b', 0xED, 0xA0, 0xBD]).unwrap_err().valid_up_to,
+            2
+        );
+        assert_eq!(decode_modified_utf8(&[0xE4, 0xB8]).unwrap_err().valid_up_to, 0);
+
+        let mut buf = [0u8; 2];
+        assert_eq!(encode_modified_utf8_into("\0", &mut buf), Some(2));
+        assert_eq!(encode_modified_utf8_into("ab\0", &mut buf), None);
+    }
+}
@@ -74,14 +74,19 @@ impl<'env> LocalRef<'env> {
     ///
     /// The caller must ensure that the raw pointer is valid and points to a Java object.
     pub unsafe fn from_raw(env: &'env JNIEnv<'env>, raw: sys::jobject) -> Self {
-        Self {
-            env,
-            ptr: NonNull::new(raw).expect("create local reference from null pointer"),
-        }
+        let ptr = NonNull::new(raw).expect("create local reference from null pointer");
+
+        #[cfg(feature = "track-local-refs")]
+        crate::LocalRefTracker::track(ptr.as_ptr() as usize);
+
+        Self { env, ptr }
     }
 
     /// Converts this local reference to a trampoline reference.
     pub fn into_trampoline(self) -> TrampolineRef<'env> {
+        #[cfg(feature = "track-local-refs")]
+        crate::LocalRefTracker::untrack(self.ptr.as_ptr() as usize);
+
         let r = TrampolineRef {
             ptr: self.ptr,
             _env: PhantomData,
@@ -121,6 +126,9 @@ impl<'env> StrongRef for LocalRef<'env> {}
 
 impl<'env> Drop for LocalRef<'env> {
     fn drop(&mut self) {
+        #[cfg(feature = "track-local-refs")]
+        crate::LocalRefTracker::untrack(self.ptr.as_ptr() as usize);
+
         unsafe {
             call!(self.env.as_raw_ptr(), DeleteLocalRef, self.ptr.as_ptr());
         }
@@ -253,7 +261,7 @@ unsafe impl<'vm> Sync for WeakGlobalRef<'vm> {}
 
 impl<'vm> JNIEnv<'vm> {
     /// Creates a new local reference to the given reference.
-    pub fn new_local_ref<R: Ref>(&self, r: &R) -> Option<LocalRef<'_>> {
+    pub fn new_local_ref<R: Ref + ?Sized>(&self, r: &R) -> Option<LocalRef<'_>> {
         unsafe {
             #[cfg(debug_assertions)]
             r.enforce_valid_runtime(self);
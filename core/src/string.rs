@@ -1,16 +1,36 @@
-use core::ops::Deref;
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+use core::{char::DecodeUtf16, ffi::CStr, ops::Deref};
+
+#[cfg(feature = "alloc")]
+use crate::{InvalidModifiedUTF8, decode_modified_utf8, encode_modified_utf8, encode_modified_utf8_into};
+use crate::{JNIEnv, LocalRef, Ref, StrongRef, helper::call};
 
-use crate::{JNIEnv, LocalRef, StrongRef, helper::call};
+/// Strings shorter than this in modified UTF-8 are transferred through a stack buffer.
+const STACK_BUFFER_LEN: usize = 256;
 
 #[cfg(feature = "alloc")]
 impl<'vm> JNIEnv<'vm> {
     /// Create new string in jvm.
     pub fn new_string(&self, s: impl AsRef<str>) -> LocalRef<'_> {
-        unsafe {
-            let u16s = s.as_ref().encode_utf16().collect::<alloc::vec::Vec<_>>();
+        let s = s.as_ref();
 
+        let mut buf = [0u8; STACK_BUFFER_LEN];
+        let heap;
+        let utf = match encode_modified_utf8_into(s, &mut buf[..STACK_BUFFER_LEN - 1]) {
+            Some(len) => &buf[..=len],
+            None => {
+                let mut utf = encode_modified_utf8(s);
+                utf.push(0);
+
+                heap = utf;
+                &heap[..]
+            }
+        };
+
+        unsafe {
             let obj = self
-                .run_catch(|| call!(self.as_raw_ptr(), NewString, u16s.as_ptr(), u16s.len() as _))
+                .run_catch(|| call!(self.as_raw_ptr(), NewStringUTF, utf.as_ptr() as _))
                 .expect("BROKEN: Jvm throws exception while creating new string.");
 
             LocalRef::from_raw(self, obj)
@@ -22,24 +42,37 @@ impl<'vm> JNIEnv<'vm> {
     /// # Safety
     ///
     /// The `s` must be a valid string object.
-    pub unsafe fn get_string<R: StrongRef>(&self, s: &R) -> alloc::string::String {
+    pub unsafe fn get_string<R: StrongRef>(&self, s: &R) -> String {
         #[cfg(debug_assertions)]
         s.enforce_valid_runtime(self);
 
-        self.run_catch(|| unsafe {
-            let obj = s.as_raw_ptr();
-
-            let length = call!(self.as_raw_ptr(), GetStringLength, obj);
-            let ptr = call!(self.as_raw_ptr(), GetStringChars, obj, core::ptr::null_mut());
+        unsafe {
+            let length = self.get_string_length(s);
 
-            let ret = alloc::string::String::from_utf16(core::slice::from_raw_parts(ptr, length as _))
-                .expect("BROKEN: Jvm returns invalid UTF-16 string.");
+            // short strings fit the stack buffer in the longest possible encoding, so the modified UTF-8 length
+            // is queried only for longer strings, which are copied into a heap buffer of the exact size.
+            if length as usize * 3 < STACK_BUFFER_LEN {
+                let mut buf = [0u8; STACK_BUFFER_LEN];
+                let len = self
+                    .copy_string_utf_region(s, 0, length, &mut buf)
+                    .expect("BROKEN: Jvm throws exception while getting string.");
 
-            call!(self.as_raw_ptr(), ReleaseStringChars, obj, ptr);
+                decode_modified_utf8(&buf[..len])
+                    .expect("BROKEN: Jvm returns invalid modified UTF-8 string.")
+                    .into_owned()
+            } else {
+                let mut utf = alloc::vec![0u8; self.get_string_utf_length(s) as usize + 1];
+                let len = self
+                    .copy_string_utf_region(s, 0, length, &mut utf)
+                    .expect("BROKEN: Jvm throws exception while getting string.");
+                utf.truncate(len);
 
-            ret
-        })
-        .expect("BROKEN: Jvm throws exception while getting string.")
+                match decode_modified_utf8(&utf).expect("BROKEN: Jvm returns invalid modified UTF-8 string.") {
+                    Cow::Borrowed(_) => String::from_utf8_unchecked(utf),
+                    Cow::Owned(s) => s,
+                }
+            }
+        }
     }
 }
 
@@ -59,6 +92,14 @@ impl<'a, R: StrongRef> Deref for ModifiedUTF8StrGuard<'a, R> {
     }
 }
 
+impl<'a, R: StrongRef> ModifiedUTF8StrGuard<'a, R> {
+    /// Decodes the modified UTF-8 string, borrowing it if no conversion is needed.
+    #[cfg(feature = "alloc")]
+    pub fn to_str(&self) -> Result<Cow<'_, str>, InvalidModifiedUTF8> {
+        decode_modified_utf8(self)
+    }
+}
+
 impl<'a, R: StrongRef> Drop for ModifiedUTF8StrGuard<'a, R> {
     fn drop(&mut self) {
         unsafe {
@@ -78,11 +119,41 @@ impl<'a, R: StrongRef> Drop for ModifiedUTF8StrGuard<'a, R> {
 
 impl<'vm> JNIEnv<'vm> {
     /// Create new modified UTF-8 string in jvm.
+    ///
+    /// The NUL terminator is optional, a terminator is appended if `s` doesn't end with one.
+    /// See [`encode_modified_utf8`](crate::encode_modified_utf8) for encoding a `str`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is not terminated and too long to be terminated on stack without the `alloc` feature.
     pub fn new_modified_utf8_string(&self, s: impl AsRef<[u8]>) -> Result<LocalRef<'_>, LocalRef<'_>> {
-        assert!(s.as_ref().ends_with(b"\0"), "Modified UTF-8 string must be null-terminated.");
+        let s = s.as_ref();
+
+        #[cfg(debug_assertions)]
+        if let Some(Err(err)) = crate::mutf8::decode_units(s.strip_suffix(b"\0").unwrap_or(s)).find(Result::is_err) {
+            panic!("{err}");
+        }
+
+        let mut buf = [0u8; STACK_BUFFER_LEN];
+        #[cfg(feature = "alloc")]
+        let heap;
+        let utf = if s.ends_with(b"\0") {
+            s
+        } else if s.len() < STACK_BUFFER_LEN {
+            buf[..s.len()].copy_from_slice(s);
+            &buf[..=s.len()]
+        } else {
+            #[cfg(feature = "alloc")]
+            {
+                heap = [s, b"\0"].concat();
+                &heap[..]
+            }
+            #[cfg(not(feature = "alloc"))]
+            panic!("Modified UTF-8 string must be null-terminated.")
+        };
 
         unsafe {
-            let obj = self.run_catch(|| call!(self.as_raw_ptr(), NewStringUTF, s.as_ref().as_ptr() as _))?;
+            let obj = self.run_catch(|| call!(self.as_raw_ptr(), NewStringUTF, utf.as_ptr() as _))?;
 
             Ok(LocalRef::from_raw(self, obj))
         }
@@ -113,3 +184,221 @@ impl<'vm> JNIEnv<'vm> {
         .expect("BROKEN: JVM throws exception while getting modified UTF-8 string.")
     }
 }
+
+/// Number of UTF-16 code units copied from the Java VM at once by [`StringUnits`].
+const CHUNK_LEN: usize = 256;
+
+impl<'vm> JNIEnv<'vm> {
+    /// Returns the length of a string in UTF-16 code units.
+    ///
+    /// # Safety
+    ///
+    /// The `s` must be a valid string object.
+    pub unsafe fn get_string_length<R: StrongRef>(&self, s: &R) -> i32 {
+        #[cfg(debug_assertions)]
+        s.enforce_valid_runtime(self);
+
+        self.run_catch(|| unsafe { call!(self.as_raw_ptr(), GetStringLength, s.as_raw_ptr()) })
+            .expect("BROKEN: Jvm throws exception while getting string length.")
+    }
+
+    /// Returns the length of a string in modified UTF-8, excluding the NUL terminator.
+    ///
+    /// # Safety
+    ///
+    /// The `s` must be a valid string object.
+    pub unsafe fn get_string_utf_length<R: StrongRef>(&self, s: &R) -> i32 {
+        #[cfg(debug_assertions)]
+        s.enforce_valid_runtime(self);
+
+        self.run_catch(|| unsafe { call!(self.as_raw_ptr(), GetStringUTFLength, s.as_raw_ptr()) })
+            .expect("BROKEN: Jvm throws exception while getting string UTF length.")
+    }
+
+    /// Copies UTF-16 code units of a string starting at `offset` into `buf`.
+    ///
+    /// Throws `StringIndexOutOfBoundsException` if the region is out of bounds.
+    ///
+    /// # Safety
+    ///
+    /// The `s` must be a valid string object.
+    pub unsafe fn get_string_region<R: StrongRef>(&self, s: &R, offset: i32, buf: &mut [u16]) -> Result<(), LocalRef<'_>> {
+        #[cfg(debug_assertions)]
+        s.enforce_valid_runtime(self);
+
+        self.run_catch(|| unsafe {
+            call!(
+                self.as_raw_ptr(),
+                GetStringRegion,
+                s.as_raw_ptr(),
+                offset,
+                buf.len() as _,
+                buf.as_mut_ptr()
+            )
+        })
+    }
+
+    /// Copies `len` UTF-16 code units of a string starting at `offset` into `buf` in modified UTF-8,
+    /// returns the number of bytes written, excluding the NUL terminator.
+    ///
+    /// Throws `StringIndexOutOfBoundsException` if the region is out of bounds, or `IllegalArgumentException`
+    /// if `buf` is shorter than `len * 3 + 1`, which is the longest possible encoding with the terminator.
+    ///
+    /// # Safety
+    ///
+    /// The `s` must be a valid string object.
+    pub unsafe fn get_string_utf_region<R: StrongRef>(
+        &self,
+        s: &R,
+        offset: i32,
+        len: i32,
+        buf: &mut [u8],
+    ) -> Result<usize, LocalRef<'_>> {
+        #[cfg(debug_assertions)]
+        s.enforce_valid_runtime(self);
+
+        let required = (len.max(0) as usize) * 3 + 1;
+        if buf.len() < required {
+            return Err(self.new_illegal_argument(c"buffer is too short for the string region"));
+        }
+
+        // modified UTF-8 never contains zero bytes, so the length is where the terminator is
+        buf[..required].fill(0);
+
+        unsafe { self.copy_string_utf_region(s, offset, len, buf) }
+    }
+
+    /// Copies a string region into `buf`, which must be zeroed and long enough.
+    unsafe fn copy_string_utf_region<R: StrongRef>(
+        &self,
+        s: &R,
+        offset: i32,
+        len: i32,
+        buf: &mut [u8],
+    ) -> Result<usize, LocalRef<'_>> {
+        self.run_catch(|| unsafe {
+            call!(
+                self.as_raw_ptr(),
+                GetStringUTFRegion,
+                s.as_raw_ptr(),
+                offset,
+                len,
+                buf.as_mut_ptr() as _
+            )
+        })?;
+
+        Ok(buf.iter().position(|&b| b == 0).unwrap_or(buf.len()))
+    }
+
+    fn new_illegal_argument(&self, msg: &CStr) -> LocalRef<'_> {
+        let cls = match self.find_class(c"java/lang/IllegalArgumentException") {
+            Ok(cls) => cls,
+            Err(err) => return err,
+        };
+
+        match self.run_catch(|| unsafe { call!(self.as_raw_ptr(), ThrowNew, cls.as_raw_ptr(), msg.as_ptr()) }) {
+            Ok(_) => panic!("BROKEN: ThrowNew does not throw"),
+            Err(err) => err,
+        }
+    }
+
+    /// Returns an iterator over UTF-16 code units of a string, which are copied in chunks.
+    ///
+    /// # Safety
+    ///
+    /// The `s` must be a valid string object.
+    pub unsafe fn iter_string_units<'a, R: StrongRef>(&'a self, s: &'a R) -> StringUnits<'a, R> {
+        StringUnits {
+            env: self,
+            s,
+            length: unsafe { self.get_string_length(s) },
+            offset: 0,
+            buf: [0; CHUNK_LEN],
+            pos: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns an iterator over chars of a string, which are copied in chunks.
+    ///
+    /// Unpaired surrogates are returned as errors.
+    ///
+    /// # Safety
+    ///
+    /// The `s` must be a valid string object.
+    pub unsafe fn iter_string_chars<'a, R: StrongRef>(&'a self, s: &'a R) -> DecodeUtf16<StringUnits<'a, R>> {
+        char::decode_utf16(unsafe { self.iter_string_units(s) })
+    }
+}
+
+/// An iterator over UTF-16 code units of a string, created by [`JNIEnv::iter_string_units`].
+///
+/// The string is copied into a fixed buffer in chunks, so no allocation is required.
+pub struct StringUnits<'a, R: StrongRef> {
+    env: &'a JNIEnv<'a>,
+    s: &'a R,
+    length: i32,
+    offset: i32,
+    buf: [u16; CHUNK_LEN],
+    pos: usize,
+    filled: usize,
+}
+
+impl<'a, R: StrongRef> StringUnits<'a, R> {
+    fn fill(&mut self) -> bool {
+        if self.pos < self.filled {
+            return true;
+        }
+
+        let len = ((self.length - self.offset) as usize).min(CHUNK_LEN);
+        if len == 0 {
+            return false;
+        }
+
+        unsafe { self.env.get_string_region(self.s, self.offset, &mut self.buf[..len]) }
+            .expect("BROKEN: Jvm throws exception while getting string region.");
+
+        self.offset += len as i32;
+        self.pos = 0;
+        self.filled = len;
+
+        true
+    }
+
+    /// Returns the next chunk of UTF-16 code units, or `None` if the string is exhausted.
+    ///
+    /// A chunk may split a surrogate pair.
+    pub fn next_chunk(&mut self) -> Option<&[u16]> {
+        if !self.fill() {
+            return None;
+        }
+
+        let range = self.pos..self.filled;
+        self.pos = self.filled;
+
+        Some(&self.buf[range])
+    }
+}
+
+impl<'a, R: StrongRef> Iterator for StringUnits<'a, R> {
+    type Item = u16;
+
+    fn next(&mut self) -> Option<u16> {
+        if !self.fill() {
+            return None;
+        }
+
+        let unit = self.buf[self.pos];
+        self.pos += 1;
+
+        Some(unit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.length - self.offset) as usize + (self.filled - self.pos);
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, R: StrongRef> ExactSizeIterator for StringUnits<'a, R> {}
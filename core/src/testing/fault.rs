@@ -0,0 +1,356 @@
+#![allow(non_snake_case)]
+
+use alloc::{borrow::ToOwned, boxed::Box, ffi::CString, string::String, vec::Vec};
+use core::{cell::RefCell, marker::PhantomData, mem::offset_of};
+
+use crate::{JNIEnv, sys};
+
+/// A fault injected into a JNI function call by [`FaultInjector`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// Throws a new exception of `class` (internal name, e.g. `java/lang/IllegalStateException`) with `message`,
+    /// then returns zero or `null` without calling the function.
+    Throw { class: String, message: String },
+    /// Returns zero or `null` without calling the function or throwing an exception.
+    Fail,
+}
+
+impl Fault {
+    /// Creates a fault that throws a new exception of `class` with `message`.
+    pub fn throw(class: &str, message: &str) -> Self {
+        Self::Throw {
+            class: class.to_owned(),
+            message: message.to_owned(),
+        }
+    }
+
+    /// Creates a fault that throws `java.lang.OutOfMemoryError`.
+    pub fn out_of_memory() -> Self {
+        Self::throw("java/lang/OutOfMemoryError", "injected fault")
+    }
+}
+
+/// A rule of injecting a [`Fault`] into calls of a JNI function, added by [`FaultInjector::inject`].
+///
+/// By default the fault is injected into every call of the function.
+#[derive(Debug, Clone)]
+pub struct FaultRule {
+    function: &'static str,
+    fault: Fault,
+    skip: usize,
+    times: Option<usize>,
+    probability: Option<(f64, u64)>,
+    calls: usize,
+    injected: usize,
+}
+
+impl FaultRule {
+    /// Creates a rule that injects `fault` into calls of `function`, e.g. `FindClass` or `CallIntMethodA`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `function` is not an interposable JNI function. Variadic functions (e.g. `CallIntMethod`)
+    /// are not interposable.
+    pub fn new(function: &str, fault: Fault) -> Self {
+        let function = FUNCTIONS
+            .iter()
+            .find(|(_, name)| *name == function)
+            .map(|(_, name)| *name)
+            .unwrap_or_else(|| panic!("unknown JNI function {function}"));
+
+        Self {
+            function,
+            fault,
+            skip: 0,
+            times: None,
+            probability: None,
+            calls: 0,
+            injected: 0,
+        }
+    }
+
+    /// Lets the first `n` calls pass through.
+    pub fn skip(mut self, n: usize) -> Self {
+        self.skip = n;
+        self
+    }
+
+    /// Injects the fault at most `n` times.
+    pub fn times(mut self, n: usize) -> Self {
+        self.times = Some(n);
+        self
+    }
+
+    /// Injects the fault into each call with probability `p`, using a deterministic random generator seeded with `seed`.
+    pub fn probability(mut self, p: f64, seed: u64) -> Self {
+        // xorshift never leaves the zero state
+        self.probability = Some((p, seed.max(1)));
+        self
+    }
+
+    fn check(&mut self) -> bool {
+        self.calls += 1;
+
+        if self.calls <= self.skip || self.times.is_some_and(|times| self.injected >= times) {
+            return false;
+        }
+
+        if let Some((p, state)) = &mut self.probability {
+            *state ^= *state << 13;
+            *state ^= *state >> 7;
+            *state ^= *state << 17;
+
+            if ((*state >> 11) as f64 / (1u64 << 53) as f64) >= *p {
+                return false;
+            }
+        }
+
+        self.injected += 1;
+
+        true
+    }
+}
+
+#[repr(C)]
+struct Raw {
+    // `&env` is the `JNIEnv *` of the injector.
+    env: *const sys::JNINativeInterface_,
+    inner: *mut sys::JNIEnv,
+    table: sys::JNINativeInterface_,
+    rules: RefCell<Vec<FaultRule>>,
+}
+
+impl Raw {
+    fn check(&self, offset: usize) -> Option<Fault> {
+        let mut rules = self.rules.borrow_mut();
+        if rules.is_empty() {
+            return None;
+        }
+
+        let (_, function) = FUNCTIONS
+            .iter()
+            .find(|(o, _)| *o == offset)
+            .expect("BROKEN: interposed function not found");
+
+        let mut fault = None;
+        for rule in rules.iter_mut().filter(|rule| rule.function == *function) {
+            if fault.is_some() {
+                rule.calls += 1;
+            } else if rule.check() {
+                fault = Some(rule.fault.clone());
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        if let Some(fault) = &fault {
+            tracing::debug!(function = *function, ?fault, "fault injected");
+        }
+
+        fault
+    }
+
+    unsafe fn inject(&self, fault: &Fault) {
+        let Fault::Throw { class, message } = fault else {
+            return;
+        };
+
+        unsafe {
+            let class = CString::new(class.as_str()).expect("BROKEN: exception class contains nul");
+            let message = CString::new(message.as_str()).expect("BROKEN: exception message contains nul");
+
+            let cls = (**self.inner)
+                .FindClass
+                .expect("BROKEN: function JNIEnv::FindClass undefined")(self.inner, class.as_ptr());
+            assert!(!cls.is_null(), "BROKEN: exception class {class:?} not found");
+
+            (**self.inner).ThrowNew.expect("BROKEN: function JNIEnv::ThrowNew undefined")(self.inner, cls, message.as_ptr());
+            (**self.inner)
+                .DeleteLocalRef
+                .expect("BROKEN: function JNIEnv::DeleteLocalRef undefined")(self.inner, cls);
+        }
+    }
+}
+
+unsafe fn raw<'a>(env: *mut sys::JNIEnv) -> &'a Raw {
+    // `env` points to the first field of `Raw`
+    unsafe { &*(env as *const Raw) }
+}
+
+trait Interpose {
+    fn interpose<const OFFSET: usize>() -> Self;
+}
+
+macro_rules! impl_interpose {
+    ($($arg:ident: $typ:ident),*) => {
+        impl<$($typ: 'static,)* R: 'static> Interpose for Option<unsafe extern "system" fn(*mut sys::JNIEnv, $($typ),*) -> R> {
+            fn interpose<const OFFSET: usize>() -> Self {
+                unsafe extern "system" fn trampoline<const OFFSET: usize, $($typ,)* R>(env: *mut sys::JNIEnv, $($arg: $typ),*) -> R {
+                    unsafe {
+                        let raw = raw(env);
+
+                        if let Some(fault) = raw.check(OFFSET) {
+                            raw.inject(&fault);
+
+                            // all JNI return types are integers, pointers or void
+                            return core::mem::zeroed();
+                        }
+
+                        let func = *((*raw.inner) as *const u8).add(OFFSET)
+                            .cast::<Option<unsafe extern "system" fn(*mut sys::JNIEnv, $($typ),*) -> R>>();
+
+                        func.expect("BROKEN: interposed function undefined")(raw.inner, $($arg),*)
+                    }
+                }
+
+                Some(trampoline::<OFFSET, $($typ,)* R>)
+            }
+        }
+    };
+}
+
+impl_interpose!();
+impl_interpose!(a: A);
+impl_interpose!(a: A, b: B);
+impl_interpose!(a: A, b: B, c: C);
+impl_interpose!(a: A, b: B, c: C, d: D);
+impl_interpose!(a: A, b: B, c: C, d: D, e: E);
+
+macro_rules! interposable {
+    (variadic: [$($variadic:ident),* $(,)?], interposed: [$($name:ident),* $(,)?] $(,)?) => {
+        const FUNCTIONS: &[(usize, &str)] = &[$((offset_of!(sys::JNINativeInterface_, $name), stringify!($name))),*];
+
+        fn interpose_table(inner: &sys::JNINativeInterface_) -> sys::JNINativeInterface_ {
+            let mut table = *inner;
+
+            $(table.$variadic = None;)*
+            $(table.$name = table.$name.and(Interpose::interpose::<{ offset_of!(sys::JNINativeInterface_, $name) }>());)*
+
+            table
+        }
+    };
+}
+
+interposable!(
+    variadic: [
+        NewObject, CallObjectMethod, CallBooleanMethod, CallByteMethod, CallCharMethod, CallShortMethod, CallIntMethod,
+        CallLongMethod, CallFloatMethod, CallDoubleMethod, CallVoidMethod, CallNonvirtualObjectMethod,
+        CallNonvirtualBooleanMethod, CallNonvirtualByteMethod, CallNonvirtualCharMethod, CallNonvirtualShortMethod,
+        CallNonvirtualIntMethod, CallNonvirtualLongMethod, CallNonvirtualFloatMethod, CallNonvirtualDoubleMethod,
+        CallNonvirtualVoidMethod, CallStaticObjectMethod, CallStaticBooleanMethod, CallStaticByteMethod,
+        CallStaticCharMethod, CallStaticShortMethod, CallStaticIntMethod, CallStaticLongMethod, CallStaticFloatMethod,
+        CallStaticDoubleMethod, CallStaticVoidMethod,
+    ],
+    interposed: [
+        GetVersion, DefineClass, FindClass, FromReflectedMethod, FromReflectedField, ToReflectedMethod, GetSuperclass,
+        IsAssignableFrom, ToReflectedField, Throw, ThrowNew, ExceptionOccurred, ExceptionDescribe, ExceptionClear, FatalError,
+        PushLocalFrame, PopLocalFrame, NewGlobalRef, DeleteGlobalRef, DeleteLocalRef, IsSameObject, NewLocalRef,
+        EnsureLocalCapacity, AllocObject, NewObjectV, NewObjectA, GetObjectClass, IsInstanceOf, GetMethodID,
+        CallObjectMethodV, CallObjectMethodA, CallBooleanMethodV, CallBooleanMethodA, CallByteMethodV, CallByteMethodA,
+        CallCharMethodV, CallCharMethodA, CallShortMethodV, CallShortMethodA, CallIntMethodV, CallIntMethodA,
+        CallLongMethodV, CallLongMethodA, CallFloatMethodV, CallFloatMethodA, CallDoubleMethodV, CallDoubleMethodA,
+        CallVoidMethodV, CallVoidMethodA, CallNonvirtualObjectMethodV, CallNonvirtualObjectMethodA,
+        CallNonvirtualBooleanMethodV, CallNonvirtualBooleanMethodA, CallNonvirtualByteMethodV, CallNonvirtualByteMethodA,
+        CallNonvirtualCharMethodV, CallNonvirtualCharMethodA, CallNonvirtualShortMethodV, CallNonvirtualShortMethodA,
+        CallNonvirtualIntMethodV, CallNonvirtualIntMethodA, CallNonvirtualLongMethodV, CallNonvirtualLongMethodA,
+        CallNonvirtualFloatMethodV, CallNonvirtualFloatMethodA, CallNonvirtualDoubleMethodV, CallNonvirtualDoubleMethodA,
+        CallNonvirtualVoidMethodV, CallNonvirtualVoidMethodA, GetFieldID, GetObjectField, GetBooleanField, GetByteField,
+        GetCharField, GetShortField, GetIntField, GetLongField, GetFloatField, GetDoubleField, SetObjectField,
+        SetBooleanField, SetByteField, SetCharField, SetShortField, SetIntField, SetLongField, SetFloatField,
+        SetDoubleField, GetStaticMethodID, CallStaticObjectMethodV, CallStaticObjectMethodA, CallStaticBooleanMethodV,
+        CallStaticBooleanMethodA, CallStaticByteMethodV, CallStaticByteMethodA, CallStaticCharMethodV,
+        CallStaticCharMethodA, CallStaticShortMethodV, CallStaticShortMethodA, CallStaticIntMethodV, CallStaticIntMethodA,
+        CallStaticLongMethodV, CallStaticLongMethodA, CallStaticFloatMethodV, CallStaticFloatMethodA,
+        CallStaticDoubleMethodV, CallStaticDoubleMethodA, CallStaticVoidMethodV, CallStaticVoidMethodA, GetStaticFieldID,
+        GetStaticObjectField, GetStaticBooleanField, GetStaticByteField, GetStaticCharField, GetStaticShortField,
+        GetStaticIntField, GetStaticLongField, GetStaticFloatField, GetStaticDoubleField, SetStaticObjectField,
+        SetStaticBooleanField, SetStaticByteField, SetStaticCharField, SetStaticShortField, SetStaticIntField,
+        SetStaticLongField, SetStaticFloatField, SetStaticDoubleField, NewString, GetStringLength, GetStringChars,
+        ReleaseStringChars, NewStringUTF, GetStringUTFLength, GetStringUTFChars, ReleaseStringUTFChars, GetArrayLength,
+        NewObjectArray, GetObjectArrayElement, SetObjectArrayElement, NewBooleanArray, NewByteArray, NewCharArray,
+        NewShortArray, NewIntArray, NewLongArray, NewFloatArray, NewDoubleArray, GetBooleanArrayElements,
+        GetByteArrayElements, GetCharArrayElements, GetShortArrayElements, GetIntArrayElements, GetLongArrayElements,
+        GetFloatArrayElements, GetDoubleArrayElements, ReleaseBooleanArrayElements, ReleaseByteArrayElements,
+        ReleaseCharArrayElements, ReleaseShortArrayElements, ReleaseIntArrayElements, ReleaseLongArrayElements,
+        ReleaseFloatArrayElements, ReleaseDoubleArrayElements, GetBooleanArrayRegion, GetByteArrayRegion,
+        GetCharArrayRegion, GetShortArrayRegion, GetIntArrayRegion, GetLongArrayRegion, GetFloatArrayRegion,
+        GetDoubleArrayRegion, SetBooleanArrayRegion, SetByteArrayRegion, SetCharArrayRegion, SetShortArrayRegion,
+        SetIntArrayRegion, SetLongArrayRegion, SetFloatArrayRegion, SetDoubleArrayRegion, RegisterNatives,
+        UnregisterNatives, MonitorEnter, MonitorExit, GetJavaVM, GetStringRegion, GetStringUTFRegion,
+        GetPrimitiveArrayCritical, ReleasePrimitiveArrayCritical, GetStringCritical, ReleaseStringCritical,
+        NewWeakGlobalRef, DeleteWeakGlobalRef, ExceptionCheck, NewDirectByteBuffer, GetDirectBufferAddress,
+        GetDirectBufferCapacity, GetObjectRefType, GetModule, IsVirtualThread,
+    ],
+);
+
+/// An interposition layer over a JNI environment that injects [`Fault`]s into chosen JNI functions,
+/// enabled by the `testing` feature.
+///
+/// Calls made through [`FaultInjector::env`] are forwarded to the wrapped environment, unless a [`FaultRule`]
+/// fires. It works with both a real Java VM and [`FakeJvm`](super::FakeJvm), so error paths built on
+/// [`JNIEnv::run_catch`] can be exercised end to end.
+///
+/// Only calls made through the returned environment are interposed, calls from other threads, native methods
+/// and reference drops on other threads go to the Java VM directly. Variadic functions are left undefined
+/// and panic when called.
+///
+/// Injecting a fault into functions whose result is trusted (e.g. a `null` from `GetStringUTFChars`)
+/// may crash the Java VM.
+///
+/// # Example
+///
+/// ```rust
+/// use typed_jni_core::testing::{FakeJvm, Fault, FaultInjector, FaultRule};
+///
+/// let jvm = FakeJvm::new();
+/// let faults = FaultInjector::new(jvm.env());
+/// faults.inject(FaultRule::new("FindClass", Fault::throw("java/lang/NoClassDefFoundError", "injected")).times(1));
+///
+/// let env = faults.env();
+/// assert!(env.find_class(c"java/lang/String").is_err());
+/// assert!(env.find_class(c"java/lang/String").is_ok());
+/// assert_eq!(faults.injected(), 1);
+/// ```
+pub struct FaultInjector<'env> {
+    raw: Box<Raw>,
+    _env: PhantomData<&'env JNIEnv<'env>>,
+}
+
+impl<'env> FaultInjector<'env> {
+    /// Creates an injector wrapping `env`, which injects no faults until rules are added.
+    pub fn new(env: &'env JNIEnv) -> Self {
+        let inner = env.as_raw_ptr();
+
+        let mut raw = Box::new(Raw {
+            env: core::ptr::null(),
+            inner,
+            table: interpose_table(unsafe { &**inner }),
+            rules: RefCell::new(Vec::new()),
+        });
+        raw.env = &raw.table;
+
+        Self { raw, _env: PhantomData }
+    }
+
+    /// Returns the interposed JNI environment.
+    pub fn env(&self) -> &JNIEnv<'env> {
+        unsafe { JNIEnv::from_raw(&self.raw.env as *const _ as *mut sys::JNIEnv) }
+    }
+
+    /// Adds a rule.
+    ///
+    /// Rules are checked in the order they were added and each rule counts every call of its function,
+    /// the fault of the first firing rule is injected.
+    pub fn inject(&self, rule: FaultRule) {
+        self.raw.rules.borrow_mut().push(rule);
+    }
+
+    /// Removes all rules.
+    pub fn clear(&self) {
+        self.raw.rules.borrow_mut().clear();
+    }
+
+    /// Returns the number of faults injected by the current rules.
+    pub fn injected(&self) -> usize {
+        self.raw.rules.borrow().iter().map(|rule| rule.injected).sum()
+    }
+}
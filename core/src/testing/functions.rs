@@ -0,0 +1,1505 @@
+#![allow(non_snake_case)]
+
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
+use core::{ffi::CStr, panic::AssertUnwindSafe};
+use std::{eprintln, panic::catch_unwind};
+
+use super::{
+    FakeValue, Raw,
+    state::{Buffer, MethodImpl, ObjId, Object, RefKind, State, Val, parse_method_signature},
+};
+use crate::{JNIEnv, LocalRef, Ref, sys};
+
+pub(super) struct NativeInterface(pub sys::JNINativeInterface_);
+
+unsafe impl Sync for NativeInterface {}
+
+pub(super) struct InvokeInterface(pub sys::JNIInvokeInterface_);
+
+unsafe impl Sync for InvokeInterface {}
+
+unsafe fn raw<'a>(env: *mut sys::JNIEnv) -> &'a Raw {
+    // `env` points to the first field of `Raw`
+    unsafe { &*(env as *const Raw) }
+}
+
+unsafe fn raw_of_vm<'a>(vm: *mut sys::JavaVM) -> &'a Raw {
+    unsafe { &*((vm as *const u8).sub(core::mem::offset_of!(Raw, vm)) as *const Raw) }
+}
+
+unsafe fn to_str<'a>(s: *const core::ffi::c_char) -> &'a str {
+    unsafe {
+        CStr::from_ptr(s)
+            .to_str()
+            .unwrap_or_else(|_| panic!("fake JNI: non UTF-8 name {:?}", CStr::from_ptr(s)))
+    }
+}
+
+fn method_index(method: sys::jmethodID) -> usize {
+    (method as usize).checked_sub(1).expect("fake JNI: null method id")
+}
+
+fn field_index(field: sys::jfieldID) -> usize {
+    (field as usize).checked_sub(1).expect("fake JNI: null field id")
+}
+
+trait Primitive: Copy + Default + Send + 'static {
+    const SIG: &'static str;
+
+    fn into_val(self) -> Val;
+
+    fn from_val(v: Val) -> Self;
+}
+
+macro_rules! impl_primitive {
+    ($typ:ty, $sig:literal, $variant:ident) => {
+        impl Primitive for $typ {
+            const SIG: &'static str = $sig;
+
+            fn into_val(self) -> Val {
+                Val::$variant(self)
+            }
+
+            fn from_val(v: Val) -> Self {
+                match v {
+                    Val::$variant(v) => v,
+                    v => panic!("fake JNI: {v:?} is not a value of {}", $sig),
+                }
+            }
+        }
+    };
+}
+
+impl_primitive!(bool, "Z", Boolean);
+impl_primitive!(i8, "B", Byte);
+impl_primitive!(u16, "C", Char);
+impl_primitive!(i16, "S", Short);
+impl_primitive!(i32, "I", Int);
+impl_primitive!(i64, "J", Long);
+impl_primitive!(f32, "F", Float);
+impl_primitive!(f64, "D", Double);
+
+fn read_arg(state: &State, sig: &str, value: sys::jvalue) -> Val {
+    unsafe {
+        match sig.as_bytes()[0] {
+            b'Z' => Val::Boolean(value.z),
+            b'B' => Val::Byte(value.b),
+            b'C' => Val::Char(value.c),
+            b'S' => Val::Short(value.s),
+            b'I' => Val::Int(value.i),
+            b'J' => Val::Long(value.j),
+            b'F' => Val::Float(value.f),
+            b'D' => Val::Double(value.d),
+            _ => Val::Object(state.resolve(value.l)),
+        }
+    }
+}
+
+fn matches_signature(value: &FakeValue, sig: &str) -> bool {
+    matches!(
+        (value, sig.as_bytes()[0]),
+        (FakeValue::Void, b'V')
+            | (FakeValue::Boolean(_), b'Z')
+            | (FakeValue::Byte(_), b'B')
+            | (FakeValue::Char(_), b'C')
+            | (FakeValue::Short(_), b'S')
+            | (FakeValue::Int(_), b'I')
+            | (FakeValue::Long(_), b'J')
+            | (FakeValue::Float(_), b'F')
+            | (FakeValue::Double(_), b'D')
+            | (FakeValue::Object(_), b'L' | b'[')
+    )
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Invoke {
+    Virtual,
+    Static,
+    Constructor,
+}
+
+/// Invokes `method`, returns `None` if an exception is thrown.
+unsafe fn invoke(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    obj: sys::jobject,
+    method: sys::jmethodID,
+    args: *const sys::jvalue,
+    kind: Invoke,
+) -> Option<Val> {
+    let raw = unsafe { raw(env) };
+    let mut state = raw.lock();
+
+    let method = method_index(method);
+    let description = state.describe_method(method);
+    state.record(function, Some(description.clone()));
+
+    let (arg_sigs, ret_sig) = parse_method_signature(&state.method(method).sig).expect("BROKEN: invalid method signature");
+    let ret_sig = String::from(ret_sig);
+    let args = arg_sigs
+        .iter()
+        .enumerate()
+        .map(|(idx, sig)| read_arg(&state, sig, unsafe { *args.add(idx) }))
+        .collect::<Vec<_>>();
+
+    let (this, method) = match (kind, state.resolve(obj)) {
+        (_, None) => {
+            state.throw_new("java/lang/NullPointerException", &format!("invoke {description} on null"));
+
+            return None;
+        }
+        (Invoke::Virtual, Some(this)) => (this, state.dispatch(state.class_of(this), method)),
+        (Invoke::Static, Some(cls)) => (cls, method),
+        (Invoke::Constructor, Some(cls)) => {
+            let class = state.as_class(cls);
+
+            (state.alloc(class), method)
+        }
+    };
+
+    let ret = match state.method(method).imp.clone() {
+        MethodImpl::Abstract => {
+            state.throw_new("java/lang/AbstractMethodError", &description);
+
+            return None;
+        }
+        MethodImpl::Builtin(f) => match f(&mut state, this, &args) {
+            Ok(v) => v,
+            Err(ex) => {
+                state.pending = Some(ex);
+
+                return None;
+            }
+        },
+        MethodImpl::Fake(f) => {
+            // runs the method in a new local frame, like a native method called by the Java VM
+            state.push_frame();
+
+            let this = state.new_ref(Some(this), RefKind::Local);
+            let args = args
+                .iter()
+                .map(|arg| match arg {
+                    Val::Object(obj) => Err(state.new_ref(*obj, RefKind::Local)),
+                    v => Ok(*v),
+                })
+                .collect::<Vec<_>>();
+
+            drop(state);
+
+            let env = unsafe { JNIEnv::from_raw(env) };
+            let ret = {
+                let this = unsafe { LocalRef::from_raw(env, this) };
+                let args = args
+                    .into_iter()
+                    .map(|arg| match arg {
+                        Ok(Val::Boolean(v)) => FakeValue::Boolean(v),
+                        Ok(Val::Byte(v)) => FakeValue::Byte(v),
+                        Ok(Val::Char(v)) => FakeValue::Char(v),
+                        Ok(Val::Short(v)) => FakeValue::Short(v),
+                        Ok(Val::Int(v)) => FakeValue::Int(v),
+                        Ok(Val::Long(v)) => FakeValue::Long(v),
+                        Ok(Val::Float(v)) => FakeValue::Float(v),
+                        Ok(Val::Double(v)) => FakeValue::Double(v),
+                        Ok(Val::Object(_)) => unreachable!(),
+                        Err(obj) => FakeValue::Object((!obj.is_null()).then(|| unsafe { LocalRef::from_raw(env, obj) })),
+                    })
+                    .collect::<Vec<_>>();
+
+                // the lock is released before dropping local references
+                match catch_unwind(AssertUnwindSafe(|| f(env, &this, &args))) {
+                    Ok(Ok(ret)) if matches_signature(&ret, &ret_sig) => Ok(match ret {
+                        FakeValue::Void => Val::Object(None),
+                        FakeValue::Boolean(v) => Val::Boolean(v),
+                        FakeValue::Byte(v) => Val::Byte(v),
+                        FakeValue::Char(v) => Val::Char(v),
+                        FakeValue::Short(v) => Val::Short(v),
+                        FakeValue::Int(v) => Val::Int(v),
+                        FakeValue::Long(v) => Val::Long(v),
+                        FakeValue::Float(v) => Val::Float(v),
+                        FakeValue::Double(v) => Val::Double(v),
+                        FakeValue::Object(v) => Val::Object(v.and_then(|v| raw.lock().resolve(v.as_raw_ptr()))),
+                    }),
+                    Ok(Ok(ret)) => Err(Err(format!("fake method {description} returns {ret:?}"))),
+                    Ok(Err(ex)) => Err(Ok(raw.lock().resolve(ex.as_raw_ptr()).expect("BROKEN: null throwable"))),
+                    Err(err) => {
+                        let msg = if let Some(msg) = err.downcast_ref::<&str>() {
+                            msg
+                        } else if let Some(msg) = err.downcast_ref::<String>() {
+                            msg.as_str()
+                        } else {
+                            "unknown"
+                        };
+
+                        Err(Err(format!("rust panic: {msg}")))
+                    }
+                }
+            };
+
+            let mut state = raw.lock();
+            assert!(state.pop_frame(), "BROKEN: local frame of fake method is popped");
+
+            match ret {
+                Ok(ret) => ret,
+                Err(Ok(ex)) => {
+                    state.pending = Some(ex);
+
+                    return None;
+                }
+                Err(Err(msg)) => {
+                    state.throw_new("java/lang/Error", &msg);
+
+                    return None;
+                }
+            }
+        }
+    };
+
+    if kind == Invoke::Constructor {
+        Some(Val::Object(Some(this)))
+    } else {
+        Some(ret)
+    }
+}
+
+// version, classes and exceptions
+
+unsafe extern "system" fn GetVersion(env: *mut sys::JNIEnv) -> sys::jint {
+    unsafe { raw(env) }.lock().record("GetVersion", None);
+
+    sys::JNI_VERSION_1_8
+}
+
+unsafe extern "system" fn FindClass(env: *mut sys::JNIEnv, name: *const core::ffi::c_char) -> sys::jclass {
+    let mut state = unsafe { raw(env) }.lock();
+    let name = unsafe { to_str(name) };
+
+    state.record("FindClass", Some(name.into()));
+
+    match state.find_class(name) {
+        Some(class) => {
+            let object = state.class(class).object;
+
+            state.new_ref(Some(object), RefKind::Local)
+        }
+        None => {
+            state.throw_new("java/lang/NoClassDefFoundError", name);
+
+            core::ptr::null_mut()
+        }
+    }
+}
+
+unsafe extern "system" fn GetSuperclass(env: *mut sys::JNIEnv, sub: sys::jclass) -> sys::jclass {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetSuperclass", None);
+
+    let class = state.as_class(state.resolve(sub).expect("fake JNI: null class"));
+    let superclass = state.class(class).superclass.map(|c| state.class(c).object);
+
+    state.new_ref(superclass, RefKind::Local)
+}
+
+unsafe extern "system" fn IsAssignableFrom(env: *mut sys::JNIEnv, sub: sys::jclass, sup: sys::jclass) -> sys::jboolean {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("IsAssignableFrom", None);
+
+    let sub = state.as_class(state.resolve(sub).expect("fake JNI: null class"));
+    let sup = state.as_class(state.resolve(sup).expect("fake JNI: null class"));
+
+    state.is_assignable(sub, sup)
+}
+
+unsafe extern "system" fn Throw(env: *mut sys::JNIEnv, obj: sys::jthrowable) -> sys::jint {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("Throw", None);
+    state.pending = Some(state.resolve(obj).expect("fake JNI: throw null"));
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn ThrowNew(env: *mut sys::JNIEnv, clazz: sys::jclass, msg: *const core::ffi::c_char) -> sys::jint {
+    let mut state = unsafe { raw(env) }.lock();
+
+    let class = state.as_class(state.resolve(clazz).expect("fake JNI: null class"));
+    let name = state.class(class).name.clone();
+    state.record("ThrowNew", Some(name));
+
+    let msg = (!msg.is_null()).then(|| unsafe { CStr::from_ptr(msg) }.to_string_lossy());
+    state.throw_new_in(class, msg.as_deref());
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn ExceptionOccurred(env: *mut sys::JNIEnv) -> sys::jthrowable {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("ExceptionOccurred", None);
+
+    let pending = state.pending;
+    state.new_ref(pending, RefKind::Local)
+}
+
+unsafe extern "system" fn ExceptionDescribe(env: *mut sys::JNIEnv) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("ExceptionDescribe", None);
+
+    if let Some(ex) = state.pending.take() {
+        let class = state.class(state.class_of(ex)).name.replace('/', ".");
+
+        match state.message_of(ex) {
+            Some(msg) => eprintln!("{class}: {msg}"),
+            None => eprintln!("{class}"),
+        }
+    }
+}
+
+unsafe extern "system" fn ExceptionClear(env: *mut sys::JNIEnv) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("ExceptionClear", None);
+    state.pending = None;
+}
+
+unsafe extern "system" fn ExceptionCheck(env: *mut sys::JNIEnv) -> sys::jboolean {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("ExceptionCheck", None);
+    state.pending.is_some()
+}
+
+// references
+
+unsafe extern "system" fn PushLocalFrame(env: *mut sys::JNIEnv, _capacity: sys::jint) -> sys::jint {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("PushLocalFrame", None);
+    state.push_frame();
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn PopLocalFrame(env: *mut sys::JNIEnv, result: sys::jobject) -> sys::jobject {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("PopLocalFrame", None);
+
+    let result = state.resolve(result);
+    assert!(state.pop_frame(), "fake JNI: pop local frame without push");
+
+    state.new_ref(result, RefKind::Local)
+}
+
+unsafe extern "system" fn EnsureLocalCapacity(env: *mut sys::JNIEnv, _capacity: sys::jint) -> sys::jint {
+    unsafe { raw(env) }.lock().record("EnsureLocalCapacity", None);
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn NewLocalRef(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jobject {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("NewLocalRef", None);
+
+    let obj = state.resolve(obj);
+    state.new_ref(obj, RefKind::Local)
+}
+
+unsafe extern "system" fn DeleteLocalRef(env: *mut sys::JNIEnv, obj: sys::jobject) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("DeleteLocalRef", None);
+    state.delete_ref(obj, RefKind::Local);
+}
+
+unsafe extern "system" fn NewGlobalRef(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jobject {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("NewGlobalRef", None);
+
+    let obj = state.resolve(obj);
+    state.new_ref(obj, RefKind::Global)
+}
+
+unsafe extern "system" fn DeleteGlobalRef(env: *mut sys::JNIEnv, obj: sys::jobject) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("DeleteGlobalRef", None);
+    state.delete_ref(obj, RefKind::Global);
+}
+
+unsafe extern "system" fn NewWeakGlobalRef(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jweak {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("NewWeakGlobalRef", None);
+
+    let obj = state.resolve(obj);
+    state.new_ref(obj, RefKind::WeakGlobal)
+}
+
+unsafe extern "system" fn DeleteWeakGlobalRef(env: *mut sys::JNIEnv, obj: sys::jweak) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("DeleteWeakGlobalRef", None);
+    state.delete_ref(obj, RefKind::WeakGlobal);
+}
+
+unsafe extern "system" fn GetObjectRefType(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jobjectRefType {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetObjectRefType", None);
+
+    match state.ref_kind(obj) {
+        Some(RefKind::Local) => sys::jobjectRefType::JNILocalRefType,
+        Some(RefKind::Global) => sys::jobjectRefType::JNIGlobalRefType,
+        Some(RefKind::WeakGlobal) => sys::jobjectRefType::JNIWeakGlobalRefType,
+        None => sys::jobjectRefType::JNIInvalidRefType,
+    }
+}
+
+unsafe extern "system" fn IsSameObject(env: *mut sys::JNIEnv, obj1: sys::jobject, obj2: sys::jobject) -> sys::jboolean {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("IsSameObject", None);
+    state.resolve(obj1) == state.resolve(obj2)
+}
+
+// objects
+
+unsafe extern "system" fn AllocObject(env: *mut sys::JNIEnv, clazz: sys::jclass) -> sys::jobject {
+    let mut state = unsafe { raw(env) }.lock();
+
+    let class = state.as_class(state.resolve(clazz).expect("fake JNI: null class"));
+    let name = state.class(class).name.clone();
+    state.record("AllocObject", Some(name));
+
+    let obj = state.alloc(class);
+    state.new_ref(Some(obj), RefKind::Local)
+}
+
+unsafe extern "system" fn NewObjectA(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    method: sys::jmethodID,
+    args: *const sys::jvalue,
+) -> sys::jobject {
+    let obj = unsafe { invoke(env, "NewObjectA", clazz, method, args, Invoke::Constructor) };
+
+    unsafe { raw(env) }
+        .lock()
+        .new_ref(obj.and_then(Val::as_object), RefKind::Local)
+}
+
+unsafe extern "system" fn GetObjectClass(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jclass {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetObjectClass", None);
+
+    let class = state.class_of(state.resolve(obj).expect("fake JNI: get class of null"));
+    let object = state.class(class).object;
+
+    state.new_ref(Some(object), RefKind::Local)
+}
+
+unsafe extern "system" fn IsInstanceOf(env: *mut sys::JNIEnv, obj: sys::jobject, clazz: sys::jclass) -> sys::jboolean {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("IsInstanceOf", None);
+
+    let class = state.as_class(state.resolve(clazz).expect("fake JNI: null class"));
+
+    // null is an instance of any class in JNI
+    state
+        .resolve(obj)
+        .is_none_or(|obj| state.is_assignable(state.class_of(obj), class))
+}
+
+unsafe extern "system" fn MonitorEnter(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jint {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("MonitorEnter", None);
+
+    let obj = state.resolve(obj).expect("fake JNI: enter monitor of null");
+    state.monitor_enter(obj);
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn MonitorExit(env: *mut sys::JNIEnv, obj: sys::jobject) -> sys::jint {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("MonitorExit", None);
+
+    let obj = state.resolve(obj).expect("fake JNI: exit monitor of null");
+    if state.monitor_exit(obj) {
+        sys::JNI_OK
+    } else {
+        state.throw_new("java/lang/IllegalMonitorStateException", "current thread is not owner");
+
+        sys::JNI_ERR
+    }
+}
+
+// members
+
+unsafe fn get_member_id(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    clazz: sys::jclass,
+    name: *const core::ffi::c_char,
+    sig: *const core::ffi::c_char,
+    find: fn(&State, usize, &str, &str) -> Option<usize>,
+    error: &str,
+) -> usize {
+    // described like `State::describe_method` and `State::describe_field`
+    let separator = if error == "java/lang/NoSuchFieldError" { ":" } else { "" };
+
+    let mut state = unsafe { raw(env) }.lock();
+    let (name, sig) = unsafe { (to_str(name), to_str(sig)) };
+
+    let class = state.as_class(state.resolve(clazz).expect("fake JNI: null class"));
+    let description = format!("{}.{name}{separator}{sig}", state.class(class).name);
+    state.record(function, Some(description.clone()));
+
+    match find(&state, class, name, sig) {
+        Some(id) => id + 1,
+        None => {
+            state.throw_new(error, &description);
+
+            0
+        }
+    }
+}
+
+unsafe extern "system" fn GetMethodID(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    name: *const core::ffi::c_char,
+    sig: *const core::ffi::c_char,
+) -> sys::jmethodID {
+    let find = |state: &State, class, name: &str, sig: &str| state.find_method(class, name, sig, false);
+
+    unsafe { get_member_id(env, "GetMethodID", clazz, name, sig, find, "java/lang/NoSuchMethodError") as _ }
+}
+
+unsafe extern "system" fn GetStaticMethodID(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    name: *const core::ffi::c_char,
+    sig: *const core::ffi::c_char,
+) -> sys::jmethodID {
+    let find = |state: &State, class, name: &str, sig: &str| state.find_method(class, name, sig, true);
+
+    unsafe {
+        get_member_id(
+            env,
+            "GetStaticMethodID",
+            clazz,
+            name,
+            sig,
+            find,
+            "java/lang/NoSuchMethodError",
+        ) as _
+    }
+}
+
+unsafe extern "system" fn GetFieldID(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    name: *const core::ffi::c_char,
+    sig: *const core::ffi::c_char,
+) -> sys::jfieldID {
+    let find = |state: &State, class, name: &str, sig: &str| state.find_field(class, name, sig, false);
+
+    unsafe { get_member_id(env, "GetFieldID", clazz, name, sig, find, "java/lang/NoSuchFieldError") as _ }
+}
+
+unsafe extern "system" fn GetStaticFieldID(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    name: *const core::ffi::c_char,
+    sig: *const core::ffi::c_char,
+) -> sys::jfieldID {
+    let find = |state: &State, class, name: &str, sig: &str| state.find_field(class, name, sig, true);
+
+    unsafe { get_member_id(env, "GetStaticFieldID", clazz, name, sig, find, "java/lang/NoSuchFieldError") as _ }
+}
+
+// method calls
+
+macro_rules! define_call_functions {
+    ($(($typ:ty, $call:ident, $call_static:ident)),* $(,)?) => {
+        $(
+            unsafe extern "system" fn $call(
+                env: *mut sys::JNIEnv,
+                obj: sys::jobject,
+                method: sys::jmethodID,
+                args: *const sys::jvalue,
+            ) -> $typ {
+                unsafe { invoke(env, stringify!($call), obj, method, args, Invoke::Virtual) }.map_or_else(Default::default, <$typ>::from_val)
+            }
+
+            unsafe extern "system" fn $call_static(
+                env: *mut sys::JNIEnv,
+                clazz: sys::jclass,
+                method: sys::jmethodID,
+                args: *const sys::jvalue,
+            ) -> $typ {
+                unsafe { invoke(env, stringify!($call_static), clazz, method, args, Invoke::Static) }.map_or_else(Default::default, <$typ>::from_val)
+            }
+        )*
+    };
+}
+
+define_call_functions!(
+    (bool, CallBooleanMethodA, CallStaticBooleanMethodA),
+    (i8, CallByteMethodA, CallStaticByteMethodA),
+    (u16, CallCharMethodA, CallStaticCharMethodA),
+    (i16, CallShortMethodA, CallStaticShortMethodA),
+    (i32, CallIntMethodA, CallStaticIntMethodA),
+    (i64, CallLongMethodA, CallStaticLongMethodA),
+    (f32, CallFloatMethodA, CallStaticFloatMethodA),
+    (f64, CallDoubleMethodA, CallStaticDoubleMethodA),
+);
+
+unsafe extern "system" fn CallVoidMethodA(
+    env: *mut sys::JNIEnv,
+    obj: sys::jobject,
+    method: sys::jmethodID,
+    args: *const sys::jvalue,
+) {
+    unsafe { invoke(env, "CallVoidMethodA", obj, method, args, Invoke::Virtual) };
+}
+
+unsafe extern "system" fn CallStaticVoidMethodA(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    method: sys::jmethodID,
+    args: *const sys::jvalue,
+) {
+    unsafe { invoke(env, "CallStaticVoidMethodA", clazz, method, args, Invoke::Static) };
+}
+
+unsafe extern "system" fn CallObjectMethodA(
+    env: *mut sys::JNIEnv,
+    obj: sys::jobject,
+    method: sys::jmethodID,
+    args: *const sys::jvalue,
+) -> sys::jobject {
+    let ret = unsafe { invoke(env, "CallObjectMethodA", obj, method, args, Invoke::Virtual) };
+
+    unsafe { raw(env) }
+        .lock()
+        .new_ref(ret.and_then(Val::as_object), RefKind::Local)
+}
+
+unsafe extern "system" fn CallStaticObjectMethodA(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    method: sys::jmethodID,
+    args: *const sys::jvalue,
+) -> sys::jobject {
+    let ret = unsafe { invoke(env, "CallStaticObjectMethodA", clazz, method, args, Invoke::Static) };
+
+    unsafe { raw(env) }
+        .lock()
+        .new_ref(ret.and_then(Val::as_object), RefKind::Local)
+}
+
+// fields
+
+/// Resolves the target of a field access, returns `None` if an exception is thrown.
+fn access_field(
+    state: &mut State,
+    function: &'static str,
+    obj: sys::jobject,
+    field: sys::jfieldID,
+) -> Option<(Option<ObjId>, usize)> {
+    let field = field_index(field);
+    let description = state.describe_field(field);
+
+    state.record(function, Some(description.clone()));
+
+    match state.resolve(obj) {
+        None => {
+            state.throw_new("java/lang/NullPointerException", &format!("access {description} of null"));
+
+            None
+        }
+        Some(_) if state.field(field).is_static => Some((None, field)),
+        Some(obj) => Some((Some(obj), field)),
+    }
+}
+
+macro_rules! define_field_functions {
+    ($(($typ:ty, $get:ident, $set:ident, $get_static:ident, $set_static:ident)),* $(,)?) => {
+        $(
+            unsafe extern "system" fn $get(env: *mut sys::JNIEnv, obj: sys::jobject, field: sys::jfieldID) -> $typ {
+                let mut state = unsafe { raw(env) }.lock();
+
+                access_field(&mut state, stringify!($get), obj, field).map_or_else(Default::default, |(obj, field)| <$typ>::from_val(state.get_field(obj, field)))
+            }
+
+            unsafe extern "system" fn $set(env: *mut sys::JNIEnv, obj: sys::jobject, field: sys::jfieldID, value: $typ) {
+                let mut state = unsafe { raw(env) }.lock();
+
+                if let Some((obj, field)) = access_field(&mut state, stringify!($set), obj, field) {
+                    state.set_field(obj, field, value.into_val());
+                }
+            }
+
+            unsafe extern "system" fn $get_static(env: *mut sys::JNIEnv, clazz: sys::jclass, field: sys::jfieldID) -> $typ {
+                let mut state = unsafe { raw(env) }.lock();
+
+                access_field(&mut state, stringify!($get_static), clazz, field).map_or_else(Default::default, |(obj, field)| <$typ>::from_val(state.get_field(obj, field)))
+            }
+
+            unsafe extern "system" fn $set_static(env: *mut sys::JNIEnv, clazz: sys::jclass, field: sys::jfieldID, value: $typ) {
+                let mut state = unsafe { raw(env) }.lock();
+
+                if let Some((obj, field)) = access_field(&mut state, stringify!($set_static), clazz, field) {
+                    state.set_field(obj, field, value.into_val());
+                }
+            }
+        )*
+    };
+}
+
+define_field_functions!(
+    (
+        bool,
+        GetBooleanField,
+        SetBooleanField,
+        GetStaticBooleanField,
+        SetStaticBooleanField
+    ),
+    (i8, GetByteField, SetByteField, GetStaticByteField, SetStaticByteField),
+    (u16, GetCharField, SetCharField, GetStaticCharField, SetStaticCharField),
+    (i16, GetShortField, SetShortField, GetStaticShortField, SetStaticShortField),
+    (i32, GetIntField, SetIntField, GetStaticIntField, SetStaticIntField),
+    (i64, GetLongField, SetLongField, GetStaticLongField, SetStaticLongField),
+    (f32, GetFloatField, SetFloatField, GetStaticFloatField, SetStaticFloatField),
+    (
+        f64,
+        GetDoubleField,
+        SetDoubleField,
+        GetStaticDoubleField,
+        SetStaticDoubleField
+    ),
+);
+
+unsafe fn get_object_field(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    obj: sys::jobject,
+    field: sys::jfieldID,
+) -> sys::jobject {
+    let mut state = unsafe { raw(env) }.lock();
+
+    let value = access_field(&mut state, function, obj, field).and_then(|(obj, field)| state.get_field(obj, field).as_object());
+
+    state.new_ref(value, RefKind::Local)
+}
+
+unsafe fn set_object_field(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    obj: sys::jobject,
+    field: sys::jfieldID,
+    value: sys::jobject,
+) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    if let Some((obj, field)) = access_field(&mut state, function, obj, field) {
+        let value = state.resolve(value);
+
+        state.set_field(obj, field, Val::Object(value));
+    }
+}
+
+unsafe extern "system" fn GetObjectField(env: *mut sys::JNIEnv, obj: sys::jobject, field: sys::jfieldID) -> sys::jobject {
+    unsafe { get_object_field(env, "GetObjectField", obj, field) }
+}
+
+unsafe extern "system" fn SetObjectField(env: *mut sys::JNIEnv, obj: sys::jobject, field: sys::jfieldID, value: sys::jobject) {
+    unsafe { set_object_field(env, "SetObjectField", obj, field, value) }
+}
+
+unsafe extern "system" fn GetStaticObjectField(env: *mut sys::JNIEnv, clazz: sys::jclass, field: sys::jfieldID) -> sys::jobject {
+    unsafe { get_object_field(env, "GetStaticObjectField", clazz, field) }
+}
+
+unsafe extern "system" fn SetStaticObjectField(
+    env: *mut sys::JNIEnv,
+    clazz: sys::jclass,
+    field: sys::jfieldID,
+    value: sys::jobject,
+) {
+    unsafe { set_object_field(env, "SetStaticObjectField", clazz, field, value) }
+}
+
+// strings
+
+fn string_chars(state: &State, str_: sys::jstring) -> &[u16] {
+    match state.object(state.resolve(str_).expect("fake JNI: null string")) {
+        Object::String(chars) => chars,
+        _ => panic!("fake JNI: object is not a string"),
+    }
+}
+
+fn encode_utf(chars: &[u16]) -> Vec<u8> {
+    let mut utf = Vec::with_capacity(chars.len());
+    for &c in chars {
+        crate::mutf8::encode_unit(c, |b| utf.push(b));
+    }
+
+    utf
+}
+
+/// Checks `start..start + len` is in bounds of `str_`, throws `StringIndexOutOfBoundsException` if not.
+fn check_string_bounds(
+    state: &mut State,
+    str_: sys::jstring,
+    start: sys::jsize,
+    len: sys::jsize,
+) -> Option<core::ops::Range<usize>> {
+    let length = string_chars(state, str_).len();
+
+    if start < 0 || len < 0 || start as usize + len as usize > length {
+        state.throw_new(
+            "java/lang/StringIndexOutOfBoundsException",
+            &format!("range [{start}, {start} + {len}) out of bounds for length {length}"),
+        );
+
+        return None;
+    }
+
+    Some(start as usize..start as usize + len as usize)
+}
+
+unsafe extern "system" fn NewString(env: *mut sys::JNIEnv, unicode: *const sys::jchar, len: sys::jsize) -> sys::jstring {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("NewString", None);
+
+    let chars = unsafe { core::slice::from_raw_parts(unicode, len as usize) }.to_vec();
+    let obj = state.new_string(chars);
+
+    state.new_ref(Some(obj), RefKind::Local)
+}
+
+unsafe extern "system" fn NewStringUTF(env: *mut sys::JNIEnv, utf: *const core::ffi::c_char) -> sys::jstring {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("NewStringUTF", None);
+
+    let chars = crate::mutf8::decode_units(unsafe { CStr::from_ptr(utf) }.to_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_or_else(|err| panic!("fake JNI: {err}"));
+    let obj = state.new_string(chars);
+
+    state.new_ref(Some(obj), RefKind::Local)
+}
+
+unsafe extern "system" fn GetStringLength(env: *mut sys::JNIEnv, str_: sys::jstring) -> sys::jsize {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetStringLength", None);
+    string_chars(&state, str_).len() as _
+}
+
+unsafe extern "system" fn GetStringUTFLength(env: *mut sys::JNIEnv, str_: sys::jstring) -> sys::jsize {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetStringUTFLength", None);
+    encode_utf(string_chars(&state, str_)).len() as _
+}
+
+unsafe extern "system" fn GetStringChars(
+    env: *mut sys::JNIEnv,
+    str_: sys::jstring,
+    is_copy: *mut sys::jboolean,
+) -> *const sys::jchar {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetStringChars", None);
+
+    // terminated like the Java VM does, which also makes the buffer non-empty
+    let chars = string_chars(&state, str_).iter().copied().chain([0]).collect::<Box<[u16]>>();
+    let ptr = chars.as_ptr();
+    state.buffers.insert(ptr as usize, Buffer::Chars(chars));
+
+    if !is_copy.is_null() {
+        unsafe { *is_copy = true };
+    }
+
+    ptr
+}
+
+unsafe extern "system" fn ReleaseStringChars(env: *mut sys::JNIEnv, _str: sys::jstring, chars: *const sys::jchar) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("ReleaseStringChars", None);
+
+    match state.buffers.remove(&(chars as usize)) {
+        Some(Buffer::Chars(buf)) if buf.as_ptr() == chars => (),
+        _ => panic!("fake JNI: release invalid string chars {chars:?}"),
+    }
+}
+
+unsafe extern "system" fn GetStringUTFChars(
+    env: *mut sys::JNIEnv,
+    str_: sys::jstring,
+    is_copy: *mut sys::jboolean,
+) -> *const core::ffi::c_char {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetStringUTFChars", None);
+
+    let utf = encode_utf(string_chars(&state, str_))
+        .into_iter()
+        .chain([0])
+        .collect::<Box<[u8]>>();
+    let ptr = utf.as_ptr();
+    state.buffers.insert(ptr as usize, Buffer::Utf(utf));
+
+    if !is_copy.is_null() {
+        unsafe { *is_copy = true };
+    }
+
+    ptr as _
+}
+
+unsafe extern "system" fn ReleaseStringUTFChars(env: *mut sys::JNIEnv, _str: sys::jstring, chars: *const core::ffi::c_char) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("ReleaseStringUTFChars", None);
+
+    match state.buffers.remove(&(chars as usize)) {
+        Some(Buffer::Utf(buf)) if buf.as_ptr() == chars as *const u8 => (),
+        _ => panic!("fake JNI: release invalid string UTF chars {chars:?}"),
+    }
+}
+
+unsafe extern "system" fn GetStringRegion(
+    env: *mut sys::JNIEnv,
+    str_: sys::jstring,
+    start: sys::jsize,
+    len: sys::jsize,
+    buf: *mut sys::jchar,
+) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetStringRegion", None);
+
+    if let Some(range) = check_string_bounds(&mut state, str_, start, len) {
+        let chars = &string_chars(&state, str_)[range];
+
+        unsafe { core::ptr::copy_nonoverlapping(chars.as_ptr(), buf, chars.len()) };
+    }
+}
+
+unsafe extern "system" fn GetStringUTFRegion(
+    env: *mut sys::JNIEnv,
+    str_: sys::jstring,
+    start: sys::jsize,
+    len: sys::jsize,
+    buf: *mut core::ffi::c_char,
+) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetStringUTFRegion", None);
+
+    if let Some(range) = check_string_bounds(&mut state, str_, start, len) {
+        // terminated like the Java VM does
+        let utf = encode_utf(&string_chars(&state, str_)[range]);
+
+        unsafe {
+            core::ptr::copy_nonoverlapping(utf.as_ptr(), buf as *mut u8, utf.len());
+            *buf.add(utf.len()) = 0;
+        }
+    }
+}
+
+// arrays
+
+fn array_elements(state: &mut State, array: sys::jarray) -> &mut Vec<Val> {
+    let array = state.resolve(array).expect("fake JNI: null array");
+
+    match state.object_mut(array) {
+        Object::Array { elements, .. } => elements,
+        _ => panic!("fake JNI: object is not an array"),
+    }
+}
+
+/// Checks `start..start + len` is in bounds of `array`, throws `ArrayIndexOutOfBoundsException` if not.
+fn check_bounds(state: &mut State, array: sys::jarray, start: sys::jsize, len: sys::jsize) -> Option<core::ops::Range<usize>> {
+    let length = array_elements(state, array).len();
+
+    if start < 0 || len < 0 || start as usize + len as usize > length {
+        state.throw_new(
+            "java/lang/ArrayIndexOutOfBoundsException",
+            &format!("range [{start}, {start} + {len}) out of bounds for length {length}"),
+        );
+
+        return None;
+    }
+
+    Some(start as usize..start as usize + len as usize)
+}
+
+fn new_array(state: &mut State, class: &str, len: sys::jsize, initial: Val) -> sys::jarray {
+    if len < 0 {
+        state.throw_new("java/lang/NegativeArraySizeException", &format!("{len}"));
+
+        return core::ptr::null_mut();
+    }
+
+    let class = state.find_class(class).expect("BROKEN: invalid array class");
+    let array = state.new_array(class, vec![initial; len as usize]);
+
+    state.new_ref(Some(array), RefKind::Local)
+}
+
+unsafe extern "system" fn GetArrayLength(env: *mut sys::JNIEnv, array: sys::jarray) -> sys::jsize {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetArrayLength", None);
+    array_elements(&mut state, array).len() as _
+}
+
+unsafe extern "system" fn NewObjectArray(
+    env: *mut sys::JNIEnv,
+    len: sys::jsize,
+    clazz: sys::jclass,
+    init: sys::jobject,
+) -> sys::jobjectArray {
+    let mut state = unsafe { raw(env) }.lock();
+
+    let class = state.as_class(state.resolve(clazz).expect("fake JNI: null class"));
+    let name = state.class(class).name.clone();
+    state.record("NewObjectArray", Some(name.clone()));
+
+    let name = if name.starts_with('[') {
+        format!("[{name}")
+    } else {
+        format!("[L{name};")
+    };
+    let initial = Val::Object(state.resolve(init));
+
+    new_array(&mut state, &name, len, initial)
+}
+
+unsafe extern "system" fn GetObjectArrayElement(
+    env: *mut sys::JNIEnv,
+    array: sys::jobjectArray,
+    index: sys::jsize,
+) -> sys::jobject {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("GetObjectArrayElement", None);
+
+    let Some(range) = check_bounds(&mut state, array, index, 1) else {
+        return core::ptr::null_mut();
+    };
+
+    let value = array_elements(&mut state, array)[range.start].as_object();
+    state.new_ref(value, RefKind::Local)
+}
+
+unsafe extern "system" fn SetObjectArrayElement(
+    env: *mut sys::JNIEnv,
+    array: sys::jobjectArray,
+    index: sys::jsize,
+    value: sys::jobject,
+) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record("SetObjectArrayElement", None);
+
+    if let Some(range) = check_bounds(&mut state, array, index, 1) {
+        let value = state.resolve(value);
+
+        array_elements(&mut state, array)[range.start] = Val::Object(value);
+    }
+}
+
+unsafe fn get_array_elements<T: Primitive>(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    array: sys::jarray,
+    is_copy: *mut sys::jboolean,
+) -> *mut T {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record(function, None);
+
+    // at least one element is reserved, so each buffer has a distinct address
+    let elements = array_elements(&mut state, array);
+    let mut buf = Vec::with_capacity(elements.len().max(1));
+    buf.extend(elements.iter().map(|v| T::from_val(*v)));
+
+    let ptr = buf.as_mut_ptr();
+    let array = state.resolve(array).expect("BROKEN: null array");
+    state.buffers.insert(ptr as usize, Buffer::Array(array, Box::new(buf)));
+
+    if !is_copy.is_null() {
+        unsafe { *is_copy = true };
+    }
+
+    ptr
+}
+
+unsafe fn release_array_elements<T: Primitive>(env: *mut sys::JNIEnv, function: &'static str, elems: *mut T, mode: sys::jint) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record(function, None);
+
+    let Some(Buffer::Array(array, buf)) = state.buffers.remove(&(elems as usize)) else {
+        panic!("fake JNI: release invalid array elements {elems:?}");
+    };
+    let buf = buf
+        .downcast::<Vec<T>>()
+        .expect("fake JNI: release array elements with wrong type");
+
+    if mode != sys::JNI_ABORT
+        && let Object::Array { elements, .. } = state.object_mut(array)
+    {
+        for (element, value) in elements.iter_mut().zip(buf.iter()) {
+            *element = value.into_val();
+        }
+    }
+
+    if mode == sys::JNI_COMMIT {
+        state.buffers.insert(elems as usize, Buffer::Array(array, buf));
+    }
+}
+
+unsafe fn get_array_region<T: Primitive>(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    array: sys::jarray,
+    start: sys::jsize,
+    len: sys::jsize,
+    buf: *mut T,
+) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record(function, None);
+
+    if let Some(range) = check_bounds(&mut state, array, start, len) {
+        let buf = unsafe { core::slice::from_raw_parts_mut(buf, range.len()) };
+
+        for (dst, src) in buf.iter_mut().zip(&array_elements(&mut state, array)[range]) {
+            *dst = T::from_val(*src);
+        }
+    }
+}
+
+unsafe fn set_array_region<T: Primitive>(
+    env: *mut sys::JNIEnv,
+    function: &'static str,
+    array: sys::jarray,
+    start: sys::jsize,
+    len: sys::jsize,
+    buf: *const T,
+) {
+    let mut state = unsafe { raw(env) }.lock();
+
+    state.record(function, None);
+
+    if let Some(range) = check_bounds(&mut state, array, start, len) {
+        let buf = unsafe { core::slice::from_raw_parts(buf, range.len()) };
+
+        for (dst, src) in array_elements(&mut state, array)[range].iter_mut().zip(buf) {
+            *dst = src.into_val();
+        }
+    }
+}
+
+macro_rules! define_array_functions {
+    ($(($typ:ty, $new:ident, $get_elements:ident, $release_elements:ident, $get_region:ident, $set_region:ident)),* $(,)?) => {
+        $(
+            unsafe extern "system" fn $new(env: *mut sys::JNIEnv, len: sys::jsize) -> sys::jarray {
+                let mut state = unsafe { raw(env) }.lock();
+
+                state.record(stringify!($new), None);
+                new_array(&mut state, &format!("[{}", <$typ>::SIG), len, <$typ>::default().into_val())
+            }
+
+            unsafe extern "system" fn $get_elements(env: *mut sys::JNIEnv, array: sys::jarray, is_copy: *mut sys::jboolean) -> *mut $typ {
+                unsafe { get_array_elements(env, stringify!($get_elements), array, is_copy) }
+            }
+
+            unsafe extern "system" fn $release_elements(env: *mut sys::JNIEnv, _array: sys::jarray, elems: *mut $typ, mode: sys::jint) {
+                unsafe { release_array_elements(env, stringify!($release_elements), elems, mode) }
+            }
+
+            unsafe extern "system" fn $get_region(env: *mut sys::JNIEnv, array: sys::jarray, start: sys::jsize, len: sys::jsize, buf: *mut $typ) {
+                unsafe { get_array_region(env, stringify!($get_region), array, start, len, buf) }
+            }
+
+            unsafe extern "system" fn $set_region(env: *mut sys::JNIEnv, array: sys::jarray, start: sys::jsize, len: sys::jsize, buf: *const $typ) {
+                unsafe { set_array_region(env, stringify!($set_region), array, start, len, buf) }
+            }
+        )*
+    };
+}
+
+define_array_functions!(
+    (
+        bool,
+        NewBooleanArray,
+        GetBooleanArrayElements,
+        ReleaseBooleanArrayElements,
+        GetBooleanArrayRegion,
+        SetBooleanArrayRegion
+    ),
+    (
+        i8,
+        NewByteArray,
+        GetByteArrayElements,
+        ReleaseByteArrayElements,
+        GetByteArrayRegion,
+        SetByteArrayRegion
+    ),
+    (
+        u16,
+        NewCharArray,
+        GetCharArrayElements,
+        ReleaseCharArrayElements,
+        GetCharArrayRegion,
+        SetCharArrayRegion
+    ),
+    (
+        i16,
+        NewShortArray,
+        GetShortArrayElements,
+        ReleaseShortArrayElements,
+        GetShortArrayRegion,
+        SetShortArrayRegion
+    ),
+    (
+        i32,
+        NewIntArray,
+        GetIntArrayElements,
+        ReleaseIntArrayElements,
+        GetIntArrayRegion,
+        SetIntArrayRegion
+    ),
+    (
+        i64,
+        NewLongArray,
+        GetLongArrayElements,
+        ReleaseLongArrayElements,
+        GetLongArrayRegion,
+        SetLongArrayRegion
+    ),
+    (
+        f32,
+        NewFloatArray,
+        GetFloatArrayElements,
+        ReleaseFloatArrayElements,
+        GetFloatArrayRegion,
+        SetFloatArrayRegion
+    ),
+    (
+        f64,
+        NewDoubleArray,
+        GetDoubleArrayElements,
+        ReleaseDoubleArrayElements,
+        GetDoubleArrayRegion,
+        SetDoubleArrayRegion
+    ),
+);
+
+// vm
+
+unsafe extern "system" fn GetJavaVM(env: *mut sys::JNIEnv, vm: *mut *mut sys::JavaVM) -> sys::jint {
+    let raw = unsafe { raw(env) };
+
+    raw.lock().record("GetJavaVM", None);
+
+    unsafe { *vm = &raw.vm as *const _ as *mut sys::JavaVM };
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn GetEnv(vm: *mut sys::JavaVM, penv: *mut *mut core::ffi::c_void, _version: sys::jint) -> sys::jint {
+    let raw = unsafe { raw_of_vm(vm) };
+
+    // the fake environment is usable on any thread
+    unsafe { *penv = &raw.env as *const _ as *mut core::ffi::c_void };
+
+    sys::JNI_OK
+}
+
+unsafe extern "system" fn AttachCurrentThread(
+    vm: *mut sys::JavaVM,
+    penv: *mut *mut core::ffi::c_void,
+    _args: *mut core::ffi::c_void,
+) -> sys::jint {
+    unsafe { GetEnv(vm, penv, sys::JNI_VERSION_1_8) }
+}
+
+unsafe extern "system" fn DetachCurrentThread(_vm: *mut sys::JavaVM) -> sys::jint {
+    sys::JNI_OK
+}
+
+// functions not listed here are `None`, calling them panics
+pub(super) static NATIVE_INTERFACE: NativeInterface = NativeInterface(sys::JNINativeInterface_ {
+    GetVersion: Some(GetVersion),
+    FindClass: Some(FindClass),
+    GetSuperclass: Some(GetSuperclass),
+    IsAssignableFrom: Some(IsAssignableFrom),
+    Throw: Some(Throw),
+    ThrowNew: Some(ThrowNew),
+    ExceptionOccurred: Some(ExceptionOccurred),
+    ExceptionDescribe: Some(ExceptionDescribe),
+    ExceptionClear: Some(ExceptionClear),
+    ExceptionCheck: Some(ExceptionCheck),
+    PushLocalFrame: Some(PushLocalFrame),
+    PopLocalFrame: Some(PopLocalFrame),
+    EnsureLocalCapacity: Some(EnsureLocalCapacity),
+    NewLocalRef: Some(NewLocalRef),
+    DeleteLocalRef: Some(DeleteLocalRef),
+    NewGlobalRef: Some(NewGlobalRef),
+    DeleteGlobalRef: Some(DeleteGlobalRef),
+    NewWeakGlobalRef: Some(NewWeakGlobalRef),
+    DeleteWeakGlobalRef: Some(DeleteWeakGlobalRef),
+    GetObjectRefType: Some(GetObjectRefType),
+    IsSameObject: Some(IsSameObject),
+    AllocObject: Some(AllocObject),
+    NewObjectA: Some(NewObjectA),
+    GetObjectClass: Some(GetObjectClass),
+    IsInstanceOf: Some(IsInstanceOf),
+    MonitorEnter: Some(MonitorEnter),
+    MonitorExit: Some(MonitorExit),
+    GetMethodID: Some(GetMethodID),
+    GetStaticMethodID: Some(GetStaticMethodID),
+    GetFieldID: Some(GetFieldID),
+    GetStaticFieldID: Some(GetStaticFieldID),
+    CallObjectMethodA: Some(CallObjectMethodA),
+    CallBooleanMethodA: Some(CallBooleanMethodA),
+    CallByteMethodA: Some(CallByteMethodA),
+    CallCharMethodA: Some(CallCharMethodA),
+    CallShortMethodA: Some(CallShortMethodA),
+    CallIntMethodA: Some(CallIntMethodA),
+    CallLongMethodA: Some(CallLongMethodA),
+    CallFloatMethodA: Some(CallFloatMethodA),
+    CallDoubleMethodA: Some(CallDoubleMethodA),
+    CallVoidMethodA: Some(CallVoidMethodA),
+    CallStaticObjectMethodA: Some(CallStaticObjectMethodA),
+    CallStaticBooleanMethodA: Some(CallStaticBooleanMethodA),
+    CallStaticByteMethodA: Some(CallStaticByteMethodA),
+    CallStaticCharMethodA: Some(CallStaticCharMethodA),
+    CallStaticShortMethodA: Some(CallStaticShortMethodA),
+    CallStaticIntMethodA: Some(CallStaticIntMethodA),
+    CallStaticLongMethodA: Some(CallStaticLongMethodA),
+    CallStaticFloatMethodA: Some(CallStaticFloatMethodA),
+    CallStaticDoubleMethodA: Some(CallStaticDoubleMethodA),
+    CallStaticVoidMethodA: Some(CallStaticVoidMethodA),
+    GetObjectField: Some(GetObjectField),
+    GetBooleanField: Some(GetBooleanField),
+    GetByteField: Some(GetByteField),
+    GetCharField: Some(GetCharField),
+    GetShortField: Some(GetShortField),
+    GetIntField: Some(GetIntField),
+    GetLongField: Some(GetLongField),
+    GetFloatField: Some(GetFloatField),
+    GetDoubleField: Some(GetDoubleField),
+    SetObjectField: Some(SetObjectField),
+    SetBooleanField: Some(SetBooleanField),
+    SetByteField: Some(SetByteField),
+    SetCharField: Some(SetCharField),
+    SetShortField: Some(SetShortField),
+    SetIntField: Some(SetIntField),
+    SetLongField: Some(SetLongField),
+    SetFloatField: Some(SetFloatField),
+    SetDoubleField: Some(SetDoubleField),
+    GetStaticObjectField: Some(GetStaticObjectField),
+    GetStaticBooleanField: Some(GetStaticBooleanField),
+    GetStaticByteField: Some(GetStaticByteField),
+    GetStaticCharField: Some(GetStaticCharField),
+    GetStaticShortField: Some(GetStaticShortField),
+    GetStaticIntField: Some(GetStaticIntField),
+    GetStaticLongField: Some(GetStaticLongField),
+    GetStaticFloatField: Some(GetStaticFloatField),
+    GetStaticDoubleField: Some(GetStaticDoubleField),
+    SetStaticObjectField: Some(SetStaticObjectField),
+    SetStaticBooleanField: Some(SetStaticBooleanField),
+    SetStaticByteField: Some(SetStaticByteField),
+    SetStaticCharField: Some(SetStaticCharField),
+    SetStaticShortField: Some(SetStaticShortField),
+    SetStaticIntField: Some(SetStaticIntField),
+    SetStaticLongField: Some(SetStaticLongField),
+    SetStaticFloatField: Some(SetStaticFloatField),
+    SetStaticDoubleField: Some(SetStaticDoubleField),
+    NewString: Some(NewString),
+    GetStringLength: Some(GetStringLength),
+    GetStringChars: Some(GetStringChars),
+    ReleaseStringChars: Some(ReleaseStringChars),
+    NewStringUTF: Some(NewStringUTF),
+    GetStringUTFLength: Some(GetStringUTFLength),
+    GetStringUTFChars: Some(GetStringUTFChars),
+    GetStringRegion: Some(GetStringRegion),
+    GetStringUTFRegion: Some(GetStringUTFRegion),
+    ReleaseStringUTFChars: Some(ReleaseStringUTFChars),
+    GetArrayLength: Some(GetArrayLength),
+    NewObjectArray: Some(NewObjectArray),
+    GetObjectArrayElement: Some(GetObjectArrayElement),
+    SetObjectArrayElement: Some(SetObjectArrayElement),
+    NewBooleanArray: Some(NewBooleanArray),
+    NewByteArray: Some(NewByteArray),
+    NewCharArray: Some(NewCharArray),
+    NewShortArray: Some(NewShortArray),
+    NewIntArray: Some(NewIntArray),
+    NewLongArray: Some(NewLongArray),
+    NewFloatArray: Some(NewFloatArray),
+    NewDoubleArray: Some(NewDoubleArray),
+    GetBooleanArrayElements: Some(GetBooleanArrayElements),
+    GetByteArrayElements: Some(GetByteArrayElements),
+    GetCharArrayElements: Some(GetCharArrayElements),
+    GetShortArrayElements: Some(GetShortArrayElements),
+    GetIntArrayElements: Some(GetIntArrayElements),
+    GetLongArrayElements: Some(GetLongArrayElements),
+    GetFloatArrayElements: Some(GetFloatArrayElements),
+    GetDoubleArrayElements: Some(GetDoubleArrayElements),
+    ReleaseBooleanArrayElements: Some(ReleaseBooleanArrayElements),
+    ReleaseByteArrayElements: Some(ReleaseByteArrayElements),
+    ReleaseCharArrayElements: Some(ReleaseCharArrayElements),
+    ReleaseShortArrayElements: Some(ReleaseShortArrayElements),
+    ReleaseIntArrayElements: Some(ReleaseIntArrayElements),
+    ReleaseLongArrayElements: Some(ReleaseLongArrayElements),
+    ReleaseFloatArrayElements: Some(ReleaseFloatArrayElements),
+    ReleaseDoubleArrayElements: Some(ReleaseDoubleArrayElements),
+    GetBooleanArrayRegion: Some(GetBooleanArrayRegion),
+    GetByteArrayRegion: Some(GetByteArrayRegion),
+    GetCharArrayRegion: Some(GetCharArrayRegion),
+    GetShortArrayRegion: Some(GetShortArrayRegion),
+    GetIntArrayRegion: Some(GetIntArrayRegion),
+    GetLongArrayRegion: Some(GetLongArrayRegion),
+    GetFloatArrayRegion: Some(GetFloatArrayRegion),
+    GetDoubleArrayRegion: Some(GetDoubleArrayRegion),
+    SetBooleanArrayRegion: Some(SetBooleanArrayRegion),
+    SetByteArrayRegion: Some(SetByteArrayRegion),
+    SetCharArrayRegion: Some(SetCharArrayRegion),
+    SetShortArrayRegion: Some(SetShortArrayRegion),
+    SetIntArrayRegion: Some(SetIntArrayRegion),
+    SetLongArrayRegion: Some(SetLongArrayRegion),
+    SetFloatArrayRegion: Some(SetFloatArrayRegion),
+    SetDoubleArrayRegion: Some(SetDoubleArrayRegion),
+    GetJavaVM: Some(GetJavaVM),
+    ..unsafe { core::mem::zeroed() }
+});
+
+pub(super) static INVOKE_INTERFACE: InvokeInterface = InvokeInterface(sys::JNIInvokeInterface_ {
+    GetEnv: Some(GetEnv),
+    AttachCurrentThread: Some(AttachCurrentThread),
+    AttachCurrentThreadAsDaemon: Some(AttachCurrentThread),
+    DetachCurrentThread: Some(DetachCurrentThread),
+    ..unsafe { core::mem::zeroed() }
+});
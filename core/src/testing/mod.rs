@@ -0,0 +1,293 @@
+//! An in-process fake JNI environment for unit tests without a Java VM, and fault injection for JNI calls.
+
+mod fault;
+mod functions;
+mod state;
+
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+pub use self::fault::*;
+use self::state::{ClassDef, MethodImpl, State};
+use crate::{JNIEnv, JavaVM, LocalRef, sys};
+
+/// A value passed to or returned from a fake method.
+#[derive(Debug)]
+pub enum FakeValue<'env> {
+    Void,
+    Boolean(bool),
+    Byte(i8),
+    Char(u16),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Object(Option<LocalRef<'env>>),
+}
+
+/// A fake method implemented in Rust.
+///
+/// The second argument is the receiver of an instance method, or the class of a static method.
+/// Returning `Err(throwable)` throws it to the caller.
+pub type FakeMethod = dyn for<'env> Fn(&'env JNIEnv<'static>, &LocalRef<'env>, &[FakeValue<'env>]) -> Result<FakeValue<'env>, LocalRef<'env>>
+    + Send
+    + Sync;
+
+/// A JNI function call recorded by [`FakeJvm`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FakeCall {
+    /// Name of the JNI function, e.g. `CallIntMethodA`.
+    pub function: &'static str,
+    /// Target of the call, e.g. `java/lang/String` for `FindClass`, `org/example/Foo.bar(I)V` for
+    /// methods and `org/example/Foo.count:I` for fields.
+    pub target: Option<String>,
+}
+
+impl Display for FakeCall {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match &self.target {
+            Some(target) => write!(f, "{}({target})", self.function),
+            None => f.write_str(self.function),
+        }
+    }
+}
+
+#[repr(C)]
+pub(crate) struct Raw {
+    // `&env` is the `JNIEnv *` and `&vm` is the `JavaVM *` of this fake.
+    env: *const sys::JNINativeInterface_,
+    vm: *const sys::JNIInvokeInterface_,
+    state: Mutex<State>,
+}
+
+unsafe impl Send for Raw {}
+unsafe impl Sync for Raw {}
+
+impl Raw {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// An in-process fake of the Java VM, enabled by the `testing` feature.
+///
+/// The fake implements JNI functions on a scriptable heap, so code written against [`JNIEnv`]
+/// can run in plain `cargo test`. Classes are defined with [`FakeJvm::class`], `java.lang.Object`,
+/// `java.lang.Class`, `java.lang.String`, `java.lang.Throwable` and common exceptions are predefined.
+///
+/// Supported functions include class and member lookups, object creation, method calls, field accesses,
+/// strings, arrays, references, local frames, exceptions and monitors. Other functions (e.g. `DefineClass`)
+/// panic when called. Misuses such as invalid references abort the process, like `-Xcheck:jni` does.
+///
+/// All threads share the same local frames and pending exception.
+///
+/// Every call is recorded, see [`FakeJvm::calls`] and [`FakeJvm::assert_calls`].
+///
+/// The fake is never freed, since references and caches may outlive it.
+///
+/// # Example
+///
+/// ```rust
+/// use typed_jni_core::testing::{FakeJvm, FakeValue};
+///
+/// let jvm = FakeJvm::new();
+/// jvm.class("org/example/Counter")
+///     .field("count", "I")
+///     .method("next", "()I", |env, this, _| unsafe {
+///         let cls = env.get_object_class(this);
+///         let field = env.get_field_id::<false, _>(&cls, c"count", c"I").unwrap();
+///         let count = env.get_int_field(this, field).unwrap() + 1;
+///
+///         env.set_int_field(this, field, count).unwrap();
+///
+///         Ok(FakeValue::Int(count))
+///     })
+///     .define();
+///
+/// let env = jvm.env();
+/// unsafe {
+///     let cls = env.find_class(c"org/example/Counter").unwrap();
+///     let init = env.get_method_id::<false, _>(&cls, c"<init>", c"()V").unwrap();
+///     let next = env.get_method_id::<false, _>(&cls, c"next", c"()I").unwrap();
+///     let counter = env.new_object(&cls, init, []).unwrap();
+///
+///     assert_eq!(env.call_int_method(&counter, next, []).unwrap(), 1);
+///     assert_eq!(env.call_int_method(&counter, next, []).unwrap(), 2);
+/// }
+///
+/// jvm.assert_calls(["FindClass(org/example/Counter)", "CallIntMethodA(org/example/Counter.next()I)"]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct FakeJvm {
+    raw: &'static Raw,
+}
+
+impl Default for FakeJvm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FakeJvm {
+    /// Creates a new fake Java VM.
+    pub fn new() -> Self {
+        let raw = Box::leak(Box::new(Raw {
+            env: &functions::NATIVE_INTERFACE.0,
+            vm: &functions::INVOKE_INTERFACE.0,
+            state: Mutex::new(State::new()),
+        }));
+
+        Self { raw }
+    }
+
+    /// Returns the JNI environment of the fake, which is usable on any thread.
+    pub fn env(&self) -> &'static JNIEnv<'static> {
+        unsafe { JNIEnv::from_raw(&self.raw.env as *const _ as *mut sys::JNIEnv) }
+    }
+
+    /// Returns the fake Java VM.
+    pub fn vm(&self) -> &'static JavaVM {
+        unsafe { JavaVM::from_raw(&self.raw.vm as *const _ as *mut sys::JavaVM) }
+    }
+
+    /// Starts a definition of the class with internal name `name`, e.g. `org/example/Foo`.
+    pub fn class(&self, name: &str) -> FakeClassBuilder {
+        FakeClassBuilder {
+            jvm: *self,
+            def: ClassDef {
+                name: name.to_owned(),
+                superclass: Some("java/lang/Object".to_owned()),
+                interfaces: Vec::new(),
+                fields: Vec::new(),
+                methods: Vec::new(),
+            },
+        }
+    }
+
+    /// Returns the recorded calls.
+    pub fn calls(&self) -> Vec<FakeCall> {
+        self.raw.lock().calls.clone()
+    }
+
+    /// Clears the recorded calls.
+    pub fn clear_calls(&self) {
+        self.raw.lock().calls.clear();
+    }
+
+    /// Asserts that the recorded calls contain `expected` in order, other calls in between are ignored.
+    ///
+    /// Each expected call is matched against the [`Display`] form of [`FakeCall`],
+    /// e.g. `FindClass(java/lang/String)` or `DeleteLocalRef`.
+    #[track_caller]
+    pub fn assert_calls<'a>(&self, expected: impl IntoIterator<Item = &'a str>) {
+        let calls = self.calls();
+
+        let mut recorded = calls.iter().map(|call| call.to_string());
+        for call in expected {
+            if !recorded.any(|c| c == call) {
+                let recorded = calls.iter().map(|call| call.to_string()).collect::<Vec<_>>().join("\n  ");
+
+                panic!("expected call {call} is not recorded in order, recorded calls:\n  {recorded}");
+            }
+        }
+    }
+
+    /// Returns the number of live local references.
+    pub fn live_local_refs(&self) -> usize {
+        self.raw.lock().live_local_refs()
+    }
+}
+
+/// A builder of a fake class, created by [`FakeJvm::class`].
+pub struct FakeClassBuilder {
+    jvm: FakeJvm,
+    def: ClassDef,
+}
+
+impl FakeClassBuilder {
+    /// Sets the superclass, defaults to `java/lang/Object`.
+    pub fn superclass(mut self, name: &str) -> Self {
+        self.def.superclass = Some(name.to_owned());
+        self
+    }
+
+    /// Makes the class an interface, which has no superclass and no constructor.
+    pub fn interface(mut self) -> Self {
+        self.def.superclass = None;
+        self
+    }
+
+    /// Adds an implemented interface.
+    pub fn implements(mut self, name: &str) -> Self {
+        self.def.interfaces.push(name.to_owned());
+        self
+    }
+
+    /// Adds an instance field with JNI signature `sig`, initialized to zero or `null`.
+    pub fn field(mut self, name: &str, sig: &str) -> Self {
+        self.def.fields.push((name.to_owned(), sig.to_owned(), false));
+        self
+    }
+
+    /// Adds a static field with JNI signature `sig`, initialized to zero or `null`.
+    pub fn static_field(mut self, name: &str, sig: &str) -> Self {
+        self.def.fields.push((name.to_owned(), sig.to_owned(), true));
+        self
+    }
+
+    /// Adds an instance method with JNI signature `sig`, use `<init>` to add a constructor.
+    ///
+    /// Classes without constructors have an implicit `<init>()V`. A panic in `f` is thrown as `java.lang.Error`.
+    pub fn method<F>(mut self, name: &str, sig: &str, f: F) -> Self
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>, &LocalRef<'env>, &[FakeValue<'env>]) -> Result<FakeValue<'env>, LocalRef<'env>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.def
+            .methods
+            .push((name.to_owned(), sig.to_owned(), false, MethodImpl::Fake(Arc::new(f))));
+        self
+    }
+
+    /// Adds a static method with JNI signature `sig`.
+    pub fn static_method<F>(mut self, name: &str, sig: &str, f: F) -> Self
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>, &LocalRef<'env>, &[FakeValue<'env>]) -> Result<FakeValue<'env>, LocalRef<'env>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.def
+            .methods
+            .push((name.to_owned(), sig.to_owned(), true, MethodImpl::Fake(Arc::new(f))));
+        self
+    }
+
+    /// Adds an abstract method, calling it throws `AbstractMethodError` unless a subclass implements it.
+    pub fn abstract_method(mut self, name: &str, sig: &str) -> Self {
+        self.def
+            .methods
+            .push((name.to_owned(), sig.to_owned(), false, MethodImpl::Abstract));
+        self
+    }
+
+    /// Defines the class.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the class is already defined, its superclass or interfaces are not defined,
+    /// or a method signature is invalid.
+    pub fn define(self) {
+        self.jvm.raw.lock().define_class(self.def);
+    }
+}
@@ -0,0 +1,715 @@
+use alloc::{borrow::ToOwned, boxed::Box, collections::BTreeMap, format, string::String, sync::Arc, vec, vec::Vec};
+use core::any::Any;
+
+use super::{FakeCall, FakeMethod};
+use crate::sys;
+
+pub(super) type ObjId = usize;
+
+/// A value stored in the fake heap.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Val {
+    Boolean(bool),
+    Byte(i8),
+    Char(u16),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Object(Option<ObjId>),
+}
+
+impl Val {
+    /// Returns the default value of a field or array element with signature `sig`.
+    pub fn default_of(sig: &str) -> Self {
+        match sig.as_bytes().first() {
+            Some(b'Z') => Val::Boolean(false),
+            Some(b'B') => Val::Byte(0),
+            Some(b'C') => Val::Char(0),
+            Some(b'S') => Val::Short(0),
+            Some(b'I') => Val::Int(0),
+            Some(b'J') => Val::Long(0),
+            Some(b'F') => Val::Float(0.0),
+            Some(b'D') => Val::Double(0.0),
+            _ => Val::Object(None),
+        }
+    }
+
+    pub fn as_object(self) -> Option<ObjId> {
+        match self {
+            Val::Object(obj) => obj,
+            v => panic!("fake JNI: {v:?} is not an object"),
+        }
+    }
+}
+
+/// Splits a method signature into its argument signatures and return signature.
+pub(super) fn parse_method_signature(sig: &str) -> Option<(Vec<&str>, &str)> {
+    let (args, ret) = sig.strip_prefix('(')?.split_once(')')?;
+
+    let mut ret_args = Vec::new();
+    let mut rest = args;
+    while !rest.is_empty() {
+        let len = value_signature_len(rest)?;
+
+        ret_args.push(&rest[..len]);
+        rest = &rest[len..];
+    }
+
+    if ret != "V" && value_signature_len(ret) != Some(ret.len()) {
+        return None;
+    }
+
+    Some((ret_args, ret))
+}
+
+fn value_signature_len(sig: &str) -> Option<usize> {
+    match sig.as_bytes().first()? {
+        b'Z' | b'B' | b'C' | b'S' | b'I' | b'J' | b'F' | b'D' => Some(1),
+        b'L' => sig.find(';').map(|idx| idx + 1),
+        b'[' => value_signature_len(&sig[1..]).map(|len| len + 1),
+        _ => None,
+    }
+}
+
+/// Implementation of a fake method.
+#[derive(Clone)]
+pub(super) enum MethodImpl {
+    Abstract,
+    Builtin(fn(&mut State, ObjId, &[Val]) -> Result<Val, ObjId>),
+    Fake(Arc<FakeMethod>),
+}
+
+pub(super) struct Method {
+    pub class: usize,
+    pub name: String,
+    pub sig: String,
+    pub is_static: bool,
+    pub imp: MethodImpl,
+}
+
+pub(super) struct Field {
+    pub class: usize,
+    pub name: String,
+    pub sig: String,
+    pub is_static: bool,
+}
+
+pub(super) struct Class {
+    pub name: String,
+    pub superclass: Option<usize>,
+    pub interfaces: Vec<usize>,
+    pub object: ObjId,
+    pub fields: Vec<usize>,
+    pub methods: Vec<usize>,
+    pub statics: BTreeMap<usize, Val>,
+}
+
+pub(super) enum Object {
+    Instance { class: usize, fields: BTreeMap<usize, Val> },
+    Class(usize),
+    String(Vec<u16>),
+    Array { class: usize, elements: Vec<Val> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RefKind {
+    Local,
+    Global,
+    WeakGlobal,
+}
+
+/// A buffer lent to native code by `Get*Chars`, `Get*ArrayElements` or `Get*Critical`.
+pub(super) enum Buffer {
+    Chars(Box<[u16]>),
+    Utf(Box<[u8]>),
+    Array(ObjId, Box<dyn Any + Send>),
+}
+
+/// A class definition passed to [`State::define_class`].
+pub(super) struct ClassDef {
+    pub name: String,
+    pub superclass: Option<String>,
+    pub interfaces: Vec<String>,
+    pub fields: Vec<(String, String, bool)>,
+    pub methods: Vec<(String, String, bool, MethodImpl)>,
+}
+
+pub(super) struct State {
+    classes: Vec<Class>,
+    class_names: BTreeMap<String, usize>,
+    objects: Vec<Object>,
+    refs: BTreeMap<usize, (ObjId, RefKind)>,
+    next_ref: usize,
+    frames: Vec<Vec<usize>>,
+    methods: Vec<Method>,
+    fields: Vec<Field>,
+    monitors: BTreeMap<ObjId, usize>,
+    detail_message: usize,
+    pub pending: Option<ObjId>,
+    pub calls: Vec<FakeCall>,
+    pub buffers: BTreeMap<usize, Buffer>,
+}
+
+const BOOTSTRAP_CLASSES: &[(&str, &str)] = &[
+    ("java/lang/Exception", "java/lang/Throwable"),
+    ("java/lang/Error", "java/lang/Throwable"),
+    ("java/lang/RuntimeException", "java/lang/Exception"),
+    ("java/lang/ReflectiveOperationException", "java/lang/Exception"),
+    ("java/lang/ClassNotFoundException", "java/lang/ReflectiveOperationException"),
+    ("java/lang/NoSuchMethodException", "java/lang/ReflectiveOperationException"),
+    ("java/lang/NoSuchFieldException", "java/lang/ReflectiveOperationException"),
+    ("java/lang/InterruptedException", "java/lang/Exception"),
+    ("java/lang/LinkageError", "java/lang/Error"),
+    ("java/lang/NoClassDefFoundError", "java/lang/LinkageError"),
+    ("java/lang/IncompatibleClassChangeError", "java/lang/LinkageError"),
+    ("java/lang/NoSuchMethodError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/NoSuchFieldError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/AbstractMethodError", "java/lang/IncompatibleClassChangeError"),
+    ("java/lang/IllegalArgumentException", "java/lang/RuntimeException"),
+    ("java/lang/IllegalStateException", "java/lang/RuntimeException"),
+    ("java/lang/IllegalMonitorStateException", "java/lang/RuntimeException"),
+    ("java/lang/UnsupportedOperationException", "java/lang/RuntimeException"),
+    ("java/lang/NullPointerException", "java/lang/RuntimeException"),
+    ("java/lang/ClassCastException", "java/lang/RuntimeException"),
+    ("java/lang/ArrayStoreException", "java/lang/RuntimeException"),
+    ("java/lang/NegativeArraySizeException", "java/lang/RuntimeException"),
+    ("java/lang/IndexOutOfBoundsException", "java/lang/RuntimeException"),
+    (
+        "java/lang/ArrayIndexOutOfBoundsException",
+        "java/lang/IndexOutOfBoundsException",
+    ),
+    (
+        "java/lang/StringIndexOutOfBoundsException",
+        "java/lang/IndexOutOfBoundsException",
+    ),
+];
+
+fn no_op(_: &mut State, _: ObjId, _: &[Val]) -> Result<Val, ObjId> {
+    Ok(Val::Object(None))
+}
+
+fn throwable_init_with_message(state: &mut State, this: ObjId, args: &[Val]) -> Result<Val, ObjId> {
+    let field = state.detail_message;
+    if let Object::Instance { fields, .. } = &mut state.objects[this] {
+        fields.insert(field, args[0]);
+    }
+
+    Ok(Val::Object(None))
+}
+
+fn throwable_get_message(state: &mut State, this: ObjId, _: &[Val]) -> Result<Val, ObjId> {
+    match &state.objects[this] {
+        Object::Instance { fields, .. } => Ok(fields.get(&state.detail_message).copied().unwrap_or(Val::Object(None))),
+        _ => Ok(Val::Object(None)),
+    }
+}
+
+fn class_get_name(state: &mut State, this: ObjId, _: &[Val]) -> Result<Val, ObjId> {
+    let Object::Class(class) = state.objects[this] else {
+        return Ok(Val::Object(None));
+    };
+
+    let name = state.classes[class].name.replace('/', ".");
+
+    Ok(Val::Object(Some(state.new_string(name.encode_utf16().collect()))))
+}
+
+impl State {
+    pub fn new() -> Self {
+        let mut state = Self {
+            classes: Vec::new(),
+            class_names: BTreeMap::new(),
+            objects: Vec::new(),
+            refs: BTreeMap::new(),
+            next_ref: 0,
+            frames: vec![Vec::new()],
+            methods: Vec::new(),
+            fields: Vec::new(),
+            monitors: BTreeMap::new(),
+            detail_message: 0,
+            pending: None,
+            calls: Vec::new(),
+            buffers: BTreeMap::new(),
+        };
+
+        let def = |name: &str, superclass: Option<&str>| ClassDef {
+            name: name.to_owned(),
+            superclass: superclass.map(ToOwned::to_owned),
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+        };
+
+        state.define_class(def("java/lang/Object", None));
+        state.define_class(ClassDef {
+            methods: vec![(
+                "getName".to_owned(),
+                "()Ljava/lang/String;".to_owned(),
+                false,
+                MethodImpl::Builtin(class_get_name),
+            )],
+            ..def("java/lang/Class", Some("java/lang/Object"))
+        });
+        state.define_class(def("java/lang/String", Some("java/lang/Object")));
+
+        let throwable = state.define_class(ClassDef {
+            fields: vec![("detailMessage".to_owned(), "Ljava/lang/String;".to_owned(), false)],
+            methods: vec![
+                ("<init>".to_owned(), "()V".to_owned(), false, MethodImpl::Builtin(no_op)),
+                (
+                    "<init>".to_owned(),
+                    "(Ljava/lang/String;)V".to_owned(),
+                    false,
+                    MethodImpl::Builtin(throwable_init_with_message),
+                ),
+                (
+                    "getMessage".to_owned(),
+                    "()Ljava/lang/String;".to_owned(),
+                    false,
+                    MethodImpl::Builtin(throwable_get_message),
+                ),
+            ],
+            ..def("java/lang/Throwable", Some("java/lang/Object"))
+        });
+        state.detail_message = state.classes[throwable].fields[0];
+
+        for (name, superclass) in BOOTSTRAP_CLASSES {
+            state.define_class(ClassDef {
+                methods: vec![
+                    ("<init>".to_owned(), "()V".to_owned(), false, MethodImpl::Builtin(no_op)),
+                    (
+                        "<init>".to_owned(),
+                        "(Ljava/lang/String;)V".to_owned(),
+                        false,
+                        MethodImpl::Builtin(throwable_init_with_message),
+                    ),
+                ],
+                ..def(name, Some(superclass))
+            });
+        }
+
+        state
+    }
+
+    pub fn record(&mut self, function: &'static str, target: Option<String>) {
+        self.calls.push(FakeCall { function, target });
+    }
+
+    // classes
+
+    pub fn define_class(&mut self, def: ClassDef) -> usize {
+        assert!(
+            !self.class_names.contains_key(&def.name),
+            "fake class {} is already defined",
+            def.name
+        );
+
+        let lookup = |state: &Self, name: &str| {
+            *state
+                .class_names
+                .get(name)
+                .unwrap_or_else(|| panic!("fake class {name} referenced by {} is not defined", def.name))
+        };
+
+        let superclass = def.superclass.as_deref().map(|name| lookup(self, name));
+        let interfaces = def.interfaces.iter().map(|name| lookup(self, name)).collect();
+
+        let class = self.classes.len();
+        let object = self.objects.len();
+        self.objects.push(Object::Class(class));
+
+        let mut fields = Vec::new();
+        let mut statics = BTreeMap::new();
+        for (name, sig, is_static) in def.fields {
+            let id = self.fields.len();
+            if is_static {
+                statics.insert(id, Val::default_of(&sig));
+            }
+
+            self.fields.push(Field {
+                class,
+                name,
+                sig,
+                is_static,
+            });
+            fields.push(id);
+        }
+
+        let mut methods = Vec::new();
+        let has_constructor = def.methods.iter().any(|(name, ..)| name == "<init>");
+        // interfaces have no superclass, and no constructor
+        let implicit_constructor = (!has_constructor && (def.superclass.is_some() || def.name == "java/lang/Object"))
+            .then(|| ("<init>".to_owned(), "()V".to_owned(), false, MethodImpl::Builtin(no_op)));
+        for (name, sig, is_static, imp) in def.methods.into_iter().chain(implicit_constructor) {
+            assert!(
+                parse_method_signature(&sig).is_some(),
+                "invalid signature {sig} of fake method {}.{name}",
+                def.name
+            );
+
+            methods.push(self.methods.len());
+            self.methods.push(Method {
+                class,
+                name,
+                sig,
+                is_static,
+                imp,
+            });
+        }
+
+        self.classes.push(Class {
+            name: def.name.clone(),
+            superclass,
+            interfaces,
+            object,
+            fields,
+            methods,
+            statics,
+        });
+        self.class_names.insert(def.name, class);
+
+        class
+    }
+
+    /// Finds a class by its internal name, array classes are created on demand.
+    pub fn find_class(&mut self, name: &str) -> Option<usize> {
+        if let Some(class) = self.class_names.get(name) {
+            return Some(*class);
+        }
+
+        let component = name.strip_prefix('[')?;
+        if value_signature_len(component) != Some(component.len()) {
+            return None;
+        }
+        if let Some(component) = component.strip_prefix('L').and_then(|c| c.strip_suffix(';')) {
+            self.find_class(component)?;
+        } else if component.starts_with('[') {
+            self.find_class(component)?;
+        }
+
+        Some(self.define_class(ClassDef {
+            name: name.to_owned(),
+            superclass: Some("java/lang/Object".to_owned()),
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+        }))
+    }
+
+    pub fn class(&self, class: usize) -> &Class {
+        &self.classes[class]
+    }
+
+    pub fn class_of(&self, obj: ObjId) -> usize {
+        match &self.objects[obj] {
+            Object::Instance { class, .. } | Object::Array { class, .. } => *class,
+            Object::Class(_) => self.class_names["java/lang/Class"],
+            Object::String(_) => self.class_names["java/lang/String"],
+        }
+    }
+
+    /// Returns the class represented by class object `obj`.
+    pub fn as_class(&self, obj: ObjId) -> usize {
+        match self.objects[obj] {
+            Object::Class(class) => class,
+            _ => panic!("fake JNI: object is not a class"),
+        }
+    }
+
+    pub fn is_assignable(&self, sub: usize, sup: usize) -> bool {
+        if sub == sup {
+            return true;
+        }
+
+        let class = &self.classes[sub];
+
+        class.superclass.is_some_and(|s| self.is_assignable(s, sup))
+            || class.interfaces.iter().any(|i| self.is_assignable(*i, sup))
+    }
+
+    // objects
+
+    pub fn object(&self, obj: ObjId) -> &Object {
+        &self.objects[obj]
+    }
+
+    pub fn object_mut(&mut self, obj: ObjId) -> &mut Object {
+        &mut self.objects[obj]
+    }
+
+    pub fn alloc(&mut self, class: usize) -> ObjId {
+        let mut fields = BTreeMap::new();
+
+        let mut current = Some(class);
+        while let Some(c) = current {
+            for field in &self.classes[c].fields {
+                let field_data = &self.fields[*field];
+                if !field_data.is_static {
+                    fields.insert(*field, Val::default_of(&field_data.sig));
+                }
+            }
+
+            current = self.classes[c].superclass;
+        }
+
+        self.objects.push(Object::Instance { class, fields });
+        self.objects.len() - 1
+    }
+
+    pub fn new_string(&mut self, chars: Vec<u16>) -> ObjId {
+        self.objects.push(Object::String(chars));
+        self.objects.len() - 1
+    }
+
+    pub fn new_array(&mut self, class: usize, elements: Vec<Val>) -> ObjId {
+        self.objects.push(Object::Array { class, elements });
+        self.objects.len() - 1
+    }
+
+    /// Creates a throwable of class `name` with `msg` and makes it pending.
+    pub fn throw_new(&mut self, name: &str, msg: &str) {
+        let class = self
+            .find_class(name)
+            .unwrap_or_else(|| panic!("fake JNI: throwable class {name} is not defined"));
+
+        self.throw_new_in(class, Some(msg));
+    }
+
+    pub fn throw_new_in(&mut self, class: usize, msg: Option<&str>) {
+        let obj = self.alloc(class);
+
+        if let Some(msg) = msg {
+            let msg = self.new_string(msg.encode_utf16().collect());
+
+            if let Object::Instance { fields, .. } = &mut self.objects[obj] {
+                fields.insert(self.detail_message, Val::Object(Some(msg)));
+            }
+        }
+
+        self.pending = Some(obj);
+    }
+
+    pub fn message_of(&self, obj: ObjId) -> Option<String> {
+        match &self.objects[obj] {
+            Object::Instance { fields, .. } => match fields.get(&self.detail_message) {
+                Some(Val::Object(Some(msg))) => match &self.objects[*msg] {
+                    Object::String(chars) => Some(String::from_utf16_lossy(chars)),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // references
+
+    pub fn new_ref(&mut self, obj: Option<ObjId>, kind: RefKind) -> sys::jobject {
+        let Some(obj) = obj else {
+            return core::ptr::null_mut();
+        };
+
+        self.next_ref += 1;
+
+        let id = self.next_ref;
+        self.refs.insert(id, (obj, kind));
+        if kind == RefKind::Local {
+            self.frames.last_mut().expect("BROKEN: no local frame").push(id);
+        }
+
+        (id * 8) as sys::jobject
+    }
+
+    pub fn resolve(&self, obj: sys::jobject) -> Option<ObjId> {
+        if obj.is_null() {
+            return None;
+        }
+
+        match self.refs.get(&(obj as usize / 8)) {
+            Some((obj, _)) => Some(*obj),
+            None => panic!("fake JNI: use of invalid or deleted reference {obj:?}"),
+        }
+    }
+
+    pub fn ref_kind(&self, obj: sys::jobject) -> Option<RefKind> {
+        self.refs.get(&(obj as usize / 8)).map(|(_, kind)| *kind)
+    }
+
+    pub fn delete_ref(&mut self, obj: sys::jobject, kind: RefKind) {
+        if obj.is_null() {
+            return;
+        }
+
+        let id = obj as usize / 8;
+        match self.refs.get(&id) {
+            Some((_, k)) if *k == kind => {
+                self.refs.remove(&id);
+
+                if kind == RefKind::Local {
+                    for frame in self.frames.iter_mut().rev() {
+                        if let Some(idx) = frame.iter().rposition(|r| *r == id) {
+                            frame.remove(idx);
+                            break;
+                        }
+                    }
+                }
+            }
+            Some((_, k)) => panic!("fake JNI: delete {k:?} reference {obj:?} as {kind:?} reference"),
+            None => panic!("fake JNI: delete invalid or deleted reference {obj:?}"),
+        }
+    }
+
+    pub fn live_local_refs(&self) -> usize {
+        self.frames.iter().map(|f| f.len()).sum()
+    }
+
+    pub fn push_frame(&mut self) {
+        self.frames.push(Vec::new());
+    }
+
+    pub fn pop_frame(&mut self) -> bool {
+        if self.frames.len() <= 1 {
+            return false;
+        }
+
+        for id in self.frames.pop().expect("BROKEN: no local frame") {
+            self.refs.remove(&id);
+        }
+
+        true
+    }
+
+    // monitors
+
+    pub fn monitor_enter(&mut self, obj: ObjId) {
+        *self.monitors.entry(obj).or_default() += 1;
+    }
+
+    pub fn monitor_exit(&mut self, obj: ObjId) -> bool {
+        match self.monitors.get_mut(&obj) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // members
+
+    fn find_member(
+        &self,
+        class: usize,
+        members: &dyn Fn(&Class) -> &[usize],
+        matches: &dyn Fn(usize) -> bool,
+        inherited: bool,
+    ) -> Option<usize> {
+        let data = &self.classes[class];
+
+        if let Some(id) = members(data).iter().copied().find(|id| matches(*id)) {
+            return Some(id);
+        }
+
+        if !inherited {
+            return None;
+        }
+
+        data.superclass
+            .into_iter()
+            .chain(data.interfaces.iter().copied())
+            .find_map(|c| self.find_member(c, members, matches, inherited))
+    }
+
+    pub fn find_method(&self, class: usize, name: &str, sig: &str, is_static: bool) -> Option<usize> {
+        self.find_member(
+            class,
+            &|c| &c.methods,
+            &|id| {
+                let m = &self.methods[id];
+                m.name == name && m.sig == sig && m.is_static == is_static
+            },
+            name != "<init>",
+        )
+    }
+
+    pub fn find_field(&self, class: usize, name: &str, sig: &str, is_static: bool) -> Option<usize> {
+        self.find_member(
+            class,
+            &|c| &c.fields,
+            &|id| {
+                let f = &self.fields[id];
+                f.name == name && f.sig == sig && f.is_static == is_static
+            },
+            true,
+        )
+    }
+
+    pub fn method(&self, method: usize) -> &Method {
+        &self.methods[method]
+    }
+
+    pub fn field(&self, field: usize) -> &Field {
+        &self.fields[field]
+    }
+
+    /// Finds the implementation of `method` for objects of `class`.
+    pub fn dispatch(&self, class: usize, method: usize) -> usize {
+        let m = &self.methods[method];
+
+        let implemented = |id: usize| {
+            let c = &self.methods[id];
+            c.name == m.name && c.sig == m.sig && !c.is_static && !matches!(c.imp, MethodImpl::Abstract)
+        };
+
+        self.find_member(class, &|c| &c.methods, &implemented, true).unwrap_or(method)
+    }
+
+    pub fn describe_method(&self, method: usize) -> String {
+        let m = &self.methods[method];
+
+        format!("{}.{}{}", self.classes[m.class].name, m.name, m.sig)
+    }
+
+    pub fn describe_field(&self, field: usize) -> String {
+        let f = &self.fields[field];
+
+        format!("{}.{}:{}", self.classes[f.class].name, f.name, f.sig)
+    }
+
+    pub fn get_field(&self, obj: Option<ObjId>, field: usize) -> Val {
+        let f = &self.fields[field];
+
+        if f.is_static {
+            return self.classes[f.class].statics[&field];
+        }
+
+        match obj.map(|obj| &self.objects[obj]) {
+            Some(Object::Instance { fields, .. }) => fields
+                .get(&field)
+                .copied()
+                .unwrap_or_else(|| panic!("fake JNI: object has no field {}", self.describe_field(field))),
+            _ => panic!("fake JNI: object has no field {}", self.describe_field(field)),
+        }
+    }
+
+    pub fn set_field(&mut self, obj: Option<ObjId>, field: usize, value: Val) {
+        let f = &self.fields[field];
+
+        if f.is_static {
+            let class = f.class;
+            self.classes[class].statics.insert(field, value);
+
+            return;
+        }
+
+        let description = self.describe_field(field);
+        match obj.map(|obj| &mut self.objects[obj]) {
+            Some(Object::Instance { fields, .. }) if fields.contains_key(&field) => {
+                fields.insert(field, value);
+            }
+            _ => panic!("fake JNI: object has no field {description}"),
+        }
+    }
+}
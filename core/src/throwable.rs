@@ -59,6 +59,9 @@ impl<'vm> JNIEnv<'vm> {
             let ret = match NonNull::new(call!(self.as_raw_ptr(), ExceptionOccurred)) {
                 None => Ok(ret),
                 Some(ex) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(throwable = ?ex, "exception thrown");
+
                     #[cfg(feature = "print-throwable")]
                     call!(self.as_raw_ptr(), ExceptionDescribe);
 
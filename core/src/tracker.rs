@@ -0,0 +1,226 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    fmt::{Display, Formatter},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{backtrace::Backtrace, cell::RefCell, sync::RwLock};
+
+/// Number of local references that JNI guarantees to be available in a local frame.
+pub const GUARANTEED_LOCAL_CAPACITY: usize = 16;
+
+/// Action taken by [`LocalRefTracker`] when a problem is detected.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum LocalRefTrackAction {
+    /// Print the report to stderr. (default)
+    #[default]
+    Warn,
+    /// Print the report to stderr and panic.
+    Panic,
+    /// Pass the report to the given function.
+    Custom(fn(&LocalRefReport)),
+}
+
+/// A problem detected by [`LocalRefTracker`].
+#[derive(Debug)]
+pub enum LocalRefReport<'a> {
+    /// Live local references in the current frame exceed its capacity.
+    Overflow {
+        /// Live local references in the current frame, including the new one.
+        live: usize,
+        /// Capacity of the current frame.
+        capacity: usize,
+        /// Backtrace of the local reference creation that exceeded the capacity.
+        backtrace: &'a Backtrace,
+    },
+    /// Local references still tracked when their frame was popped.
+    ///
+    /// These references were never released by [`LocalRef`](crate::LocalRef) `Drop`,
+    /// e.g. they were leaked by [`core::mem::forget`].
+    Leak {
+        /// Backtraces of the leaked local reference creations.
+        backtraces: &'a [Arc<Backtrace>],
+    },
+}
+
+impl Display for LocalRefReport<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LocalRefReport::Overflow {
+                live,
+                capacity,
+                backtrace,
+            } => {
+                write!(
+                    f,
+                    "local reference overflow: {live} live references in a frame of capacity {capacity}, created at:\n{backtrace}"
+                )
+            }
+            LocalRefReport::Leak { backtraces } => {
+                write!(f, "local reference leak: {} references leaked at frame pop", backtraces.len())?;
+
+                for (idx, backtrace) in backtraces.iter().enumerate() {
+                    write!(f, "\n#{idx} created at:\n{backtrace}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+static THRESHOLD: AtomicUsize = AtomicUsize::new(GUARANTEED_LOCAL_CAPACITY);
+static ACTION: RwLock<LocalRefTrackAction> = RwLock::new(LocalRefTrackAction::Warn);
+
+struct Frame {
+    capacity: usize,
+    overflowed: bool,
+    refs: Vec<(usize, Arc<Backtrace>)>,
+}
+
+impl Frame {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            overflowed: false,
+            refs: Vec::new(),
+        }
+    }
+}
+
+std::thread_local! {
+    // the first frame stands for the frame that pushed by the Java VM, it is never popped.
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+fn with_top_frame<R>(f: impl FnOnce(&mut Vec<Frame>) -> R) -> Option<R> {
+    FRAMES
+        .try_with(|frames| {
+            let mut frames = frames.borrow_mut();
+            if frames.is_empty() {
+                frames.push(Frame::new(THRESHOLD.load(Ordering::Relaxed)));
+            }
+
+            f(&mut frames)
+        })
+        .ok()
+}
+
+fn report(report: LocalRefReport) {
+    let action = *ACTION.read().unwrap_or_else(|e| e.into_inner());
+
+    match action {
+        LocalRefTrackAction::Warn => std::eprintln!("[typed-jni] {report}"),
+        LocalRefTrackAction::Panic => panic!("[typed-jni] {report}"),
+        LocalRefTrackAction::Custom(f) => f(&report),
+    }
+}
+
+/// Tracker of live local references, enabled by the `track-local-refs` feature.
+///
+/// Tracks every [`LocalRef`](crate::LocalRef) per local frame of the current thread, reports
+/// when live references exceed the capacity of the frame, and reports references leaked at frame pop.
+///
+/// The capacity of the frame pushed by the Java VM is the threshold, the capacity of other frames is
+/// the one requested by [`JNIEnv::with_push_local_frame`](crate::JNIEnv::with_push_local_frame).
+/// Both can be raised by [`JNIEnv::ensure_local_capacity`](crate::JNIEnv::ensure_local_capacity).
+///
+/// Creation backtraces are captured by [`Backtrace::capture`], set `RUST_BACKTRACE=1` to see them.
+pub struct LocalRefTracker;
+
+impl LocalRefTracker {
+    /// Set the capacity of the frame pushed by the Java VM, defaults to [`GUARANTEED_LOCAL_CAPACITY`].
+    ///
+    /// Takes effect on threads whose first local reference is tracked after this call.
+    pub fn set_threshold(threshold: usize) {
+        THRESHOLD.store(threshold, Ordering::Relaxed);
+    }
+
+    /// Set the action taken when a problem is detected, defaults to [`LocalRefTrackAction::Warn`].
+    pub fn set_action(action: LocalRefTrackAction) {
+        *ACTION.write().unwrap_or_else(|e| e.into_inner()) = action;
+    }
+
+    /// Returns the number of live local references in the current frame of the current thread.
+    pub fn live_count() -> usize {
+        with_top_frame(|frames| frames.last().map(|f| f.refs.len()).unwrap_or(0)).unwrap_or(0)
+    }
+
+    pub(crate) fn track(ptr: usize) {
+        let backtrace = Arc::new(Backtrace::capture());
+
+        let overflow = with_top_frame(|frames| {
+            // the slot may be reused after the Java VM released a leaked reference implicitly
+            untrack_in(frames, ptr);
+
+            let frame = frames.last_mut().expect("BROKEN: no local frame");
+            frame.refs.push((ptr, backtrace.clone()));
+
+            if frame.refs.len() > frame.capacity && !frame.overflowed {
+                frame.overflowed = true;
+
+                Some((frame.refs.len(), frame.capacity))
+            } else {
+                None
+            }
+        })
+        .flatten();
+
+        if let Some((live, capacity)) = overflow {
+            report(LocalRefReport::Overflow {
+                live,
+                capacity,
+                backtrace: &backtrace,
+            });
+        }
+    }
+
+    pub(crate) fn untrack(ptr: usize) {
+        with_top_frame(|frames| untrack_in(frames, ptr));
+    }
+
+    pub(crate) fn ensure_capacity(capacity: usize) {
+        with_top_frame(|frames| {
+            let frame = frames.last_mut().expect("BROKEN: no local frame");
+            frame.capacity = frame.capacity.max(frame.refs.len() + capacity);
+        });
+    }
+
+    pub(crate) fn push_frame(capacity: usize) {
+        with_top_frame(|frames| frames.push(Frame::new(capacity)));
+    }
+
+    pub(crate) fn pop_frame() {
+        let leaked = with_top_frame(|frames| {
+            if frames.len() > 1 {
+                frames.pop().map(|f| f.refs)
+            } else {
+                None
+            }
+        })
+        .flatten();
+
+        if let Some(leaked) = leaked.filter(|refs| !refs.is_empty()) {
+            let backtraces: Vec<_> = leaked.into_iter().map(|(_, backtrace)| backtrace).collect();
+
+            report(LocalRefReport::Leak { backtraces: &backtraces });
+        }
+    }
+
+    pub(crate) fn reset() {
+        let _ = FRAMES.try_with(|frames| frames.borrow_mut().clear());
+    }
+}
+
+fn untrack_in(frames: &mut [Frame], ptr: usize) {
+    for frame in frames.iter_mut().rev() {
+        if let Some(idx) = frame.refs.iter().rposition(|(p, _)| *p == ptr) {
+            frame.refs.remove(idx);
+
+            if frame.refs.len() <= frame.capacity {
+                frame.overflowed = false;
+            }
+
+            return;
+        }
+    }
+}
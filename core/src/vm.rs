@@ -1,6 +1,11 @@
-use core::{ptr::NonNull, sync::atomic::AtomicUsize};
+use core::{
+    ffi::CStr,
+    fmt::{Debug, Formatter},
+    ptr::NonNull,
+    sync::atomic::AtomicUsize,
+};
 
-use crate::{JNIEnv, helper::call, sys};
+use crate::{JNIEnv, StrongRef, helper::call, sys};
 
 /// JavaVM is a handle to the Java VM.
 #[repr(transparent)]
@@ -38,6 +43,9 @@ static ON_DETACH: AtomicUsize = AtomicUsize::new(0);
 
 impl JavaVM {
     /// Set attach hook. Given hook will be called when a new thread is attached to the Java VM.
+    ///
+    /// This hook is called before hooks added by [`JavaVM::add_attach_hook`].
+    #[cfg_attr(feature = "std", deprecated(note = "use `JavaVM::add_attach_hook` instead"))]
     #[must_use]
     pub fn set_attach_hook(hook: AttachHook) -> Option<AttachHook> {
         let old = ON_ATTACH.swap(hook as usize, core::sync::atomic::Ordering::Relaxed);
@@ -45,6 +53,9 @@ impl JavaVM {
     }
 
     /// Set detach hook. Given hook will be called when a thread is detached from the Java VM.
+    ///
+    /// This hook is called after hooks added by [`JavaVM::add_detach_hook`].
+    #[cfg_attr(feature = "std", deprecated(note = "use `JavaVM::add_detach_hook` instead"))]
     #[must_use]
     pub fn set_detach_hook(hook: AttachHook) -> Option<AttachHook> {
         let old = ON_DETACH.swap(hook as usize, core::sync::atomic::Ordering::Relaxed);
@@ -65,6 +76,52 @@ impl JavaVM {
 #[derive(Debug)]
 pub struct AttachError;
 
+/// A strong reference to an instance of `java.lang.ThreadGroup`.
+///
+/// # Safety
+///
+/// [`ThreadGroupRef::as_thread_group`] must return a reference to an instance of `java.lang.ThreadGroup`.
+pub unsafe trait ThreadGroupRef {
+    /// Returns the reference to the thread group.
+    fn as_thread_group(&self) -> &dyn StrongRef;
+}
+
+/// Options for attaching a thread to the Java VM.
+///
+/// A `bool` can be converted into options with only `as_daemon` specified.
+#[derive(Clone, Copy, Default)]
+pub struct AttachOptions<'a> {
+    /// Whether to attach the thread as a daemon thread.
+    pub as_daemon: bool,
+    /// The name of the thread in modified UTF-8.
+    ///
+    /// If `None`, the name of the current Rust thread is used. (requires `std` feature)
+    pub name: Option<&'a CStr>,
+    /// The `java.lang.ThreadGroup` that the thread is added to.
+    ///
+    /// The reference must stay valid after the thread is detached, e.g. a global reference.
+    pub group: Option<&'a dyn ThreadGroupRef>,
+}
+
+impl<'a> Debug for AttachOptions<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AttachOptions")
+            .field("as_daemon", &self.as_daemon)
+            .field("name", &self.name)
+            .field("group", &self.group.map(|group| group.as_thread_group()))
+            .finish()
+    }
+}
+
+impl<'a> From<bool> for AttachOptions<'a> {
+    fn from(as_daemon: bool) -> Self {
+        Self {
+            as_daemon,
+            ..Default::default()
+        }
+    }
+}
+
 impl JavaVM {
     /// Get the current thread's JNIEnv.
     ///
@@ -98,16 +155,35 @@ impl JavaVM {
     /// # Safety
     ///
     /// The JNIEnv not managed by lifetime system, please make sure it is not used after detachment.
-    pub unsafe fn attach_current_thread<'a, 's: 'a>(&'s self, as_daemon: bool) -> Result<&'a JNIEnv<'a>, AttachError> {
+    pub unsafe fn attach_current_thread<'a, 's: 'a, 'o>(
+        &'s self,
+        options: impl Into<AttachOptions<'o>>,
+    ) -> Result<&'a JNIEnv<'a>, AttachError> {
         unsafe {
+            let options = options.into();
+
+            #[cfg(feature = "std")]
+            let default_name = match options.name {
+                None => std::thread::current().name().map(|name| {
+                    alloc::ffi::CString::new(crate::encode_modified_utf8(name)).expect("BROKEN: modified UTF-8 contains NUL")
+                }),
+                Some(_) => None,
+            };
+            #[cfg(feature = "std")]
+            let name = options.name.or(default_name.as_deref());
+            #[cfg(not(feature = "std"))]
+            let name = options.name;
+
             let mut env: *mut sys::JNIEnv = core::ptr::null_mut();
             let args = sys::JavaVMAttachArgs {
                 version: sys::JNI_VERSION_1_4 as _,
-                name: core::ptr::null_mut(),
-                group: core::ptr::null_mut(),
+                name: name.map_or(core::ptr::null_mut(), |name| name.as_ptr().cast_mut()),
+                group: options
+                    .group
+                    .map_or(core::ptr::null_mut(), |group| group.as_thread_group().as_raw_ptr()),
             };
 
-            let ret = if as_daemon {
+            let ret = if options.as_daemon {
                 call!(
                     self.as_raw_ptr(),
                     AttachCurrentThreadAsDaemon,
@@ -126,6 +202,9 @@ impl JavaVM {
             if ret == sys::JNI_OK {
                 self.run_hook(&ON_ATTACH);
 
+                #[cfg(feature = "std")]
+                self.run_attach_hooks();
+
                 Ok(JNIEnv::from_raw(env))
             } else {
                 Err(AttachError)
@@ -140,8 +219,14 @@ impl JavaVM {
     /// This operation may break state of current thread
     pub unsafe fn detach_current_thread(&self) -> Result<(), AttachError> {
         unsafe {
+            #[cfg(feature = "std")]
+            self.run_detach_hooks();
+
             self.run_hook(&ON_DETACH);
 
+            #[cfg(feature = "track-local-refs")]
+            crate::LocalRefTracker::reset();
+
             if call!(self.as_raw_ptr(), DetachCurrentThread) == sys::JNI_OK {
                 Ok(())
             } else {
@@ -151,38 +236,148 @@ impl JavaVM {
     }
 
     /// Run the given function with the current thread attached to the Java VM.
-    pub fn with_attached_thread<'vm, F, R>(&'vm self, as_daemon: bool, f: F) -> Result<R, AttachError>
+    ///
+    /// `options` only takes effect when the current thread is not attached yet.
+    pub fn with_attached_thread<'vm, 'o, F, R>(&'vm self, options: impl Into<AttachOptions<'o>>, f: F) -> Result<R, AttachError>
     where
         F: for<'env> FnOnce(&'env JNIEnv<'vm>) -> R,
     {
         match unsafe { self.current_env() } {
-            Some(env) => {
-                unsafe {
-                    let ret = call!(env.as_raw_ptr(), PushLocalFrame, 4);
-                    assert_eq!(ret, sys::JNI_OK, "BROKEN: cannot push local frame, maybe stack overflow?");
-                }
+            Some(env) => Ok(run_with_local_frame(env, f)),
+            None => {
+                let env = unsafe { self.attach_current_thread(options)? };
 
                 let ret = f(env);
 
                 unsafe {
-                    call!(env.as_raw_ptr(), PopLocalFrame, core::ptr::null_mut());
+                    self.detach_current_thread()
+                        .expect("BROKEN: cannot detach current thread from javavm");
                 }
 
                 Ok(ret)
             }
-            None => {
-                let env = unsafe { self.attach_current_thread(as_daemon)? };
+        }
+    }
+}
 
-                let ret = f(env);
+fn run_with_local_frame<'vm, F, R>(env: &JNIEnv<'vm>, f: F) -> R
+where
+    F: for<'env> FnOnce(&'env JNIEnv<'vm>) -> R,
+{
+    unsafe {
+        let ret = call!(env.as_raw_ptr(), PushLocalFrame, 4);
+        assert_eq!(ret, sys::JNI_OK, "BROKEN: cannot push local frame, maybe stack overflow?");
+    }
 
-                unsafe {
-                    self.detach_current_thread()
-                        .expect("BROKEN: cannot detach current thread from javavm");
-                }
+    #[cfg(feature = "track-local-refs")]
+    crate::LocalRefTracker::push_frame(4);
 
-                Ok(ret)
+    let ret = f(env);
+
+    #[cfg(feature = "track-local-refs")]
+    crate::LocalRefTracker::pop_frame();
+
+    unsafe {
+        call!(env.as_raw_ptr(), PopLocalFrame, core::ptr::null_mut());
+    }
+
+    ret
+}
+
+/// Detaches the current thread from the Java VM when the thread exits.
+#[cfg(feature = "std")]
+struct PersistentAttachment {
+    vm: *mut sys::JavaVM,
+}
+
+#[cfg(feature = "std")]
+impl Drop for PersistentAttachment {
+    fn drop(&mut self) {
+        unsafe {
+            let vm = JavaVM::from_raw(self.vm);
+
+            // the thread may have been detached manually
+            if vm.current_env().is_some() {
+                vm.detach_current_thread()
+                    .expect("BROKEN: cannot detach current thread from javavm");
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static PERSISTENT_ATTACHMENT: core::cell::RefCell<Option<PersistentAttachment>> = const { core::cell::RefCell::new(None) };
+}
+
+#[cfg(feature = "std")]
+impl JavaVM {
+    /// Attach the current thread to the Java VM until the thread exits.
+    ///
+    /// The thread will be detached automatically (with detach hooks) when it exits.
+    /// If the current thread is already attached, its JNIEnv will be returned as is.
+    ///
+    /// **NOTE**: The thread-local destructors are not guaranteed to run on the main thread.
+    ///
+    /// # Safety
+    ///
+    /// The JNIEnv not managed by lifetime system, please make sure it is not used after detachment.
+    pub unsafe fn attach_current_thread_persistent<'a, 's: 'a, 'o>(
+        &'s self,
+        options: impl Into<AttachOptions<'o>>,
+    ) -> Result<&'a JNIEnv<'a>, AttachError> {
+        unsafe {
+            if let Some(env) = self.current_env() {
+                return Ok(env);
+            }
+
+            // forget the stale attachment left by a manual detachment, dropping it after
+            // attaching would detach the thread again.
+            let stale = PERSISTENT_ATTACHMENT.try_with(|attachment| core::mem::forget(attachment.take()));
+            if stale.is_err() {
+                return Err(AttachError);
+            }
+
+            let env = self.attach_current_thread(options)?;
+
+            let registered = PERSISTENT_ATTACHMENT.try_with(|attachment| {
+                *attachment.borrow_mut() = Some(PersistentAttachment { vm: self.as_raw_ptr() });
+            });
+            if registered.is_err() {
+                self.detach_current_thread()
+                    .expect("BROKEN: cannot detach current thread from javavm");
+
+                return Err(AttachError);
             }
+
+            Ok(env)
+        }
+    }
+
+    /// Run the given function with the current thread attached to the Java VM until the thread exits.
+    ///
+    /// Unlike [`JavaVM::with_attached_thread`], the thread will not be detached after `f` returns,
+    /// so repeated calls on the same thread will not pay for attachment again.
+    /// Local references created in `f` are always released with a pushed local frame.
+    ///
+    /// `options` only takes effect when the current thread is not attached yet.
+    ///
+    /// If the thread is exiting (thread-locals are destroying), fallback to [`JavaVM::with_attached_thread`].
+    pub fn with_attached_thread_persistent<'vm, 'o, F, R>(
+        &'vm self,
+        options: impl Into<AttachOptions<'o>>,
+        f: F,
+    ) -> Result<R, AttachError>
+    where
+        F: for<'env> FnOnce(&'env JNIEnv<'vm>) -> R,
+    {
+        if PERSISTENT_ATTACHMENT.try_with(|_| ()).is_err() {
+            return self.with_attached_thread(options, f);
         }
+
+        let env = unsafe { self.attach_current_thread_persistent(options)? };
+
+        Ok(run_with_local_frame(env, f))
     }
 }
 
@@ -1,3 +1,4 @@
+mod nested;
 mod object;
 mod primitive;
 mod primitive_impls;
@@ -6,9 +7,16 @@ use core::marker::PhantomData;
 
 use typed_jni_core::{JNIEnv, StrongRef};
 
-pub use self::{object::*, primitive::*};
+pub use self::{
+    nested::{ArrayItem, TypedNestedArrayExt},
+    object::*,
+    primitive::*,
+};
 use crate::{LocalObject, Object, ObjectType, Signature, Type, TypedRef, builtin::JavaThrowable};
 
+/// Elements converted in a single local frame at most.
+const FRAME_CAPACITY: usize = 16;
+
 /// A type descriptor of an array.
 pub struct Array<T: Type + 'static>(pub PhantomData<T>);
 
@@ -21,7 +29,7 @@ impl<T: Type + 'static> ObjectType for Array<T> {}
 /// Extension methods for typed arrays.
 pub trait TypedArrayExt {
     /// Get the length of an array.
-    fn typed_get_array_length<R: StrongRef, T: ObjectType>(
+    fn typed_get_array_length<R: StrongRef, T: Type + 'static>(
         &self,
         array: &Object<R, Array<T>>,
     ) -> Result<i32, LocalObject<'_, JavaThrowable>>;
@@ -35,3 +43,25 @@ impl<'vm> TypedArrayExt for JNIEnv<'vm> {
         unsafe { self.get_array_length(&**array).map_err(|err| LocalObject::from_ref(err)) }
     }
 }
+
+/// Runs `f` in a local frame of [`FRAME_CAPACITY`], all local references created in `f` are released after it returns
+/// except the thrown one.
+///
+/// # Safety
+///
+/// * `f` must create local references only with the given [`JNIEnv`].
+unsafe fn with_frame<'env, F>(env: &'env JNIEnv, f: F) -> Result<(), LocalObject<'env, JavaThrowable>>
+where
+    F: for<'scope> FnOnce(&'scope JNIEnv) -> Result<(), LocalObject<'scope, JavaThrowable>>,
+{
+    unsafe {
+        let ((), err) = env
+            .with_push_local_frame(FRAME_CAPACITY as i32, |env| ((), f(env).err().map(|err| err.into_ref())))
+            .map_err(|err| LocalObject::from_ref(err))?;
+
+        match err {
+            Some(err) => Err(LocalObject::from_ref(err)),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use alloc::{format, string::String, vec, vec::Vec};
+
+use typed_jni_core::{JNIEnv, LocalRef, StrongRef};
+
+use crate::{
+    Array, LocalObject, Object, ObjectType, TypedArrayExt, TypedRef,
+    array::{FRAME_CAPACITY, primitive_impls::PrimitiveArrayElement, with_frame},
+    builtin::{JavaString, JavaThrowable},
+    resolver, throwable,
+};
+
+/// A Rust value which can be stored into a Java array of [`ArrayItem::Element`] and read back.
+///
+/// Supported Types:
+///
+/// * Primitive array elements: `bool`, `i8`, `u16`, `i16`, `i32`, `i64`, `f32`, `f64`
+/// * Rust string: `String` as `java.lang.String`
+/// * Nullable objects: `Option<String>`, `Option<Vec<_>>`, where `None` is stored as `null`
+/// * Nested arrays: `Vec<impl ArrayItem>`, e.g. `Vec<Vec<i32>>` as `int[][]`
+pub trait ArrayItem: Sized {
+    /// The Java type of the item.
+    type Element: crate::Type + 'static;
+
+    /// Looks up the classes required by [`ArrayItem::new_array`], outermost first.
+    #[doc(hidden)]
+    fn array_classes<'env>(env: &'env JNIEnv, classes: &mut Vec<LocalRef<'env>>) -> Result<(), LocalObject<'env, JavaThrowable>>;
+
+    /// Creates a Java array of `items` with the classes taken by [`ArrayItem::array_classes`].
+    #[doc(hidden)]
+    fn new_array<'env>(
+        env: &'env JNIEnv,
+        classes: &[LocalRef],
+        items: &[Self],
+    ) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>>;
+
+    /// Reads all items of the Java `array`.
+    ///
+    /// # Safety
+    ///
+    /// `array` must be an array of [`ArrayItem::Element`].
+    #[doc(hidden)]
+    unsafe fn get_array<'env, R: StrongRef>(env: &'env JNIEnv, array: &R) -> Result<Vec<Self>, LocalObject<'env, JavaThrowable>>;
+}
+
+macro_rules! impl_array_item_for_primitive {
+    ($($typ:ty),*) => {
+        $(
+            impl ArrayItem for $typ {
+                type Element = $typ;
+
+                fn array_classes<'env>(_: &'env JNIEnv, _: &mut Vec<LocalRef<'env>>) -> Result<(), LocalObject<'env, JavaThrowable>> {
+                    Ok(())
+                }
+
+                fn new_array<'env>(
+                    env: &'env JNIEnv,
+                    _: &[LocalRef],
+                    items: &[Self],
+                ) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>> {
+                    unsafe {
+                        let array = <$typ>::new_instance(env, array_len(env, items.len())?)
+                            .map_err(|err| LocalObject::from_ref(err))?;
+
+                        <$typ>::set_region(env, &array, 0, items).map_err(|err| LocalObject::from_ref(err))?;
+
+                        Ok(array)
+                    }
+                }
+
+                unsafe fn get_array<'env, R: StrongRef>(
+                    env: &'env JNIEnv,
+                    array: &R,
+                ) -> Result<Vec<Self>, LocalObject<'env, JavaThrowable>> {
+                    unsafe {
+                        let len = env.get_array_length(array).map_err(|err| LocalObject::from_ref(err))?;
+
+                        let mut items = vec![<$typ>::default(); len as usize];
+                        <$typ>::get_region(env, array, 0, &mut items).map_err(|err| LocalObject::from_ref(err))?;
+
+                        Ok(items)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_array_item_for_primitive!(bool, i8, u16, i16, i32, i64, f32, f64);
+
+/// A Rust value stored as an element of a Java object array.
+pub trait ObjectItem: Sized {
+    /// The Java type of the object.
+    type Element: ObjectType;
+
+    /// Looks up the classes required by [`ObjectItem::to_object`], outermost first.
+    fn object_classes<'env>(env: &'env JNIEnv, classes: &mut Vec<LocalRef<'env>>)
+    -> Result<(), LocalObject<'env, JavaThrowable>>;
+
+    /// Converts the value to a Java object with the classes taken by [`ObjectItem::object_classes`].
+    fn to_object<'env>(
+        &self,
+        env: &'env JNIEnv,
+        classes: &[LocalRef],
+    ) -> Result<Option<LocalRef<'env>>, LocalObject<'env, JavaThrowable>>;
+
+    /// Converts a Java object back to the value.
+    ///
+    /// # Safety
+    ///
+    /// `obj` must be an instance of [`ObjectItem::Element`].
+    unsafe fn from_object<'env>(env: &'env JNIEnv, obj: Option<LocalRef>) -> Result<Self, LocalObject<'env, JavaThrowable>>;
+}
+
+impl<T: ObjectItem> ArrayItem for T {
+    type Element = T::Element;
+
+    fn array_classes<'env>(env: &'env JNIEnv, classes: &mut Vec<LocalRef<'env>>) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        let name = resolver::helper::class_name_of::<T::Element>(env)?;
+        classes.push(env.find_class(&*name).map_err(|err| unsafe { LocalObject::from_ref(err) })?);
+
+        T::object_classes(env, classes)
+    }
+
+    fn new_array<'env>(
+        env: &'env JNIEnv,
+        classes: &[LocalRef],
+        items: &[Self],
+    ) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>> {
+        let (cls, classes) = classes.split_first().expect("BROKEN: missing element class");
+
+        unsafe {
+            let array = env
+                .new_object_array(cls, array_len(env, items.len())?)
+                .map_err(|err| LocalObject::from_ref(err))?;
+
+            for (chunk, items) in items.chunks(FRAME_CAPACITY).enumerate() {
+                with_frame(env, |env| {
+                    for (index, item) in items.iter().enumerate() {
+                        let obj = item.to_object(env, classes)?;
+
+                        env.set_object_array_element(&array, (chunk * FRAME_CAPACITY + index) as i32, obj.as_ref())
+                            .map_err(|err| LocalObject::from_ref(err))?;
+                    }
+
+                    Ok(())
+                })?;
+            }
+
+            Ok(array)
+        }
+    }
+
+    unsafe fn get_array<'env, R: StrongRef>(env: &'env JNIEnv, array: &R) -> Result<Vec<Self>, LocalObject<'env, JavaThrowable>> {
+        unsafe {
+            let len = env.get_array_length(array).map_err(|err| LocalObject::from_ref(err))? as usize;
+
+            let mut items = Vec::with_capacity(len);
+            for chunk in (0..len).step_by(FRAME_CAPACITY) {
+                with_frame(env, |env| {
+                    for index in chunk..len.min(chunk + FRAME_CAPACITY) {
+                        let obj = env
+                            .get_object_array_element(array, index as i32)
+                            .map_err(|err| LocalObject::from_ref(err))?;
+
+                        items.push(T::from_object(env, obj)?);
+                    }
+
+                    Ok(())
+                })?;
+            }
+
+            Ok(items)
+        }
+    }
+}
+
+impl ObjectItem for String {
+    type Element = JavaString;
+
+    fn object_classes<'env>(_: &'env JNIEnv, _: &mut Vec<LocalRef<'env>>) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        Ok(())
+    }
+
+    fn to_object<'env>(
+        &self,
+        env: &'env JNIEnv,
+        _: &[LocalRef],
+    ) -> Result<Option<LocalRef<'env>>, LocalObject<'env, JavaThrowable>> {
+        Ok(Some(env.new_string(self)))
+    }
+
+    unsafe fn from_object<'env>(env: &'env JNIEnv, obj: Option<LocalRef>) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        match obj {
+            Some(obj) => unsafe { Ok(env.get_string(&obj)) },
+            None => Err(null_element(env)),
+        }
+    }
+}
+
+impl<T: ObjectItem> ObjectItem for Option<T> {
+    type Element = T::Element;
+
+    fn object_classes<'env>(
+        env: &'env JNIEnv,
+        classes: &mut Vec<LocalRef<'env>>,
+    ) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        T::object_classes(env, classes)
+    }
+
+    fn to_object<'env>(
+        &self,
+        env: &'env JNIEnv,
+        classes: &[LocalRef],
+    ) -> Result<Option<LocalRef<'env>>, LocalObject<'env, JavaThrowable>> {
+        match self {
+            Some(v) => v.to_object(env, classes),
+            None => Ok(None),
+        }
+    }
+
+    unsafe fn from_object<'env>(env: &'env JNIEnv, obj: Option<LocalRef>) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        match obj {
+            Some(obj) => unsafe { T::from_object(env, Some(obj)).map(Some) },
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: ArrayItem> ObjectItem for Vec<T> {
+    type Element = Array<T::Element>;
+
+    fn object_classes<'env>(
+        env: &'env JNIEnv,
+        classes: &mut Vec<LocalRef<'env>>,
+    ) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        T::array_classes(env, classes)
+    }
+
+    fn to_object<'env>(
+        &self,
+        env: &'env JNIEnv,
+        classes: &[LocalRef],
+    ) -> Result<Option<LocalRef<'env>>, LocalObject<'env, JavaThrowable>> {
+        T::new_array(env, classes, self).map(Some)
+    }
+
+    unsafe fn from_object<'env>(env: &'env JNIEnv, obj: Option<LocalRef>) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        match obj {
+            Some(obj) => unsafe { T::get_array(env, &obj) },
+            None => Err(null_element(env)),
+        }
+    }
+}
+
+fn array_len<'env>(env: &'env JNIEnv, len: usize) -> Result<i32, LocalObject<'env, JavaThrowable>> {
+    i32::try_from(len).map_err(|_| {
+        throwable::helper::new_named_exception(
+            env,
+            c"java/lang/IllegalArgumentException",
+            &format!("array length {len} out of range"),
+        )
+    })
+}
+
+fn null_element<'env>(env: &'env JNIEnv) -> LocalObject<'env, JavaThrowable> {
+    throwable::helper::new_named_exception(env, c"java/lang/NullPointerException", "null element in array")
+}
+
+/// Extension methods for nested arrays, e.g. `int[][]` or `String[][]`.
+pub trait TypedNestedArrayExt: TypedArrayExt {
+    /// Creates a Java array of `items`, nested arrays are created level by level.
+    ///
+    /// Classes of every level are looked up once, and elements are converted in local frames,
+    /// so that large arrays don't overflow the local reference table.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use typed_jni::{Array, LocalObject, TypedNestedArrayExt, core::JNIEnv};
+    ///
+    /// fn identity<'env>(env: &'env JNIEnv<'static>) -> LocalObject<'env, Array<Array<i32>>> {
+    ///     let matrix = (0..3).map(|i| (0..3).map(|j| (i == j) as i32).collect()).collect::<Vec<Vec<i32>>>();
+    ///
+    ///     env.typed_new_nested_array(&matrix).unwrap()
+    /// }
+    /// ```
+    fn typed_new_nested_array<T: ArrayItem>(
+        &self,
+        items: &[T],
+    ) -> Result<LocalObject<'_, Array<T::Element>>, LocalObject<'_, JavaThrowable>>;
+
+    /// Reads all items of a Java array, nested arrays are read into nested `Vec`s.
+    ///
+    /// Throws `NullPointerException` if a `null` element is read into a non-`Option` item.
+    fn typed_get_nested_array<T: ArrayItem, R: StrongRef>(
+        &self,
+        array: &Object<R, Array<T::Element>>,
+    ) -> Result<Vec<T>, LocalObject<'_, JavaThrowable>>;
+}
+
+impl<'vm> TypedNestedArrayExt for JNIEnv<'vm> {
+    fn typed_new_nested_array<T: ArrayItem>(
+        &self,
+        items: &[T],
+    ) -> Result<LocalObject<'_, Array<T::Element>>, LocalObject<'_, JavaThrowable>> {
+        let mut classes = Vec::new();
+        T::array_classes(self, &mut classes)?;
+
+        T::new_array(self, &classes, items).map(|array| unsafe { LocalObject::from_ref(array) })
+    }
+
+    fn typed_get_nested_array<T: ArrayItem, R: StrongRef>(
+        &self,
+        array: &Object<R, Array<T::Element>>,
+    ) -> Result<Vec<T>, LocalObject<'_, JavaThrowable>> {
+        unsafe { T::get_array(self, &**array) }
+    }
+}
@@ -1,6 +1,69 @@
+use alloc::format;
+
 use typed_jni_core::{JNIEnv, StrongRef};
 
-use crate::{Array, Class, LocalObject, Object, ObjectType, TypedArrayExt, TypedRef, builtin::JavaThrowable};
+use crate::{
+    Array, Class, LocalObject, Object, ObjectType, TypedArrayExt, TypedRef,
+    array::{FRAME_CAPACITY, with_frame},
+    builtin::JavaThrowable,
+    throwable,
+};
+
+/// An iterator over elements of an object array, created by [`TypedObjectArrayExt::typed_iter_array_elements`].
+///
+/// Elements are fetched lazily, each yielded local reference is released once dropped.
+pub struct ArrayElements<'a, R: StrongRef, T: ObjectType> {
+    env: &'a JNIEnv<'a>,
+    array: &'a Object<R, Array<T>>,
+    front: i32,
+    back: i32,
+}
+
+impl<'a, R: StrongRef, T: ObjectType> ArrayElements<'a, R, T> {
+    fn get(&mut self, index: i32) -> Result<Option<LocalObject<'a, T>>, LocalObject<'a, JavaThrowable>> {
+        let element = self.env.typed_get_array_element(self.array, index);
+        if element.is_err() {
+            // fused after an exception
+            self.front = self.back;
+        }
+
+        element
+    }
+}
+
+impl<'a, R: StrongRef, T: ObjectType> Iterator for ArrayElements<'a, R, T> {
+    type Item = Result<Option<LocalObject<'a, T>>, LocalObject<'a, JavaThrowable>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.front += 1;
+
+        Some(self.get(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+
+        (len, Some(len))
+    }
+}
+
+impl<'a, R: StrongRef, T: ObjectType> DoubleEndedIterator for ArrayElements<'a, R, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        Some(self.get(self.back))
+    }
+}
+
+impl<'a, R: StrongRef, T: ObjectType> ExactSizeIterator for ArrayElements<'a, R, T> {}
 
 /// Extension methods for typed object arrays.
 pub trait TypedObjectArrayExt: TypedArrayExt {
@@ -33,6 +96,90 @@ pub trait TypedObjectArrayExt: TypedArrayExt {
         index: i32,
         value: Option<&Object<R, T>>,
     ) -> Result<(), LocalObject<'_, JavaThrowable>>;
+
+    /// Returns an iterator over elements of object array, `null` elements are yielded as `None`.
+    fn typed_iter_array_elements<'a, T: ObjectType, R: StrongRef>(
+        &'a self,
+        arr: &'a Object<R, Array<T>>,
+    ) -> Result<ArrayElements<'a, R, T>, LocalObject<'a, JavaThrowable>>;
+
+    /// Calls `f` with the index and the value of each element of object array, stops at the first error.
+    ///
+    /// Elements are visited in pushed local frames, all local references created in `f` are released periodically.
+    ///
+    /// # Safety
+    ///
+    /// * `f` must create local references only with the given [`JNIEnv`]. References created with a captured
+    ///   outer env are bound to the outer lifetime, but they are released when the frame is popped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use typed_jni::{Array, Object, TypedObjectArrayExt, TypedStringExt, builtin::JavaString, core::{JNIEnv, StrongRef}};
+    ///
+    /// fn total_length(env: &JNIEnv, array: &Object<impl StrongRef, Array<JavaString>>) -> usize {
+    ///     let mut total = 0;
+    ///
+    ///     unsafe {
+    ///         env.typed_for_each_array_element_in_frame(array, |env, _, s| {
+    ///             total += s.map(|s| env.typed_get_string(&s).len()).unwrap_or_default();
+    ///
+    ///             Ok(())
+    ///         })
+    ///     }
+    ///     .unwrap();
+    ///
+    ///     total
+    /// }
+    /// ```
+    ///
+    /// Capturing the outer env would let references escape the frame, so the call requires `unsafe`:
+    ///
+    /// ```rust,compile_fail,E0133
+    /// use typed_jni::{Array, LocalObject, Object, TypedObjectArrayExt, TypedStringExt, builtin::JavaString, core::{JNIEnv, StrongRef}};
+    ///
+    /// fn escape<'env>(env: &'env JNIEnv, array: &Object<impl StrongRef, Array<JavaString>>) -> Option<LocalObject<'env, JavaString>> {
+    ///     let mut escaped = None;
+    ///
+    ///     env.typed_for_each_array_element_in_frame(array, |_, _, _| {
+    ///         escaped = Some(env.typed_new_string("x"));
+    ///
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    ///
+    ///     escaped
+    /// }
+    /// ```
+    unsafe fn typed_for_each_array_element_in_frame<T, R, F>(
+        &self,
+        arr: &Object<R, Array<T>>,
+        f: F,
+    ) -> Result<(), LocalObject<'_, JavaThrowable>>
+    where
+        T: ObjectType,
+        R: StrongRef,
+        F: for<'scope> FnMut(
+            &'scope JNIEnv,
+            i32,
+            Option<LocalObject<'scope, T>>,
+        ) -> Result<(), LocalObject<'scope, JavaThrowable>>;
+
+    /// Creates a new object array of `cls` with elements taken from an exact sized iterator in one pass.
+    ///
+    /// Elements can be either objects or `Option`s of objects, where `None` is stored as `null`.
+    fn typed_collect_array<T, R, IR, I>(
+        &self,
+        cls: &Class<R, T>,
+        items: I,
+    ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>>
+    where
+        T: ObjectType,
+        R: StrongRef,
+        IR: StrongRef,
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        I::Item: Into<Option<Object<IR, T>>>;
 }
 
 impl<'vm> TypedObjectArrayExt for JNIEnv<'vm> {
@@ -84,4 +231,88 @@ impl<'vm> TypedObjectArrayExt for JNIEnv<'vm> {
                 .map_err(|err| LocalObject::from_ref(err))
         }
     }
+
+    fn typed_iter_array_elements<'a, T: ObjectType, R: StrongRef>(
+        &'a self,
+        arr: &'a Object<R, Array<T>>,
+    ) -> Result<ArrayElements<'a, R, T>, LocalObject<'a, JavaThrowable>> {
+        let len = self.typed_get_array_length(arr)?;
+
+        Ok(ArrayElements {
+            env: self,
+            array: arr,
+            front: 0,
+            back: len,
+        })
+    }
+
+    unsafe fn typed_for_each_array_element_in_frame<T, R, F>(
+        &self,
+        arr: &Object<R, Array<T>>,
+        mut f: F,
+    ) -> Result<(), LocalObject<'_, JavaThrowable>>
+    where
+        T: ObjectType,
+        R: StrongRef,
+        F: for<'scope> FnMut(
+            &'scope JNIEnv,
+            i32,
+            Option<LocalObject<'scope, T>>,
+        ) -> Result<(), LocalObject<'scope, JavaThrowable>>,
+    {
+        let len = self.typed_get_array_length(arr)?;
+
+        for chunk in (0..len).step_by(FRAME_CAPACITY) {
+            // `f` follows the same contract
+            unsafe {
+                with_frame(self, |env| {
+                    for index in chunk..len.min(chunk + FRAME_CAPACITY as i32) {
+                        let element = env.typed_get_array_element(arr, index)?;
+
+                        f(env, index, element)?;
+                    }
+
+                    Ok(())
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn typed_collect_array<T, R, IR, I>(
+        &self,
+        cls: &Class<R, T>,
+        items: I,
+    ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>>
+    where
+        T: ObjectType,
+        R: StrongRef,
+        IR: StrongRef,
+        I: IntoIterator,
+        I::IntoIter: ExactSizeIterator,
+        I::Item: Into<Option<Object<IR, T>>>,
+    {
+        let items = items.into_iter();
+
+        let len = i32::try_from(items.len()).map_err(|_| {
+            throwable::helper::new_named_exception(
+                self,
+                c"java/lang/IllegalArgumentException",
+                &format!("array length {} out of range", items.len()),
+            )
+        })?;
+
+        let array = self.typed_new_array(cls, len)?;
+
+        // each element is released right after stored
+        for (index, item) in items.take(len as usize).enumerate() {
+            unsafe {
+                self.set_object_array_element(&*array, index as i32, item.into().as_deref())
+                    .map_err(|err| LocalObject::from_ref(err))?;
+            }
+        }
+
+        Ok(array)
+    }
 }
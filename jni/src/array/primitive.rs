@@ -1,8 +1,9 @@
+use alloc::{format, string::String, vec, vec::Vec};
 use core::ops::{Deref, DerefMut};
 
 use typed_jni_core::{ArrayElementsGuard, JNIEnv, StrongRef};
 
-use crate::{Array, LocalObject, Object, TypedArrayExt, TypedRef, array::primitive_impls, builtin::JavaThrowable};
+use crate::{Array, LocalObject, Object, TypedArrayExt, TypedRef, array::primitive_impls, builtin::JavaThrowable, throwable};
 
 /// A guard for byte array elements.
 pub struct BytesArrayElementsGuard<'a, R: StrongRef>(ArrayElementsGuard<'a, i8, R>);
@@ -33,7 +34,9 @@ impl<'a, R: StrongRef> DerefMut for BytesArrayElementsGuard<'a, R> {
 /// This trait provides methods for working with primitive arrays.
 ///
 /// Valid primitive types:
+/// - `bool`: boolean (non-zero `jboolean`s are read as `true`)
 /// - `i8`: byte (also allow access as `u8`)
+/// - `u16`: char (also allow conversion from and to `str` and `char`s)
 /// - `i16`: short
 /// - `i32`: int
 /// - `i64`: long
@@ -46,6 +49,24 @@ pub trait TypedPrimitiveArrayExt: TypedArrayExt {
         len: i32,
     ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>>;
 
+    /// Creates a new primitive array with elements copied from the slice.
+    fn typed_new_array_from_slice<T: primitive_impls::PrimitiveArrayElement>(
+        &self,
+        values: &[T],
+    ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>>;
+
+    /// Creates a new primitive array with elements collected from the iterator.
+    fn typed_new_array_from_iter<T: primitive_impls::PrimitiveArrayElement>(
+        &self,
+        values: impl IntoIterator<Item = T>,
+    ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>>;
+
+    /// Reads all elements of the array into a `Vec`.
+    fn typed_array_to_vec<R: StrongRef, T: primitive_impls::PrimitiveArrayElement>(
+        &self,
+        array: &Object<R, Array<T>>,
+    ) -> Result<Vec<T>, LocalObject<'_, JavaThrowable>>;
+
     /// Reads a region of the array into the provided slice.
     fn typed_get_array_region<R: StrongRef, T: primitive_impls::PrimitiveArrayElement>(
         &self,
@@ -62,11 +83,11 @@ pub trait TypedPrimitiveArrayExt: TypedArrayExt {
         values: &[T],
     ) -> Result<(), LocalObject<'_, JavaThrowable>>;
 
-    /// Get array elements.
+    /// Get array elements, `bool` arrays expose raw `jboolean` bytes whose non-zero values are `true`.
     fn typed_get_array_elements<'env, 'a, R: StrongRef, T: primitive_impls::PrimitiveArrayElement>(
         &'env self,
         array: &'a Object<R, Array<T>>,
-    ) -> Result<ArrayElementsGuard<'a, T, R>, LocalObject<'env, JavaThrowable>>
+    ) -> Result<ArrayElementsGuard<'a, T::Raw, R>, LocalObject<'env, JavaThrowable>>
     where
         'env: 'a;
 
@@ -93,6 +114,36 @@ pub trait TypedPrimitiveArrayExt: TypedArrayExt {
     ) -> Result<BytesArrayElementsGuard<'a, R>, LocalObject<'env, JavaThrowable>>
     where
         'env: 'a;
+
+    /// Creates a new byte array with bytes copied from the slice.
+    fn typed_new_bytes_array(&self, values: &[u8]) -> Result<LocalObject<'_, Array<i8>>, LocalObject<'_, JavaThrowable>>;
+
+    /// Reads all bytes of the byte array into a `Vec`.
+    fn typed_bytes_array_to_vec<R: StrongRef>(
+        &self,
+        array: &Object<R, Array<i8>>,
+    ) -> Result<Vec<u8>, LocalObject<'_, JavaThrowable>>;
+
+    /// Creates a new char array of UTF-16 code units of the string.
+    fn typed_new_char_array_from_str(&self, s: &str) -> Result<LocalObject<'_, Array<u16>>, LocalObject<'_, JavaThrowable>>;
+
+    /// Creates a new char array of UTF-16 code units of the chars, chars out of the BMP take two units.
+    fn typed_new_char_array_from_chars(
+        &self,
+        chars: &[char],
+    ) -> Result<LocalObject<'_, Array<u16>>, LocalObject<'_, JavaThrowable>>;
+
+    /// Decodes the char array as UTF-16, unpaired surrogates are replaced with `U+FFFD`.
+    fn typed_char_array_to_string<R: StrongRef>(
+        &self,
+        array: &Object<R, Array<u16>>,
+    ) -> Result<String, LocalObject<'_, JavaThrowable>>;
+
+    /// Decodes the char array as UTF-16 into chars, unpaired surrogates are replaced with `U+FFFD`.
+    fn typed_char_array_to_chars<R: StrongRef>(
+        &self,
+        array: &Object<R, Array<u16>>,
+    ) -> Result<Vec<char>, LocalObject<'_, JavaThrowable>>;
 }
 
 impl<'vm> TypedPrimitiveArrayExt for JNIEnv<'vm> {
@@ -107,6 +158,43 @@ impl<'vm> TypedPrimitiveArrayExt for JNIEnv<'vm> {
         }
     }
 
+    fn typed_new_array_from_slice<T: primitive_impls::PrimitiveArrayElement>(
+        &self,
+        values: &[T],
+    ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>> {
+        let len = i32::try_from(values.len()).map_err(|_| {
+            throwable::helper::new_named_exception(
+                self,
+                c"java/lang/IllegalArgumentException",
+                &format!("array length {} out of range", values.len()),
+            )
+        })?;
+
+        let array = self.typed_new_primitive_array(len)?;
+        self.typed_set_array_region(&array, 0, values)?;
+
+        Ok(array)
+    }
+
+    fn typed_new_array_from_iter<T: primitive_impls::PrimitiveArrayElement>(
+        &self,
+        values: impl IntoIterator<Item = T>,
+    ) -> Result<LocalObject<'_, Array<T>>, LocalObject<'_, JavaThrowable>> {
+        self.typed_new_array_from_slice(&values.into_iter().collect::<Vec<_>>())
+    }
+
+    fn typed_array_to_vec<R: StrongRef, T: primitive_impls::PrimitiveArrayElement>(
+        &self,
+        array: &Object<R, Array<T>>,
+    ) -> Result<Vec<T>, LocalObject<'_, JavaThrowable>> {
+        let len = self.typed_get_array_length(array)?;
+
+        let mut values = vec![T::default(); len as usize];
+        self.typed_get_array_region(array, 0, &mut values)?;
+
+        Ok(values)
+    }
+
     fn typed_get_array_region<R: StrongRef, T: primitive_impls::PrimitiveArrayElement>(
         &self,
         array: &Object<R, Array<T>>,
@@ -128,7 +216,7 @@ impl<'vm> TypedPrimitiveArrayExt for JNIEnv<'vm> {
     fn typed_get_array_elements<'env, 'a, R: StrongRef, T: primitive_impls::PrimitiveArrayElement>(
         &'env self,
         array: &'a Object<R, Array<T>>,
-    ) -> Result<ArrayElementsGuard<'a, T, R>, LocalObject<'env, JavaThrowable>>
+    ) -> Result<ArrayElementsGuard<'a, T::Raw, R>, LocalObject<'env, JavaThrowable>>
     where
         'env: 'a,
     {
@@ -170,4 +258,56 @@ impl<'vm> TypedPrimitiveArrayExt for JNIEnv<'vm> {
     {
         self.typed_get_array_elements(array).map(BytesArrayElementsGuard)
     }
+
+    fn typed_new_bytes_array(&self, values: &[u8]) -> Result<LocalObject<'_, Array<i8>>, LocalObject<'_, JavaThrowable>> {
+        let values = unsafe { core::slice::from_raw_parts(values.as_ptr() as *const i8, values.len()) };
+
+        self.typed_new_array_from_slice(values)
+    }
+
+    fn typed_bytes_array_to_vec<R: StrongRef>(
+        &self,
+        array: &Object<R, Array<i8>>,
+    ) -> Result<Vec<u8>, LocalObject<'_, JavaThrowable>> {
+        let len = self.typed_get_array_length(array)?;
+
+        let mut values = vec![0u8; len as usize];
+        self.typed_get_bytes_array_region(array, 0, &mut values)?;
+
+        Ok(values)
+    }
+
+    fn typed_new_char_array_from_str(&self, s: &str) -> Result<LocalObject<'_, Array<u16>>, LocalObject<'_, JavaThrowable>> {
+        self.typed_new_array_from_iter(s.encode_utf16())
+    }
+
+    fn typed_new_char_array_from_chars(
+        &self,
+        chars: &[char],
+    ) -> Result<LocalObject<'_, Array<u16>>, LocalObject<'_, JavaThrowable>> {
+        let mut units = Vec::with_capacity(chars.len());
+        for c in chars {
+            units.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+        }
+
+        self.typed_new_array_from_slice(&units)
+    }
+
+    fn typed_char_array_to_string<R: StrongRef>(
+        &self,
+        array: &Object<R, Array<u16>>,
+    ) -> Result<String, LocalObject<'_, JavaThrowable>> {
+        self.typed_char_array_to_chars(array).map(|chars| chars.into_iter().collect())
+    }
+
+    fn typed_char_array_to_chars<R: StrongRef>(
+        &self,
+        array: &Object<R, Array<u16>>,
+    ) -> Result<Vec<char>, LocalObject<'_, JavaThrowable>> {
+        let units = self.typed_array_to_vec(array)?;
+
+        Ok(char::decode_utf16(units)
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect())
+    }
 }
@@ -1,9 +1,14 @@
+use alloc::vec;
+
 use typed_jni_core::{ArrayElementsGuard, JNIEnv, LocalRef, StrongRef};
 
 use crate::PrimitiveType;
 
 /// Function table of primitive arrays.
-pub trait PrimitiveArrayElement: PrimitiveType + Sized {
+pub trait PrimitiveArrayElement: PrimitiveType + Copy + Default + Sized {
+    /// Elements as held by the Java VM, `u8` for `bool` since any non-zero `jboolean` is `true`.
+    type Raw: Copy;
+
     fn new_instance<'env>(env: &'env JNIEnv<'_>, len: i32) -> Result<LocalRef<'env>, LocalRef<'env>>;
 
     unsafe fn get_region<'env, R: StrongRef>(
@@ -23,7 +28,7 @@ pub trait PrimitiveArrayElement: PrimitiveType + Sized {
     unsafe fn get_elements<'env, 'a, R: StrongRef>(
         env: &'env JNIEnv<'_>,
         array: &'a R,
-    ) -> Result<ArrayElementsGuard<'a, Self, R>, LocalRef<'env>>
+    ) -> Result<ArrayElementsGuard<'a, Self::Raw, R>, LocalRef<'env>>
     where
         'env: 'a;
 }
@@ -31,6 +36,8 @@ pub trait PrimitiveArrayElement: PrimitiveType + Sized {
 macro_rules! impl_primitive_array_element {
     ($typ:ty, $new:ident, $get_region:ident, $set_region:ident, $get_elements:ident) => {
         impl PrimitiveArrayElement for $typ {
+            type Raw = $typ;
+
             fn new_instance<'env>(env: &'env JNIEnv<'_>, len: i32) -> Result<LocalRef<'env>, LocalRef<'env>> {
                 env.$new(len)
             }
@@ -56,7 +63,7 @@ macro_rules! impl_primitive_array_element {
             unsafe fn get_elements<'env, 'a, R: StrongRef>(
                 env: &'env JNIEnv<'_>,
                 array: &'a R,
-            ) -> Result<ArrayElementsGuard<'a, Self, R>, LocalRef<'env>>
+            ) -> Result<ArrayElementsGuard<'a, Self::Raw, R>, LocalRef<'env>>
             where
                 'env: 'a,
             {
@@ -66,13 +73,54 @@ macro_rules! impl_primitive_array_element {
     };
 }
 
-impl_primitive_array_element!(
-    bool,
-    new_boolean_array,
-    get_boolean_array_region,
-    set_boolean_array_region,
-    get_boolean_array_elements
-);
+impl PrimitiveArrayElement for bool {
+    type Raw = u8;
+
+    fn new_instance<'env>(env: &'env JNIEnv<'_>, len: i32) -> Result<LocalRef<'env>, LocalRef<'env>> {
+        env.new_boolean_array(len)
+    }
+
+    unsafe fn get_region<'env, R: StrongRef>(
+        env: &'env JNIEnv<'_>,
+        array: &R,
+        offset: i32,
+        buf: &mut [Self],
+    ) -> Result<(), LocalRef<'env>> {
+        // read as bytes, since values other than `0` and `1` are not valid `bool`s
+        let mut bytes = vec![0u8; buf.len()];
+        unsafe { env.get_boolean_array_region(array, offset, &mut bytes)? };
+
+        for (b, v) in bytes.into_iter().zip(buf) {
+            *v = b != 0;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn set_region<'env, R: StrongRef>(
+        env: &'env JNIEnv<'_>,
+        array: &R,
+        offset: i32,
+        values: &[Self],
+    ) -> Result<(), LocalRef<'env>> {
+        unsafe {
+            let values = core::slice::from_raw_parts(values.as_ptr() as *const u8, values.len());
+
+            env.set_boolean_array_region(array, offset, values)
+        }
+    }
+
+    unsafe fn get_elements<'env, 'a, R: StrongRef>(
+        env: &'env JNIEnv<'_>,
+        array: &'a R,
+    ) -> Result<ArrayElementsGuard<'a, Self::Raw, R>, LocalRef<'env>>
+    where
+        'env: 'a,
+    {
+        unsafe { env.get_boolean_array_elements(array) }
+    }
+}
+
 impl_primitive_array_element!(
     i8,
     new_byte_array,
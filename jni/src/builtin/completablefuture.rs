@@ -0,0 +1,9 @@
+use crate::{ObjectType, Signature, Type};
+
+pub struct JavaCompletableFuture;
+
+impl Type for JavaCompletableFuture {
+    const SIGNATURE: Signature = Signature::Object("java/util/concurrent/CompletableFuture");
+}
+
+impl ObjectType for JavaCompletableFuture {}
@@ -0,0 +1,22 @@
+use crate::{ObjectType, Signature, Type};
+
+macro_rules! define_builtin_interface {
+    ($name:ident, $class:literal) => {
+        pub struct $name;
+
+        impl Type for $name {
+            const SIGNATURE: Signature = Signature::Object($class);
+        }
+
+        impl ObjectType for $name {}
+    };
+}
+
+define_builtin_interface!(JavaRunnable, "java/lang/Runnable");
+define_builtin_interface!(JavaCallable, "java/util/concurrent/Callable");
+define_builtin_interface!(JavaSupplier, "java/util/function/Supplier");
+define_builtin_interface!(JavaConsumer, "java/util/function/Consumer");
+define_builtin_interface!(JavaBiConsumer, "java/util/function/BiConsumer");
+define_builtin_interface!(JavaFunction, "java/util/function/Function");
+define_builtin_interface!(JavaBiFunction, "java/util/function/BiFunction");
+define_builtin_interface!(JavaPredicate, "java/util/function/Predicate");
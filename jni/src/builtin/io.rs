@@ -0,0 +1,17 @@
+use crate::{ObjectType, Signature, Type};
+
+pub struct JavaInputStream;
+
+impl Type for JavaInputStream {
+    const SIGNATURE: Signature = Signature::Object("java/io/InputStream");
+}
+
+impl ObjectType for JavaInputStream {}
+
+pub struct JavaOutputStream;
+
+impl Type for JavaOutputStream {
+    const SIGNATURE: Signature = Signature::Object("java/io/OutputStream");
+}
+
+impl ObjectType for JavaOutputStream {}
@@ -1,11 +1,19 @@
 mod class;
 mod classloader;
+mod completablefuture;
+mod function;
+mod io;
 mod object;
 mod string;
+mod threadgroup;
 mod throwable;
 
 pub use class::*;
 pub use classloader::*;
+pub use completablefuture::*;
+pub use function::*;
+pub use io::*;
 pub use object::*;
 pub use string::*;
+pub use threadgroup::*;
 pub use throwable::*;
@@ -0,0 +1,17 @@
+use typed_jni_core::{StrongRef, ThreadGroupRef};
+
+use crate::{Object, ObjectType, Signature, Type};
+
+pub struct JavaThreadGroup;
+
+impl Type for JavaThreadGroup {
+    const SIGNATURE: Signature = Signature::Object("java/lang/ThreadGroup");
+}
+
+impl ObjectType for JavaThreadGroup {}
+
+unsafe impl<R: StrongRef> ThreadGroupRef for Object<R, JavaThreadGroup> {
+    fn as_thread_group(&self) -> &dyn StrongRef {
+        &**self
+    }
+}
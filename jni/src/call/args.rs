@@ -8,6 +8,7 @@ use crate::{LocalObject, Null, Object, ObjectType, Signature, Type, builtin::Jav
 /// - Primitives: `bool`, `i8`, `u16`, `i16`, `i32`, `i64`, `f32`, `f64`
 /// - Any [Object] with [`StrongRef`]: `Object<impl StrongRef, Type>`, `Option<Object<impl StrongRef, Type>>`
 /// - Any reference to [Object] with [`StrongRef`]: `&Object<impl StrongRef, Type>`, `Option<&Object<impl StrongRef, Type>>`
+/// - `&dyn ToArg`, e.g. heterogeneous items of [`VarArgs`](crate::VarArgs)
 pub trait ToArg {
     fn to_arg(&self) -> Arg<'_>;
 }
@@ -61,6 +62,12 @@ impl<R: StrongRef, T: ObjectType> ToArg for Option<&Object<R, T>> {
     }
 }
 
+impl ToArg for &dyn ToArg {
+    fn to_arg(&self) -> Arg<'_> {
+        (**self).to_arg()
+    }
+}
+
 impl<T: ObjectType> ToArg for Null<T> {
     fn to_arg(&self) -> Arg<'_> {
         Arg::Object(None)
@@ -72,7 +79,16 @@ impl<T: ObjectType> ToArg for Null<T> {
 /// # Safety
 ///
 /// The implementer must ensure that the `signature` matches the signature of the arguments.
+///
+/// If [`Args::SIGNATURES`] is `Some`, it must be exactly the signatures yielded by `signature` for every value,
+/// in the same order, and the arguments applied by `apply_on` must match them, since method descriptors are
+/// built from it at compile time without looking at the values.
 pub unsafe trait Args: Sized {
+    /// Signatures of the arguments if they are known at compile time, see [`Descriptor`](crate::Descriptor).
+    ///
+    /// Must be `None` if the signatures depend on the values.
+    const SIGNATURES: Option<&'static [Signature]> = None;
+
     fn signature(&self) -> impl IntoIterator<Item = Signature> + Clone + '_;
 
     /// Apply the arguments to a JNI call.
@@ -92,6 +108,8 @@ pub unsafe trait Args: Sized {
 }
 
 unsafe impl Args for () {
+    const SIGNATURES: Option<&'static [Signature]> = Some(&[]);
+
     fn signature(&self) -> impl IntoIterator<Item = Signature> + Clone + '_ {
         []
     }
@@ -113,6 +131,8 @@ unsafe impl Args for () {
 macro_rules! impl_fixed_args {
     ($($n:ident),*) => {
         unsafe impl<$($n: ToArg + Type),*> Args for ($($n,)*) {
+            const SIGNATURES: Option<&'static [Signature]> = Some(&[$($n::SIGNATURE,)*]);
+
             fn signature(&self) -> impl IntoIterator<Item = Signature> + Clone + '_ {
                 [$($n::SIGNATURE,)*]
             }
@@ -1,12 +1,11 @@
 mod args;
 mod target;
+mod varargs;
 
 use typed_jni_core::{JNIEnv, StrongRef};
 
-pub use self::{args::*, target::Target};
-use crate::{
-    Class, LocalObject, ObjectType, Signature, Type, TypedRef, builtin::JavaThrowable, resolver, resolver::helper::MemberKind,
-};
+pub use self::{args::*, target::Target, varargs::*};
+use crate::{Class, LocalObject, ObjectType, Type, TypedRef, builtin::JavaThrowable, resolver, resolver::helper::MemberKind};
 
 /// Extension methods for typed method call.
 pub trait TypedCallExt {
@@ -22,6 +21,7 @@ pub trait TypedCallExt {
     /// * `()` - No arguments.
     /// * `(impl ToArg,)` - A single argument that implements [`ToArg`].
     /// * `(impl ToArg, ...)` - Multiple arguments that implement [`ToArg`]. (Max 32 args)
+    /// * `(impl ToArg, ..., VarArgs<Type, _>)` - Arguments with a trailing Java varargs parameter, see [`VarArgs`].
     /// * `&[&dyn DynArg]` - Any number of arguments that implement [`ToArg`]. e.g. `&[0i32 as &dyn ToArg, 2i64, false]`
     ///
     /// # Returns
@@ -45,6 +45,7 @@ pub trait TypedCallExt {
     /// * `()` - No arguments.
     /// * `(impl ToArg,)` - A single argument that implements [`ToArg`].
     /// * `(impl ToArg, ...)` - Multiple arguments that implement [`ToArg`]. (Max 32 args)
+    /// * `(impl ToArg, ..., VarArgs<Type, _>)` - Arguments with a trailing Java varargs parameter, see [`VarArgs`].
     /// * `&[&dyn DynArg]` - Any number of arguments that implement [`ToArg`]. e.g. `&[0i32 as &dyn ToArg, 2i64, false]`
     ///
     /// # Returns
@@ -67,7 +68,17 @@ impl<'vm> TypedCallExt for JNIEnv<'vm> {
     {
         unsafe {
             let name = resolver::helper::build_member_name(self, name, MemberKind::Method)?;
-            let signature = resolver::helper::build_method_signature(self, R::SIGNATURE, args.signature())?;
+            let signature = resolver::helper::method_signature_of::<R, A>(self, &args)?;
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!(
+                "call_method",
+                class = %<T::Type as Type>::SIGNATURE,
+                name = ?&*name,
+                sig = ?&*signature,
+                is_static = T::STATIC
+            )
+            .entered();
 
             if T::STATIC {
                 let method = resolver::resolve_method::<true, _>(self, &**this, &name, &signature)?;
@@ -90,7 +101,10 @@ impl<'vm> TypedCallExt for JNIEnv<'vm> {
         A: Args,
     {
         unsafe {
-            let signature = resolver::helper::build_method_signature(self, Signature::Void, args.signature())?;
+            let signature = resolver::helper::method_signature_of::<(), A>(self, &args)?;
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!("new_object", class = %T::SIGNATURE, sig = ?&*signature).entered();
 
             let method = resolver::resolve_method::<false, _>(self, &**cls, c"<init>", &signature)?;
 
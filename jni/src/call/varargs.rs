@@ -0,0 +1,241 @@
+use alloc::{format, string::String, vec::Vec};
+use core::marker::PhantomData;
+
+use typed_jni_core::{Arg, JNIEnv, LocalRef, MethodID, StrongRef};
+
+use crate::{
+    Args, LocalObject, Null, Object, ObjectType, Signature, ToArg, Type, TypedRef,
+    builtin::{JavaObject, JavaThrowable},
+    call::target::Target,
+    resolver, throwable,
+};
+
+/// A trailing Java varargs parameter of element type `T`, e.g. `Object...` of `String.format(String, Object...)`.
+///
+/// The Java array is built from `items` when the call is applied and released right after the call.
+/// Items are checked against `T` at compile time by [`VarArgValue`].
+///
+/// # Example
+///
+/// ```rust
+/// use typed_jni::{LocalClass, LocalObject, ToArg, TypedCallExt, TypedClassExt, TypedStringExt, VarArgs, builtin::{JavaObject, JavaString}, core::JNIEnv};
+///
+/// fn format<'env>(env: &'env JNIEnv<'static>) -> LocalObject<'env, JavaString> {
+///     let c_string: LocalClass<JavaString> = env.typed_find_class().unwrap();
+///     let name = env.typed_new_string("world");
+///
+///     env.typed_call_method(&c_string, "format", (env.typed_new_string("hello %s"), VarArgs::<JavaObject, _>::new([&name as &dyn ToArg])))
+///         .unwrap()
+/// }
+/// ```
+pub struct VarArgs<T: Type + 'static, I> {
+    items: I,
+    _typ: PhantomData<T>,
+}
+
+impl<T: Type + 'static, I: IntoIterator> VarArgs<T, I>
+where
+    I::Item: VarArgValue<T>,
+{
+    /// Rejects varargs of `void` when `new` is instantiated.
+    const NOT_VOID: () = assert!(!matches!(T::SIGNATURE, Signature::Void), "varargs of void");
+
+    /// Creates varargs of `items`, see [`VarArgValue`] for the supported item types.
+    ///
+    /// `T` must not be `()`, which fails to compile.
+    pub fn new(items: I) -> Self {
+        let () = Self::NOT_VOID;
+
+        Self {
+            items,
+            _typ: PhantomData,
+        }
+    }
+
+    /// Builds the Java array of items.
+    fn into_array<'env>(self, env: &'env JNIEnv) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>> {
+        let items = self.items.into_iter().collect::<Vec<_>>();
+        let args = items.iter().map(|item| item.to_arg()).collect::<Vec<_>>();
+
+        let len = i32::try_from(args.len()).map_err(|_| {
+            throwable::helper::new_named_exception(env, c"java/lang/IllegalArgumentException", "too many varargs")
+        })?;
+
+        macro_rules! primitive_array {
+            ($variant:ident, $new:ident, $set_region:ident) => {{
+                let values = args
+                    .iter()
+                    .enumerate()
+                    .map(|(index, arg)| match arg {
+                        // `bool`s are stored as `jboolean` bytes
+                        Arg::$variant(v) => Ok((*v).into()),
+                        _ => Err(index),
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|index| mismatched(env, index, T::SIGNATURE))?;
+
+                unsafe {
+                    let array = env.$new(len).map_err(|err| LocalObject::from_ref(err))?;
+
+                    env.$set_region(&array, 0, &values)
+                        .map_err(|err| LocalObject::from_ref(err))?;
+
+                    Ok(array)
+                }
+            }};
+        }
+
+        match T::SIGNATURE {
+            Signature::Boolean => primitive_array!(Boolean, new_boolean_array, set_boolean_array_region),
+            Signature::Byte => primitive_array!(Byte, new_byte_array, set_byte_array_region),
+            Signature::Char => primitive_array!(Char, new_char_array, set_char_array_region),
+            Signature::Short => primitive_array!(Short, new_short_array, set_short_array_region),
+            Signature::Int => primitive_array!(Int, new_int_array, set_int_array_region),
+            Signature::Long => primitive_array!(Long, new_long_array, set_long_array_region),
+            Signature::Float => primitive_array!(Float, new_float_array, set_float_array_region),
+            Signature::Double => primitive_array!(Double, new_double_array, set_double_array_region),
+            Signature::Void => unreachable!("BROKEN: varargs of void are rejected by `new`"),
+            Signature::Object(_) | Signature::Array(_) => unsafe {
+                let name = resolver::helper::class_name_of::<T>(env)?;
+                let cls = resolver::resolve_class(env, &name)?;
+                let array = env.new_object_array(&cls, len).map_err(|err| LocalObject::from_ref(err))?;
+
+                for (index, arg) in args.into_iter().enumerate() {
+                    let Arg::Object(obj) = arg else {
+                        return Err(mismatched(env, index, T::SIGNATURE));
+                    };
+
+                    env.set_object_array_element(&array, index as i32, obj)
+                        .map_err(|err| LocalObject::from_ref(err))?;
+                }
+
+                Ok(array)
+            },
+        }
+    }
+}
+
+/// This trait is implemented for all types that can be items of [`VarArgs`] of element type `T`.
+///
+/// Supported Types:
+///
+/// * Primitive types: `bool`, `i8`, `u16`, `i16`, `i32`, `i64`, `f32`, `f64` of themselves
+/// * Object types: `Object<impl StrongRef, T>`, `&Object<impl StrongRef, T>`, `Option` of them and `Null<T>`
+/// * `&dyn ToArg` of `JavaObject`, opting in to heterogeneous items of `Object...`, which are checked when the call is applied
+///
+/// Items of other types fail to compile:
+///
+/// ```rust,compile_fail,E0277
+/// use typed_jni::VarArgs;
+///
+/// let _ = VarArgs::<i32, _>::new([1i64]);
+/// ```
+///
+/// # Safety
+///
+/// This trait should not be implemented manually.
+pub unsafe trait VarArgValue<T: Type>: ToArg {}
+
+macro_rules! impl_var_arg_value_for_primitive {
+    ($($typ:ty),*) => {
+        $(
+            unsafe impl VarArgValue<$typ> for $typ {}
+        )*
+    };
+}
+
+impl_var_arg_value_for_primitive!(bool, i8, u16, i16, i32, i64, f32, f64);
+
+unsafe impl<R: StrongRef, T: ObjectType> VarArgValue<T> for Object<R, T> {}
+unsafe impl<R: StrongRef, T: ObjectType> VarArgValue<T> for &Object<R, T> {}
+unsafe impl<R: StrongRef, T: ObjectType> VarArgValue<T> for Option<Object<R, T>> {}
+unsafe impl<R: StrongRef, T: ObjectType> VarArgValue<T> for Option<&Object<R, T>> {}
+unsafe impl<T: ObjectType> VarArgValue<T> for Null<T> {}
+unsafe impl VarArgValue<JavaObject> for &dyn ToArg {}
+
+fn mismatched<'env>(env: &'env JNIEnv, index: usize, signature: Signature) -> LocalObject<'env, JavaThrowable> {
+    let mut name = String::new();
+    signature.write_as_java_name_to(&mut name).unwrap();
+
+    throwable::helper::new_named_exception(
+        env,
+        c"java/lang/IllegalArgumentException",
+        &format!("varargs element {index} is not {name}"),
+    )
+}
+
+impl<T: Type + 'static, I> Type for VarArgs<T, I> {
+    const SIGNATURE: Signature = Signature::Array(&T::SIGNATURE);
+}
+
+macro_rules! impl_varargs {
+    ($($n:ident),*) => {
+        unsafe impl<$($n: ToArg + Type,)* V: Type + 'static, I: IntoIterator> Args for ($($n,)* VarArgs<V, I>,)
+        where
+            I::Item: VarArgValue<V>,
+        {
+            const SIGNATURES: Option<&'static [Signature]> = Some(&[$($n::SIGNATURE,)* <VarArgs<V, I> as Type>::SIGNATURE]);
+
+            fn signature(&self) -> impl IntoIterator<Item = Signature> + Clone + '_ {
+                [$($n::SIGNATURE,)* <VarArgs<V, I> as Type>::SIGNATURE]
+            }
+
+            unsafe fn apply_on<'env, const STATIC: bool, T, R>(
+                self,
+                env: &'env JNIEnv,
+                this: &T,
+                method: MethodID<STATIC>,
+            ) -> Result<R, LocalObject<'env, JavaThrowable>>
+            where
+                T: StrongRef,
+                R: Target<'env>,
+            {
+                unsafe {
+                    #[allow(non_snake_case)]
+                    let ($($n,)* varargs,) = self;
+
+                    // released after the call
+                    let array = varargs.into_array(env)?;
+
+                    R::call(env, this, method, [$($n.to_arg(),)* Arg::Object(Some(&array))])
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+const _: () = {
+    impl_varargs!();
+    impl_varargs!(A1);
+    impl_varargs!(A1, A2);
+    impl_varargs!(A1, A2, A3);
+    impl_varargs!(A1, A2, A3, A4);
+    impl_varargs!(A1, A2, A3, A4, A5);
+    impl_varargs!(A1, A2, A3, A4, A5, A6);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25, A26);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25, A26, A27);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25, A26, A27, A28);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25, A26, A27, A28, A29);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25, A26, A27, A28, A29, A30);
+    impl_varargs!(A1, A2, A3, A4, A5, A6, A7, A8, A9, A10, A11, A12, A13, A14, A15, A16, A17, A18, A19, A20, A21, A22, A23, A24, A25, A26, A27, A28, A29, A30, A31);
+};
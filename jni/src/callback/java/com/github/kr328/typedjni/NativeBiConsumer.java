@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.function.BiConsumer;
+
+final class NativeBiConsumer extends NativeCallback implements BiConsumer<Object, Object> {
+    private NativeBiConsumer(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public void accept(Object t, Object u) {
+        invoke(t, u);
+    }
+}
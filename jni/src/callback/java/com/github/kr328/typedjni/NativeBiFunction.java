@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.function.BiFunction;
+
+final class NativeBiFunction extends NativeCallback implements BiFunction<Object, Object, Object> {
+    private NativeBiFunction(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public Object apply(Object t, Object u) {
+        return invoke(t, u);
+    }
+}
@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.concurrent.Callable;
+
+final class NativeCallable extends NativeCallback implements Callable<Object> {
+    private NativeCallable(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public Object call() {
+        return invoke(null, null);
+    }
+}
@@ -0,0 +1,21 @@
+package com.github.kr328.typedjni;
+
+/**
+ * A callback implemented by a Rust closure.
+ * <p>
+ * The closure is released by {@link NativeCleaner} once the callback becomes unreachable.
+ */
+abstract class NativeCallback {
+    private final long handle;
+
+    NativeCallback(long handle) {
+        this.handle = handle;
+    }
+
+    // an instance method keeps this callback reachable while the closure is running
+    private native Object invoke(long handle, Object a, Object b);
+
+    final Object invoke(Object a, Object b) {
+        return invoke(handle, a, b);
+    }
+}
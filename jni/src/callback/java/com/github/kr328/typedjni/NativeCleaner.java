@@ -0,0 +1,62 @@
+package com.github.kr328.typedjni;
+
+import java.lang.reflect.Method;
+
+/**
+ * Releases Rust resources once the associated object becomes unreachable.
+ * <p>
+ * Uses {@code java.lang.ref.Cleaner} if available, otherwise falls back to {@code sun.misc.Cleaner}.
+ * Errors thrown while releasing, e.g. Rust panics, are reported to the uncaught exception handler of the cleaner thread.
+ */
+final class NativeCleaner implements Runnable {
+    private static final Object CLEANER;
+    private static final Method REGISTER;
+
+    static {
+        Object cleaner;
+        Method register;
+
+        try {
+            Class<?> clazz = Class.forName("java.lang.ref.Cleaner");
+
+            cleaner = clazz.getMethod("create").invoke(null);
+            register = clazz.getMethod("register", Object.class, Runnable.class);
+        } catch (ReflectiveOperationException e) {
+            try {
+                Class<?> clazz = Class.forName("sun.misc.Cleaner");
+
+                cleaner = null;
+                register = clazz.getMethod("create", Object.class, Runnable.class);
+            } catch (ReflectiveOperationException ex) {
+                throw new ExceptionInInitializerError(ex);
+            }
+        }
+
+        CLEANER = cleaner;
+        REGISTER = register;
+    }
+
+    private final long handle;
+
+    private NativeCleaner(long handle) {
+        this.handle = handle;
+    }
+
+    private static native void release(long handle);
+
+    static void register(Object obj, long handle) throws ReflectiveOperationException {
+        REGISTER.invoke(CLEANER, obj, new NativeCleaner(handle));
+    }
+
+    @Override
+    public void run() {
+        try {
+            release(handle);
+        } catch (Throwable e) {
+            // sun.misc.Cleaner exits the VM if a cleaning action throws, report it as uncaught instead
+            Thread thread = Thread.currentThread();
+
+            thread.getUncaughtExceptionHandler().uncaughtException(thread, e);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.function.Consumer;
+
+final class NativeConsumer extends NativeCallback implements Consumer<Object> {
+    private NativeConsumer(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public void accept(Object t) {
+        invoke(t, null);
+    }
+}
@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.function.Function;
+
+final class NativeFunction extends NativeCallback implements Function<Object, Object> {
+    private NativeFunction(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public Object apply(Object t) {
+        return invoke(t, null);
+    }
+}
@@ -0,0 +1,49 @@
+package com.github.kr328.typedjni;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * An input stream implemented by a Rust reader.
+ * <p>
+ * The reader is released by {@link NativeCleaner} once the stream becomes unreachable.
+ */
+final class NativeInputStream extends InputStream {
+    // must match the stream operations in Rust
+    private static final int OP_READ = 0;
+    private static final int OP_CLOSE = 3;
+
+    private final long handle;
+
+    private NativeInputStream(long handle) {
+        this.handle = handle;
+    }
+
+    // an instance method keeps this stream reachable while the reader is running
+    private native int invoke(long handle, int op, byte[] b, int off, int len) throws IOException;
+
+    @Override
+    public int read() throws IOException {
+        byte[] b = new byte[1];
+
+        return read(b, 0, 1) < 0 ? -1 : b[0] & 0xff;
+    }
+
+    @Override
+    public int read(byte[] b, int off, int len) throws IOException {
+        if (off < 0 || len < 0 || len > b.length - off) {
+            throw new IndexOutOfBoundsException();
+        }
+
+        if (len == 0) {
+            return 0;
+        }
+
+        return invoke(handle, OP_READ, b, off, len);
+    }
+
+    @Override
+    public void close() throws IOException {
+        invoke(handle, OP_CLOSE, null, 0, 0);
+    }
+}
@@ -0,0 +1,76 @@
+package com.github.kr328.typedjni;
+
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Method;
+import java.util.concurrent.ConcurrentHashMap;
+
+final class NativeInvocationHandler extends NativeCallback implements InvocationHandler {
+    private static final ConcurrentHashMap<Method, String> DESCRIPTORS = new ConcurrentHashMap<>();
+
+    private NativeInvocationHandler(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public Object invoke(Object proxy, Method method, Object[] args) {
+        if (method.getDeclaringClass() == Object.class) {
+            switch (method.getName()) {
+                case "equals":
+                    return proxy == args[0];
+                case "hashCode":
+                    return System.identityHashCode(proxy);
+                case "toString":
+                    return proxy.getClass().getName() + "@" + Integer.toHexString(System.identityHashCode(proxy));
+            }
+        }
+
+        String descriptor = DESCRIPTORS.get(method);
+        if (descriptor == null) {
+            descriptor = describe(method);
+
+            DESCRIPTORS.put(method, descriptor);
+        }
+
+        return invoke(descriptor, args);
+    }
+
+    private static String describe(Method method) {
+        StringBuilder builder = new StringBuilder(method.getName()).append('(');
+
+        for (Class<?> type : method.getParameterTypes()) {
+            describe(builder, type);
+        }
+
+        describe(builder.append(')'), method.getReturnType());
+
+        return builder.toString();
+    }
+
+    private static void describe(StringBuilder builder, Class<?> type) {
+        if (type.isArray()) {
+            builder.append(type.getName().replace('.', '/'));
+        } else if (type.isPrimitive()) {
+            if (type == void.class) {
+                builder.append('V');
+            } else if (type == boolean.class) {
+                builder.append('Z');
+            } else if (type == byte.class) {
+                builder.append('B');
+            } else if (type == char.class) {
+                builder.append('C');
+            } else if (type == short.class) {
+                builder.append('S');
+            } else if (type == int.class) {
+                builder.append('I');
+            } else if (type == long.class) {
+                builder.append('J');
+            } else if (type == float.class) {
+                builder.append('F');
+            } else {
+                builder.append('D');
+            }
+        } else {
+            builder.append('L').append(type.getName().replace('.', '/')).append(';');
+        }
+    }
+}
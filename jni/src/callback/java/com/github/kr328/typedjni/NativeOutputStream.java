@@ -0,0 +1,53 @@
+package com.github.kr328.typedjni;
+
+import java.io.IOException;
+import java.io.OutputStream;
+
+/**
+ * An output stream implemented by a Rust writer.
+ * <p>
+ * The writer is released by {@link NativeCleaner} once the stream becomes unreachable.
+ */
+final class NativeOutputStream extends OutputStream {
+    // must match the stream operations in Rust
+    private static final int OP_WRITE = 1;
+    private static final int OP_FLUSH = 2;
+    private static final int OP_CLOSE = 3;
+
+    private final long handle;
+
+    private NativeOutputStream(long handle) {
+        this.handle = handle;
+    }
+
+    // an instance method keeps this stream reachable while the writer is running
+    private native int invoke(long handle, int op, byte[] b, int off, int len) throws IOException;
+
+    @Override
+    public void write(int b) throws IOException {
+        write(new byte[]{(byte) b}, 0, 1);
+    }
+
+    @Override
+    public void write(byte[] b, int off, int len) throws IOException {
+        if (off < 0 || len < 0 || len > b.length - off) {
+            throw new IndexOutOfBoundsException();
+        }
+
+        if (len == 0) {
+            return;
+        }
+
+        invoke(handle, OP_WRITE, b, off, len);
+    }
+
+    @Override
+    public void flush() throws IOException {
+        invoke(handle, OP_FLUSH, null, 0, 0);
+    }
+
+    @Override
+    public void close() throws IOException {
+        invoke(handle, OP_CLOSE, null, 0, 0);
+    }
+}
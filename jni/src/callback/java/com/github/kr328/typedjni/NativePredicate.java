@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.function.Predicate;
+
+final class NativePredicate extends NativeCallback implements Predicate<Object> {
+    private NativePredicate(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public boolean test(Object t) {
+        return (Boolean) invoke(t, null);
+    }
+}
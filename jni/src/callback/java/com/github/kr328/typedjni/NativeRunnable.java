@@ -0,0 +1,12 @@
+package com.github.kr328.typedjni;
+
+final class NativeRunnable extends NativeCallback implements Runnable {
+    private NativeRunnable(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public void run() {
+        invoke(null, null);
+    }
+}
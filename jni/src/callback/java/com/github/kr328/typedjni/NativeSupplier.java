@@ -0,0 +1,14 @@
+package com.github.kr328.typedjni;
+
+import java.util.function.Supplier;
+
+final class NativeSupplier extends NativeCallback implements Supplier<Object> {
+    private NativeSupplier(long handle) {
+        super(handle);
+    }
+
+    @Override
+    public Object get() {
+        return invoke(null, null);
+    }
+}
@@ -0,0 +1,424 @@
+//! Embedded Java helper classes whose methods call into Rust.
+//!
+//! Classes are compiled from `java` with `javac --release 8 -d classes java/com/github/kr328/typedjni/*.java`,
+//! and defined in the system class loader on demand, or in the class loader given to
+//! [`TypedHelperClassesExt::typed_init_helper_classes`].
+
+use alloc::{boxed::Box, format, vec::Vec};
+use core::{ffi::CStr, panic::AssertUnwindSafe};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use typed_jni_core::{Arg, GlobalRef, JNIEnv, LocalRef, NativeFunction, StrongRef, TrampolineRef};
+
+use crate::{
+    LocalObject, Object, TypedRef,
+    builtin::{JavaClassLoader, JavaThrowable},
+    resolver, throwable,
+};
+
+/// A Rust closure that can be called from Java.
+pub(crate) type Callback = dyn for<'env> Fn(
+        &'env JNIEnv<'static>,
+        Option<TrampolineRef<'env>>,
+        Option<TrampolineRef<'env>>,
+    ) -> Result<Option<LocalRef<'env>>, LocalRef<'env>>
+    + Send
+    + Sync;
+
+/// A Rust closure backing a `java.io` stream.
+///
+/// Called with a `STREAM_*` operation and the `byte[]`, `int` offset and `int` length arguments,
+/// which are `null` and `0` for operations without them, returns the `int` result of the operation.
+pub(crate) type StreamCallback = dyn for<'env> Fn(&'env JNIEnv<'static>, i32, Option<TrampolineRef<'env>>, i32, i32) -> Result<i32, LocalRef<'env>>
+    + Send
+    + Sync;
+
+/// A Rust closure that releases resources when a Java object becomes unreachable.
+pub(crate) type Release = dyn FnOnce() + Send;
+
+/// A `NativeCallback` subclass implementing a Java interface.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum CallbackKind {
+    /// `java.lang.reflect.InvocationHandler`, passes the method name with its descriptor (e.g. `run()V`) and arguments array.
+    InvocationHandler,
+    /// `java.lang.Runnable`
+    Runnable,
+    /// `java.util.concurrent.Callable`
+    Callable,
+    /// `java.util.function.Supplier`
+    Supplier,
+    /// `java.util.function.Consumer`
+    Consumer,
+    /// `java.util.function.BiConsumer`
+    BiConsumer,
+    /// `java.util.function.Function`
+    Function,
+    /// `java.util.function.BiFunction`
+    BiFunction,
+    /// `java.util.function.Predicate`, the callback must return a `java.lang.Boolean`.
+    Predicate,
+}
+
+/// A `java.io` stream subclass backed by a [`StreamCallback`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum StreamKind {
+    /// `java.io.InputStream`, passes `STREAM_READ` and `STREAM_CLOSE`.
+    Input,
+    /// `java.io.OutputStream`, passes `STREAM_WRITE`, `STREAM_FLUSH` and `STREAM_CLOSE`.
+    Output,
+}
+
+// must match the `OP_*` constants of `NativeInputStream` and `NativeOutputStream`
+/// `read(byte[], int, int)`, returns the number of bytes read or `-1` at the end of the stream.
+pub(crate) const STREAM_READ: i32 = 0;
+/// `write(byte[], int, int)`
+pub(crate) const STREAM_WRITE: i32 = 1;
+/// `flush()`
+pub(crate) const STREAM_FLUSH: i32 = 2;
+/// `close()`
+pub(crate) const STREAM_CLOSE: i32 = 3;
+
+macro_rules! class {
+    ($name:literal) => {
+        (
+            concat_cstr!("com/github/kr328/typedjni/", $name),
+            include_bytes!(concat!("classes/com/github/kr328/typedjni/", $name, ".class")),
+        )
+    };
+}
+
+macro_rules! concat_cstr {
+    ($($s:literal),*) => {
+        match CStr::from_bytes_with_nul(concat!($($s,)* "\0").as_bytes()) {
+            Ok(s) => s,
+            Err(_) => panic!("BROKEN: invalid class name"),
+        }
+    };
+}
+
+const NATIVE_CALLBACK: (&CStr, &[u8]) = class!("NativeCallback");
+const NATIVE_CLEANER: (&CStr, &[u8]) = class!("NativeCleaner");
+
+// must be in the same order as `CallbackKind`
+const NATIVE_CALLBACK_KINDS: [(&CStr, &[u8]); 9] = [
+    class!("NativeInvocationHandler"),
+    class!("NativeRunnable"),
+    class!("NativeCallable"),
+    class!("NativeSupplier"),
+    class!("NativeConsumer"),
+    class!("NativeBiConsumer"),
+    class!("NativeFunction"),
+    class!("NativeBiFunction"),
+    class!("NativePredicate"),
+];
+
+// must be in the same order as `StreamKind`
+const NATIVE_STREAM_KINDS: [(&CStr, &[u8]); 2] = [class!("NativeInputStream"), class!("NativeOutputStream")];
+
+struct Classes {
+    callbacks: Vec<GlobalRef<'static>>,
+    streams: Vec<GlobalRef<'static>>,
+    cleaner: GlobalRef<'static>,
+}
+
+static CLASSES: OnceLock<Classes> = OnceLock::new();
+static DEFINE_LOCK: Mutex<()> = Mutex::new(());
+
+fn classes<'env>(env: &'env JNIEnv) -> Result<&'static Classes, LocalObject<'env, JavaThrowable>> {
+    if let Some(classes) = CLASSES.get() {
+        return Ok(classes);
+    }
+
+    let _guard = DEFINE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(classes) = CLASSES.get() {
+        return Ok(classes);
+    }
+
+    unsafe {
+        let (c_class_loader, m_get_system_class_loader) = resolver::resolve_class_and_method::<true>(
+            env,
+            c"java/lang/ClassLoader",
+            c"getSystemClassLoader",
+            c"()Ljava/lang/ClassLoader;",
+        )?;
+        let loader = env
+            .call_object_method(&c_class_loader, m_get_system_class_loader, [])
+            .map_err(|err| LocalObject::from_ref(err))?
+            .expect("BROKEN: ClassLoader.getSystemClassLoader returns null");
+
+        init_classes(env, &loader)
+    }
+}
+
+/// Loads or defines the helper classes in `loader`, must be called with `DEFINE_LOCK` held.
+fn init_classes<'env, R: StrongRef>(env: &'env JNIEnv, loader: &R) -> Result<&'static Classes, LocalObject<'env, JavaThrowable>> {
+    unsafe {
+        // global references must outlive any env, the Java VM is never destroyed before the process exits.
+        let static_env: &JNIEnv<'static> = JNIEnv::from_raw(env.as_raw_ptr());
+
+        let (_, m_for_name) = resolver::resolve_class_and_method::<true>(
+            env,
+            c"java/lang/Class",
+            c"forName",
+            c"(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+        )?;
+
+        let define = |(name, data): (&CStr, &[u8])| -> Result<GlobalRef<'static>, LocalObject<'env, JavaThrowable>> {
+            // use the class as is if it is already loadable, e.g. compiled into an Android application
+            let class_name = env.new_string(name.to_str().expect("BROKEN: invalid class name").replace('/', "."));
+            let loaded = env.call_object_method(
+                loader,
+                m_for_name,
+                [Arg::Object(Some(&class_name)), Arg::Boolean(false), Arg::Object(Some(loader))],
+            );
+
+            let cls = match loaded {
+                Ok(cls) => cls.expect("BROKEN: Class.forName returns null"),
+                Err(_) => env
+                    .define_class(name, Some(loader), data)
+                    .map_err(|err| LocalObject::from_ref(err))?,
+            };
+
+            Ok(static_env
+                .new_global_ref(&cls)
+                .expect("BROKEN: create new global reference failed"))
+        };
+
+        // the base class must be defined before its subclasses.
+        let callback = define(NATIVE_CALLBACK)?;
+        let callbacks = NATIVE_CALLBACK_KINDS.into_iter().map(define).collect::<Result<Vec<_>, _>>()?;
+        let streams = NATIVE_STREAM_KINDS.into_iter().map(define).collect::<Result<Vec<_>, _>>()?;
+        let cleaner = define(NATIVE_CLEANER)?;
+
+        env.register_natives(
+            &callback,
+            [NativeFunction {
+                name: c"invoke",
+                signature: c"(JLjava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+                fn_ptr: invoke as *const (),
+            }],
+        )
+        .map_err(|err| LocalObject::from_ref(err))?;
+        for stream in &streams {
+            env.register_natives(
+                stream,
+                [NativeFunction {
+                    name: c"invoke",
+                    signature: c"(JI[BII)I",
+                    fn_ptr: invoke_stream as *const (),
+                }],
+            )
+            .map_err(|err| LocalObject::from_ref(err))?;
+        }
+        env.register_natives(
+            &cleaner,
+            [NativeFunction {
+                name: c"release",
+                signature: c"(J)V",
+                fn_ptr: release as *const (),
+            }],
+        )
+        .map_err(|err| LocalObject::from_ref(err))?;
+
+        Ok(CLASSES.get_or_init(|| Classes {
+            callbacks,
+            streams,
+            cleaner,
+        }))
+    }
+}
+
+/// Sets up the Java helper classes backing closures, proxies, futures and streams.
+///
+/// By default, the helper classes are defined with `DefineClass` in the system class loader on first use.
+/// `DefineClass` is not supported by Android, where the Java sources under `src/callback/java` of this crate
+/// must be compiled into the application, and this must be called with its class loader before first use.
+pub trait TypedHelperClassesExt {
+    /// Sets up the helper classes in `class_loader`.
+    ///
+    /// Classes already loadable from `class_loader` are used as is, others are defined in it.
+    /// Throws `IllegalStateException` if the helper classes are already set up.
+    fn typed_init_helper_classes<R: StrongRef>(
+        &self,
+        class_loader: &Object<R, JavaClassLoader>,
+    ) -> Result<(), LocalObject<'_, JavaThrowable>>;
+}
+
+impl<'vm> TypedHelperClassesExt for JNIEnv<'vm> {
+    fn typed_init_helper_classes<R: StrongRef>(
+        &self,
+        class_loader: &Object<R, JavaClassLoader>,
+    ) -> Result<(), LocalObject<'_, JavaThrowable>> {
+        let _guard = DEFINE_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if CLASSES.get().is_some() {
+            return Err(throwable::helper::new_named_exception(
+                self,
+                c"java/lang/IllegalStateException",
+                "helper classes are already initialized",
+            ));
+        }
+
+        init_classes(self, &**class_loader).map(|_| ())
+    }
+}
+
+/// Registers `release` to be run once `obj` becomes unreachable.
+///
+/// If the registration fails, `release` is run immediately.
+pub(crate) fn register_cleaner<'env, R: StrongRef>(
+    env: &'env JNIEnv,
+    obj: &R,
+    release: Box<Release>,
+) -> Result<(), LocalObject<'env, JavaThrowable>> {
+    let handle = Box::into_raw(Box::new(release));
+
+    let ret = classes(env).and_then(|classes| unsafe {
+        let method = resolver::resolve_method::<true, _>(env, &classes.cleaner, c"register", c"(Ljava/lang/Object;J)V")?;
+
+        env.call_void_method(&classes.cleaner, method, [Arg::Object(Some(obj)), Arg::Long(handle as i64)])
+            .map_err(|err| LocalObject::from_ref(err))
+    });
+
+    if ret.is_err() {
+        unsafe { Box::from_raw(handle)() }
+    }
+
+    ret
+}
+
+/// Creates a `NativeCallback` object of `kind` backed by `callback`.
+///
+/// Arguments absent from the interface method are passed to `callback` as `None`,
+/// and the return value is ignored if the interface method returns `void`.
+pub(crate) fn new_native_callback<'env>(
+    env: &'env JNIEnv,
+    kind: CallbackKind,
+    callback: Box<Callback>,
+) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>> {
+    let handle = Box::into_raw(Box::new(callback)) as usize;
+    let release = move || unsafe { drop(Box::from_raw(handle as *mut Box<Callback>)) };
+
+    let obj = classes(env).and_then(|classes| unsafe {
+        let class = &classes.callbacks[kind as usize];
+        let method = resolver::resolve_method::<false, _>(env, class, c"<init>", c"(J)V")?;
+
+        env.new_object(class, method, [Arg::Long(handle as i64)])
+            .map_err(|err| LocalObject::from_ref(err))
+    });
+
+    match obj {
+        Ok(obj) => register_cleaner(env, &obj, Box::new(release)).map(|_| obj),
+        Err(err) => {
+            release();
+
+            Err(err)
+        }
+    }
+}
+
+/// Creates a `NativeInputStream` or `NativeOutputStream` object backed by `callback`.
+pub(crate) fn new_native_stream<'env>(
+    env: &'env JNIEnv,
+    kind: StreamKind,
+    callback: Box<StreamCallback>,
+) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>> {
+    let handle = Box::into_raw(Box::new(callback)) as usize;
+    let release = move || unsafe { drop(Box::from_raw(handle as *mut Box<StreamCallback>)) };
+
+    let obj = classes(env).and_then(|classes| unsafe {
+        let class = &classes.streams[kind as usize];
+        let method = resolver::resolve_method::<false, _>(env, class, c"<init>", c"(J)V")?;
+
+        env.new_object(class, method, [Arg::Long(handle as i64)])
+            .map_err(|err| LocalObject::from_ref(err))
+    });
+
+    match obj {
+        Ok(obj) => register_cleaner(env, &obj, Box::new(release)).map(|_| obj),
+        Err(err) => {
+            release();
+
+            Err(err)
+        }
+    }
+}
+
+/// Creates a `java.lang.Error` describing a Rust panic.
+pub(crate) fn new_panic_error<'env>(
+    env: &'env JNIEnv,
+    panic: Box<dyn core::any::Any + Send>,
+) -> LocalObject<'env, JavaThrowable> {
+    let msg = if let Some(msg) = panic.downcast_ref::<&str>() {
+        msg
+    } else if let Some(msg) = panic.downcast_ref::<alloc::string::String>() {
+        msg.as_str()
+    } else {
+        "unknown panic"
+    };
+
+    throwable::helper::new_named_exception(env, c"java/lang/Error", &format!("rust panic: {msg}"))
+}
+
+fn throw_panic(env: &JNIEnv, panic: Box<dyn core::any::Any + Send>) {
+    unsafe { env.throw(&*new_panic_error(env, panic)) }
+}
+
+extern "system" fn invoke<'env>(
+    env: &'env JNIEnv<'static>,
+    _this: TrampolineRef<'env>,
+    handle: i64,
+    a: Option<TrampolineRef<'env>>,
+    b: Option<TrampolineRef<'env>>,
+) -> Option<TrampolineRef<'env>> {
+    let callback = unsafe { &*(handle as usize as *const Box<Callback>) };
+
+    match std::panic::catch_unwind(AssertUnwindSafe(|| callback(env, a, b))) {
+        Ok(Ok(ret)) => ret.map(|r| r.into_trampoline()),
+        Ok(Err(ex)) => {
+            unsafe { env.throw(&ex) };
+
+            None
+        }
+        Err(panic) => {
+            throw_panic(env, panic);
+
+            None
+        }
+    }
+}
+
+extern "system" fn invoke_stream<'env>(
+    env: &'env JNIEnv<'static>,
+    _this: TrampolineRef<'env>,
+    handle: i64,
+    op: i32,
+    array: Option<TrampolineRef<'env>>,
+    offset: i32,
+    len: i32,
+) -> i32 {
+    let callback = unsafe { &*(handle as usize as *const Box<StreamCallback>) };
+
+    match std::panic::catch_unwind(AssertUnwindSafe(|| callback(env, op, array, offset, len))) {
+        Ok(Ok(ret)) => ret,
+        Ok(Err(ex)) => {
+            unsafe { env.throw(&ex) };
+
+            0
+        }
+        Err(panic) => {
+            throw_panic(env, panic);
+
+            0
+        }
+    }
+}
+
+extern "system" fn release<'env>(env: &'env JNIEnv<'static>, _class: TrampolineRef<'env>, handle: i64) {
+    let release = unsafe { Box::from_raw(handle as usize as *mut Box<Release>) };
+
+    if let Err(panic) = std::panic::catch_unwind(AssertUnwindSafe(release)) {
+        throw_panic(env, panic);
+    }
+}
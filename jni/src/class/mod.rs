@@ -26,7 +26,7 @@ pub trait TypedClassExt {
 
 impl<'vm> TypedClassExt for JNIEnv<'vm> {
     fn typed_find_class<T: ObjectType>(&self) -> Result<LocalClass<'_, T>, LocalObject<'_, JavaThrowable>> {
-        let class_name = resolver::helper::build_class_name(self, T::SIGNATURE, false)?;
+        let class_name = resolver::helper::class_name_of::<T>(self)?;
 
         unsafe {
             self.find_class(&*class_name)
@@ -0,0 +1,850 @@
+use alloc::{
+    ffi::CString,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use typed_jni_core::{Arg, JNIEnv, LocalRef, MethodID, StrongRef};
+
+use crate::{
+    Class, LocalObject, MethodSignature, ObjectType, OwnedSignature, Signature, TypedRef,
+    builtin::{JavaObject, JavaThrowable},
+    resolver,
+    resolver::{
+        helper::MemberKind,
+        reflect::{MODIFIER_BRIDGE, MODIFIER_STATIC, MODIFIER_SYNTHETIC, Reflection},
+    },
+    throwable,
+};
+
+/// An owned value of any Java type, for calls whose signatures are only known at runtime.
+pub enum JValue<'env> {
+    Void,
+    Boolean(bool),
+    Byte(i8),
+    Char(u16),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    Object(Option<LocalObject<'env, JavaObject>>),
+}
+
+macro_rules! impl_from_primitive {
+    ($t:ty, $variant:ident) => {
+        impl From<$t> for JValue<'_> {
+            fn from(value: $t) -> Self {
+                JValue::$variant(value)
+            }
+        }
+    };
+}
+
+impl_from_primitive!(bool, Boolean);
+impl_from_primitive!(i8, Byte);
+impl_from_primitive!(u16, Char);
+impl_from_primitive!(i16, Short);
+impl_from_primitive!(i32, Int);
+impl_from_primitive!(i64, Long);
+impl_from_primitive!(f32, Float);
+impl_from_primitive!(f64, Double);
+
+impl From<()> for JValue<'_> {
+    fn from(_: ()) -> Self {
+        JValue::Void
+    }
+}
+
+impl<'env, T: ObjectType> From<LocalObject<'env, T>> for JValue<'env> {
+    fn from(value: LocalObject<'env, T>) -> Self {
+        JValue::Object(Some(value.into_object()))
+    }
+}
+
+impl<'env, T: ObjectType> From<Option<LocalObject<'env, T>>> for JValue<'env> {
+    fn from(value: Option<LocalObject<'env, T>>) -> Self {
+        JValue::Object(value.map(|v| v.into_object()))
+    }
+}
+
+impl<'env> JValue<'env> {
+    /// Returns the signature of a primitive value, `None` for objects.
+    pub fn primitive_signature(&self) -> Option<Signature> {
+        match self {
+            JValue::Void => Some(Signature::Void),
+            JValue::Boolean(_) => Some(Signature::Boolean),
+            JValue::Byte(_) => Some(Signature::Byte),
+            JValue::Char(_) => Some(Signature::Char),
+            JValue::Short(_) => Some(Signature::Short),
+            JValue::Int(_) => Some(Signature::Int),
+            JValue::Long(_) => Some(Signature::Long),
+            JValue::Float(_) => Some(Signature::Float),
+            JValue::Double(_) => Some(Signature::Double),
+            JValue::Object(_) => None,
+        }
+    }
+
+    fn as_arg(&self) -> Arg<'_> {
+        match self {
+            JValue::Void => panic!("BROKEN: void is not an argument"),
+            JValue::Boolean(v) => Arg::Boolean(*v),
+            JValue::Byte(v) => Arg::Byte(*v),
+            JValue::Char(v) => Arg::Char(*v),
+            JValue::Short(v) => Arg::Short(*v),
+            JValue::Int(v) => Arg::Int(*v),
+            JValue::Long(v) => Arg::Long(*v),
+            JValue::Float(v) => Arg::Float(*v),
+            JValue::Double(v) => Arg::Double(*v),
+            JValue::Object(v) => Arg::Object(v.as_ref().map(|v| &**v as &dyn StrongRef)),
+        }
+    }
+
+    /// Converts a primitive value by identity or widening primitive conversion.
+    fn widen(&self, to: Signature) -> Option<JValue<'static>> {
+        if !widens(self.primitive_signature()?, to) {
+            return None;
+        }
+
+        let integral = match *self {
+            JValue::Byte(v) => v as i64,
+            JValue::Char(v) => v as i64,
+            JValue::Short(v) => v as i64,
+            JValue::Int(v) => v as i64,
+            JValue::Long(v) => v,
+            _ => 0,
+        };
+
+        Some(match (to, self) {
+            (Signature::Boolean, JValue::Boolean(v)) => JValue::Boolean(*v),
+            (Signature::Char, JValue::Char(v)) => JValue::Char(*v),
+            (Signature::Byte, _) => JValue::Byte(integral as i8),
+            (Signature::Short, _) => JValue::Short(integral as i16),
+            (Signature::Int, _) => JValue::Int(integral as i32),
+            (Signature::Long, _) => JValue::Long(integral),
+            (Signature::Float, JValue::Float(v)) => JValue::Float(*v),
+            (Signature::Float, _) => JValue::Float(integral as f32),
+            (Signature::Double, JValue::Double(v)) => JValue::Double(*v),
+            (Signature::Double, JValue::Float(v)) => JValue::Double(*v as f64),
+            (Signature::Double, _) => JValue::Double(integral as f64),
+            _ => unreachable!("BROKEN: invalid widening"),
+        })
+    }
+}
+
+/// Primitive types in the order of preference for unboxing.
+const PRIMITIVES: [Signature; 8] = [
+    Signature::Boolean,
+    Signature::Byte,
+    Signature::Char,
+    Signature::Short,
+    Signature::Int,
+    Signature::Long,
+    Signature::Float,
+    Signature::Double,
+];
+
+/// Returns whether `from` converts to `to` by identity or widening primitive conversion.
+fn widens(from: Signature, to: Signature) -> bool {
+    use Signature::*;
+
+    matches!(
+        (from, to),
+        (Byte, Short | Int | Long | Float | Double)
+            | (Short | Char, Int | Long | Float | Double)
+            | (Int, Long | Float | Double)
+            | (Long, Float | Double)
+            | (Float, Double)
+    ) || (from == to && from != Void)
+}
+
+fn primitive_of(signature: &OwnedSignature) -> Option<Signature> {
+    PRIMITIVES.into_iter().find(|p| signature == p)
+}
+
+/// Returns the box class and `valueOf` method of a primitive type.
+fn boxing<'env>(
+    env: &'env JNIEnv,
+    primitive: Signature,
+) -> Result<(LocalRef<'env>, MethodID<true>), LocalObject<'env, JavaThrowable>> {
+    let (cls, sig) = match primitive {
+        Signature::Boolean => (c"java/lang/Boolean", c"(Z)Ljava/lang/Boolean;"),
+        Signature::Byte => (c"java/lang/Byte", c"(B)Ljava/lang/Byte;"),
+        Signature::Char => (c"java/lang/Character", c"(C)Ljava/lang/Character;"),
+        Signature::Short => (c"java/lang/Short", c"(S)Ljava/lang/Short;"),
+        Signature::Int => (c"java/lang/Integer", c"(I)Ljava/lang/Integer;"),
+        Signature::Long => (c"java/lang/Long", c"(J)Ljava/lang/Long;"),
+        Signature::Float => (c"java/lang/Float", c"(F)Ljava/lang/Float;"),
+        Signature::Double => (c"java/lang/Double", c"(D)Ljava/lang/Double;"),
+        _ => unreachable!("BROKEN: boxing non-primitive type"),
+    };
+
+    resolver::resolve_class_and_method::<true>(env, cls, c"valueOf", sig)
+}
+
+/// Returns the box class and `xxxValue` method of a primitive type.
+fn unboxing<'env>(
+    env: &'env JNIEnv,
+    primitive: Signature,
+) -> Result<(LocalRef<'env>, MethodID<false>), LocalObject<'env, JavaThrowable>> {
+    let (cls, name, sig) = match primitive {
+        Signature::Boolean => (c"java/lang/Boolean", c"booleanValue", c"()Z"),
+        Signature::Byte => (c"java/lang/Byte", c"byteValue", c"()B"),
+        Signature::Char => (c"java/lang/Character", c"charValue", c"()C"),
+        Signature::Short => (c"java/lang/Short", c"shortValue", c"()S"),
+        Signature::Int => (c"java/lang/Integer", c"intValue", c"()I"),
+        Signature::Long => (c"java/lang/Long", c"longValue", c"()J"),
+        Signature::Float => (c"java/lang/Float", c"floatValue", c"()F"),
+        Signature::Double => (c"java/lang/Double", c"doubleValue", c"()D"),
+        _ => unreachable!("BROKEN: unboxing non-primitive type"),
+    };
+
+    resolver::resolve_class_and_method::<false>(env, cls, name, sig)
+}
+
+/// Returns the primitive type of a boxed `obj` which widens to `to`.
+fn unboxable<'env, R: StrongRef>(
+    env: &'env JNIEnv,
+    obj: &R,
+    to: Signature,
+) -> Result<Option<Signature>, LocalObject<'env, JavaThrowable>> {
+    for primitive in PRIMITIVES.into_iter().filter(|p| widens(*p, to)) {
+        let (cls, _) = unboxing(env, primitive)?;
+
+        if unsafe { env.is_instance_of(obj, &cls) } {
+            return Ok(Some(primitive));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Result of converting an argument to a parameter.
+enum Coerced<'env> {
+    AsIs,
+    Converted(JValue<'env>),
+    Inconvertible,
+}
+
+/// Finds the classes of object parameters `params` with the class loader of `cls`, the loader is looked up once.
+///
+/// Classes are only found for parameters that need to be checked against their arguments, `None` is returned for
+/// primitive and `java.lang.Object` parameters, and parameters whose arguments are `null`.
+fn param_classes<'env, C: StrongRef>(
+    env: &'env JNIEnv,
+    cls: &C,
+    params: &[OwnedSignature],
+    args: &[JValue<'_>],
+) -> Result<Vec<Option<LocalRef<'env>>>, LocalObject<'env, JavaThrowable>> {
+    let mut loader = None;
+    let mut ret = Vec::with_capacity(params.len());
+
+    for (param, arg) in params.iter().zip(args) {
+        if primitive_of(param).is_some()
+            || *param == Signature::Object("java/lang/Object")
+            || matches!(arg, JValue::Void | JValue::Object(None))
+        {
+            ret.push(None);
+            continue;
+        }
+
+        let name = match param {
+            OwnedSignature::Object(name) => name.replace('/', "."),
+            param => param.to_string().replace('/', "."),
+        };
+
+        unsafe {
+            let loader = match &loader {
+                Some(loader) => loader,
+                None => {
+                    let (_, m_get_class_loader) = resolver::resolve_class_and_method::<false>(
+                        env,
+                        c"java/lang/Class",
+                        c"getClassLoader",
+                        c"()Ljava/lang/ClassLoader;",
+                    )?;
+
+                    loader.insert(
+                        env.call_object_method(cls, m_get_class_loader, [])
+                            .map_err(|err| LocalObject::from_ref(err))?,
+                    )
+                }
+            };
+
+            let (c_class, m_for_name) = resolver::resolve_class_and_method::<true>(
+                env,
+                c"java/lang/Class",
+                c"forName",
+                c"(Ljava/lang/String;ZLjava/lang/ClassLoader;)Ljava/lang/Class;",
+            )?;
+            let param_cls = env
+                .call_object_method(
+                    &c_class,
+                    m_for_name,
+                    [
+                        Arg::Object(Some(&env.new_string(name))),
+                        Arg::Boolean(false),
+                        Arg::Object(loader.as_ref().map(|v| v as _)),
+                    ],
+                )
+                .map_err(|err| LocalObject::from_ref(err))?
+                .expect("BROKEN: Class.forName returns null");
+
+            ret.push(Some(param_cls));
+        }
+    }
+
+    Ok(ret)
+}
+
+/// Returns whether `obj` is an instance of object parameter class `param_cls`, `None` for `java.lang.Object`.
+fn is_instance_of_param<R: StrongRef>(env: &JNIEnv, obj: &R, param_cls: Option<&LocalRef<'_>>) -> bool {
+    match param_cls {
+        Some(param_cls) => unsafe { env.is_instance_of(obj, param_cls) },
+        None => true,
+    }
+}
+
+/// Converts `value` to `param` by widening, boxing and unboxing conversions, objects are checked against the class
+/// `param_cls` of `param` found by [`param_classes`].
+fn coerce<'env>(
+    env: &'env JNIEnv,
+    value: &JValue<'_>,
+    param: &OwnedSignature,
+    param_cls: Option<&LocalRef<'_>>,
+) -> Result<Coerced<'env>, LocalObject<'env, JavaThrowable>> {
+    unsafe {
+        match (primitive_of(param), value) {
+            (_, JValue::Void) => Ok(Coerced::Inconvertible),
+            (Some(to), JValue::Object(Some(obj))) => {
+                let Some(primitive) = unboxable(env, &**obj, to)? else {
+                    return Ok(Coerced::Inconvertible);
+                };
+
+                let (_, method) = unboxing(env, primitive)?;
+                let unboxed = invoke(env, &**obj, method, &OwnedSignature::from(primitive), &[])?;
+
+                Ok(unboxed.widen(to).map_or(Coerced::Inconvertible, Coerced::Converted))
+            }
+            (Some(to), value) => Ok(value.widen(to).map_or(Coerced::Inconvertible, Coerced::Converted)),
+            (None, JValue::Object(None)) => Ok(Coerced::AsIs),
+            (None, JValue::Object(Some(obj))) => match is_instance_of_param(env, &**obj, param_cls) {
+                true => Ok(Coerced::AsIs),
+                false => Ok(Coerced::Inconvertible),
+            },
+            (None, value) => {
+                let primitive = value.primitive_signature().expect("BROKEN: value is not primitive");
+                let (c_box, method) = boxing(env, primitive)?;
+
+                let boxed = env
+                    .call_object_method(&c_box, method, [value.as_arg()])
+                    .map_err(|err| LocalObject::from_ref(err))?
+                    .expect("BROKEN: boxing returns null");
+
+                match is_instance_of_param(env, &boxed, param_cls) {
+                    true => Ok(Coerced::Converted(JValue::Object(Some(LocalObject::from_ref(boxed))))),
+                    false => Ok(Coerced::Inconvertible),
+                }
+            }
+        }
+    }
+}
+
+/// Converts `args` to `params` of `sig` of a method in `cls`, `None` for arguments passed as is.
+fn coerce_all<'env, C: StrongRef>(
+    env: &'env JNIEnv,
+    cls: &C,
+    sig: &MethodSignature,
+    args: &[JValue<'_>],
+) -> Result<Vec<Option<JValue<'env>>>, LocalObject<'env, JavaThrowable>> {
+    if sig.params.len() != args.len() {
+        return Err(illegal_argument(
+            env,
+            &format!("{sig}: expected {} arguments, got {}", sig.params.len(), args.len()),
+        ));
+    }
+
+    let param_classes = param_classes(env, cls, &sig.params, args)?;
+
+    let mut ret = Vec::with_capacity(args.len());
+    for (index, ((param, param_cls), arg)) in sig.params.iter().zip(&param_classes).zip(args).enumerate() {
+        match coerce(env, arg, param, param_cls.as_ref())? {
+            Coerced::AsIs => ret.push(None),
+            Coerced::Converted(value) => ret.push(Some(value)),
+            Coerced::Inconvertible => {
+                return Err(illegal_argument(
+                    env,
+                    &format!(
+                        "{sig}: cannot convert argument {index} of {} to {}",
+                        describe_value(env, arg),
+                        param.to_java_name()
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(ret)
+}
+
+fn illegal_argument<'env>(env: &'env JNIEnv, msg: &str) -> LocalObject<'env, JavaThrowable> {
+    throwable::helper::new_named_exception(env, c"java/lang/IllegalArgumentException", msg)
+}
+
+/// Describes the type of `value` in Java source style, e.g. `int`, `null` or `java.lang.String`.
+fn describe_value(env: &JNIEnv, value: &JValue<'_>) -> String {
+    match value {
+        JValue::Object(None) => "null".into(),
+        JValue::Object(Some(obj)) => Reflection::new(env)
+            .and_then(|r| r.class_name(&env.get_object_class(&**obj)))
+            .unwrap_or_else(|_| "java.lang.Object".into()),
+        value => {
+            let primitive = value.primitive_signature().expect("BROKEN: value is not primitive");
+
+            OwnedSignature::from(primitive).to_java_name()
+        }
+    }
+}
+
+fn describe_values(env: &JNIEnv, args: &[JValue<'_>]) -> String {
+    args.iter().map(|arg| describe_value(env, arg)).collect::<Vec<_>>().join(", ")
+}
+
+/// Calls `method` returning `ret` with `args`.
+///
+/// # Safety
+///
+/// `this`, `method` and `args` must match.
+unsafe fn invoke<'env, const STATIC: bool, R: StrongRef>(
+    env: &'env JNIEnv,
+    this: &R,
+    method: MethodID<STATIC>,
+    ret: &OwnedSignature,
+    args: &[Arg<'_>],
+) -> Result<JValue<'env>, LocalObject<'env, JavaThrowable>> {
+    let args = args.iter().copied();
+
+    unsafe {
+        let ret = match ret {
+            OwnedSignature::Void => env.call_void_method_variadic(this, method, args).map(|_| JValue::Void),
+            OwnedSignature::Boolean => env.call_boolean_method_variadic(this, method, args).map(JValue::Boolean),
+            OwnedSignature::Byte => env.call_byte_method_variadic(this, method, args).map(JValue::Byte),
+            OwnedSignature::Char => env.call_char_method_variadic(this, method, args).map(JValue::Char),
+            OwnedSignature::Short => env.call_short_method_variadic(this, method, args).map(JValue::Short),
+            OwnedSignature::Int => env.call_int_method_variadic(this, method, args).map(JValue::Int),
+            OwnedSignature::Long => env.call_long_method_variadic(this, method, args).map(JValue::Long),
+            OwnedSignature::Float => env.call_float_method_variadic(this, method, args).map(JValue::Float),
+            OwnedSignature::Double => env.call_double_method_variadic(this, method, args).map(JValue::Double),
+            OwnedSignature::Object(_) | OwnedSignature::Array(_) => env
+                .call_object_method_variadic(this, method, args)
+                .map(|v| JValue::Object(v.map(|v| LocalObject::from_ref(v)))),
+        };
+
+        ret.map_err(|err| LocalObject::from_ref(err))
+    }
+}
+
+/// Calls method `name` of `sig` on `this` with `args` converted to the parameters.
+fn call_with_signature<'env, T>(
+    env: &'env JNIEnv,
+    this: &T,
+    name: &str,
+    sig: &MethodSignature,
+    args: &[JValue<'_>],
+) -> Result<JValue<'env>, LocalObject<'env, JavaThrowable>>
+where
+    T: TypedRef,
+    T::Target: StrongRef + Sized,
+{
+    let name = resolver::helper::build_member_name(env, name, MemberKind::Method)?;
+    let signature = method_signature_to_cstring(sig);
+
+    unsafe {
+        if T::STATIC {
+            let coerced = coerce_all(env, &**this, sig, args)?;
+            let args = coerced_args(&coerced, args);
+
+            let method = resolver::resolve_method::<true, _>(env, &**this, &name, &signature)?;
+
+            invoke(env, &**this, method, &sig.ret, &args)
+        } else {
+            let cls = env.get_object_class(&**this);
+
+            let coerced = coerce_all(env, &cls, sig, args)?;
+            let args = coerced_args(&coerced, args);
+
+            let method = resolver::resolve_method::<false, _>(env, &cls, &name, &signature)?;
+
+            invoke(env, &**this, method, &sig.ret, &args)
+        }
+    }
+}
+
+/// Takes the converted arguments, or the original ones passed as is.
+fn coerced_args<'a>(coerced: &'a [Option<JValue<'_>>], args: &'a [JValue<'_>]) -> Vec<Arg<'a>> {
+    coerced
+        .iter()
+        .zip(args)
+        .map(|(coerced, arg)| coerced.as_ref().unwrap_or(arg).as_arg())
+        .collect()
+}
+
+/// Creates an object of `cls` with constructor of `sig` and `args` converted to the parameters.
+fn new_with_signature<'env, R: StrongRef, T: ObjectType>(
+    env: &'env JNIEnv,
+    cls: &Class<R, T>,
+    sig: &MethodSignature,
+    args: &[JValue<'_>],
+) -> Result<LocalObject<'env, T>, LocalObject<'env, JavaThrowable>> {
+    let coerced = coerce_all(env, &**cls, sig, args)?;
+    let args = coerced_args(&coerced, args);
+
+    let signature = method_signature_to_cstring(sig);
+
+    unsafe {
+        let method = resolver::resolve_method::<false, _>(env, &**cls, c"<init>", &signature)?;
+
+        env.new_object_variadic(&**cls, method, args)
+            .map(|v| LocalObject::from_ref(v))
+            .map_err(|err| LocalObject::from_ref(err))
+    }
+}
+
+fn method_signature_to_cstring(sig: &MethodSignature) -> CString {
+    let mut s = String::with_capacity(sig.size_hint() + 1);
+
+    sig.write_to(&mut s).unwrap();
+
+    CString::new(s).expect("BROKEN: parsed descriptor contains NUL")
+}
+
+fn parse_descriptor<'env>(env: &'env JNIEnv, descriptor: &str) -> Result<MethodSignature, LocalObject<'env, JavaThrowable>> {
+    MethodSignature::parse(descriptor)
+        .map_err(|err| illegal_argument(env, &format!("invalid method descriptor {descriptor:?}: {err}")))
+}
+
+/// An applicable overload.
+struct Candidate<'env> {
+    sig: MethodSignature,
+    param_classes: Vec<LocalRef<'env>>,
+    /// 1 if applicable by subtyping and widening, 2 if boxing or unboxing is also required.
+    phase: u8,
+}
+
+impl<'env> Candidate<'env> {
+    /// Returns whether every parameter is a subtype of or widens to the parameter of `other`.
+    fn is_more_specific_than(&self, env: &JNIEnv, other: &Candidate) -> bool {
+        let params = self.sig.params.iter().zip(&self.param_classes);
+        let other_params = other.sig.params.iter().zip(&other.param_classes);
+
+        params.zip(other_params).all(|((param, cls), (other_param, other_cls))| {
+            match (primitive_of(param), primitive_of(other_param)) {
+                (Some(param), Some(other_param)) => widens(param, other_param),
+                (None, None) => unsafe { env.is_assignable_from(cls, other_cls) },
+                _ => false,
+            }
+        })
+    }
+}
+
+/// Returns the phase in which `args` are applicable to `params`, `None` if not applicable.
+fn applicability<'env>(
+    env: &'env JNIEnv,
+    params: &[OwnedSignature],
+    param_classes: &[LocalRef<'env>],
+    args: &[JValue<'_>],
+) -> Result<Option<u8>, LocalObject<'env, JavaThrowable>> {
+    let mut phase = 1;
+
+    for ((param, cls), arg) in params.iter().zip(param_classes).zip(args) {
+        let arg_phase = match (primitive_of(param), arg) {
+            (_, JValue::Void) => None,
+            (Some(to), JValue::Object(Some(obj))) => unboxable(env, &**obj, to)?.map(|_| 2),
+            (Some(_), JValue::Object(None)) => None,
+            (Some(to), value) => value.primitive_signature().filter(|from| widens(*from, to)).map(|_| 1),
+            (None, JValue::Object(Some(obj))) => unsafe { env.is_instance_of(&**obj, cls) }.then_some(1),
+            (None, JValue::Object(None)) => Some(1),
+            (None, value) => {
+                let primitive = value.primitive_signature().expect("BROKEN: value is not primitive");
+                let (boxed, _) = boxing(env, primitive)?;
+
+                unsafe { env.is_assignable_from(&boxed, cls) }.then_some(2)
+            }
+        };
+
+        match arg_phase {
+            Some(arg_phase) => phase = phase.max(arg_phase),
+            None => return Ok(None),
+        }
+    }
+
+    Ok(Some(phase))
+}
+
+/// Picks the most specific overload of `name` in `cls` applicable to `args`, like javac without varargs.
+fn find_overload<'env, C: StrongRef>(
+    env: &'env JNIEnv,
+    cls: &C,
+    name: &str,
+    is_static: bool,
+    args: &[JValue<'_>],
+) -> Result<MethodSignature, LocalObject<'env, JavaThrowable>> {
+    let constructors = name == "<init>";
+    let cls = env.new_local_ref(cls).expect("BROKEN: class is null");
+
+    let r = Reflection::new(env).map_err(|err| unsafe { LocalObject::from_ref(err) })?;
+
+    let mut candidates = Vec::new();
+    let mut seen = Vec::<Vec<OwnedSignature>>::new();
+
+    r.for_each_method(&cls, constructors, |method| {
+        if !constructors && r.member_name(&method)? != name {
+            return Ok(());
+        }
+
+        let modifiers = r.modifiers(&method)?;
+        if modifiers & (MODIFIER_BRIDGE | MODIFIER_SYNTHETIC) != 0 {
+            return Ok(());
+        }
+        if !constructors && (modifiers & MODIFIER_STATIC != 0) != is_static {
+            return Ok(());
+        }
+
+        let param_classes = r.parameter_types(&method)?;
+        if param_classes.len() != args.len() {
+            return Ok(());
+        }
+
+        let params = param_classes
+            .iter()
+            .map(|cls| r.signature(cls))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // overridden methods in superclasses are visited later
+        if seen.contains(&params) {
+            return Ok(());
+        }
+        seen.push(params.clone());
+
+        let ret = match r.return_type(&method, constructors)? {
+            Some(ret) => r.signature(&ret)?,
+            None => OwnedSignature::Void,
+        };
+
+        match applicability(env, &params, &param_classes, args) {
+            Ok(Some(phase)) => candidates.push(Candidate {
+                sig: MethodSignature { params, ret },
+                param_classes,
+                phase,
+            }),
+            Ok(None) => (),
+            Err(err) => return Err(err.into_ref()),
+        }
+
+        Ok(())
+    })
+    .map_err(|err| unsafe { LocalObject::from_ref(err) })?;
+
+    let describe_call = || {
+        let class_name = r.class_name(&cls).unwrap_or_default();
+
+        format!("{class_name}.{name}({})", describe_values(env, args))
+    };
+
+    let Some(phase) = candidates.iter().map(|c| c.phase).min() else {
+        return Err(throwable::helper::new_named_exception(
+            env,
+            c"java/lang/NoSuchMethodError",
+            &format!("no applicable overload for {}", describe_call()),
+        ));
+    };
+    candidates.retain(|c| c.phase == phase);
+
+    // candidates that no other candidate is strictly more specific than
+    let maximal = (0..candidates.len())
+        .filter(|&index| {
+            let c = &candidates[index];
+
+            candidates.iter().enumerate().all(|(other_index, other)| {
+                index == other_index || !other.is_more_specific_than(env, c) || c.is_more_specific_than(env, other)
+            })
+        })
+        .collect::<Vec<_>>();
+
+    match maximal[..] {
+        [index] => Ok(candidates.swap_remove(index).sig),
+        _ => {
+            let overloads = maximal
+                .iter()
+                .map(|&index| format!("{}", candidates[index].sig))
+                .collect::<Vec<_>>();
+
+            Err(illegal_argument(
+                env,
+                &format!("ambiguous overloads {} for {}", overloads.join(", "), describe_call()),
+            ))
+        }
+    }
+}
+
+/// Extension methods for calls whose signatures are only known at runtime.
+pub trait TypedDynamicCallExt {
+    /// Calls a method with a method descriptor, e.g. `(ILjava/lang/String;)J`.
+    ///
+    /// # This
+    ///
+    /// * `&Object<impl StrongRef, Type>` - A typed reference to an object. call as instance method.
+    /// * `&Class<impl StrongRef, Type>` - A typed reference to a class. call as static method.
+    ///
+    /// # Args
+    ///
+    /// Arguments are converted to the parameters by widening primitive, boxing and unboxing conversions,
+    /// an `IllegalArgumentException` is returned if it is not possible. Objects are passed as is
+    /// if they are instances of the parameter types, which are found with the class loader of the class.
+    ///
+    /// # Returns
+    ///
+    /// * `JValue` - The returned value, [`JValue::Void`] for `void` methods.
+    fn typed_call_method_dynamic<'env, T>(
+        &'env self,
+        this: &T,
+        name: &str,
+        descriptor: &str,
+        args: &[JValue<'_>],
+    ) -> Result<JValue<'env>, LocalObject<'env, JavaThrowable>>
+    where
+        T: TypedRef,
+        T::Target: StrongRef + Sized;
+
+    /// Calls a constructor with a method descriptor, e.g. `(ILjava/lang/String;)V`.
+    ///
+    /// Arguments are converted like [`TypedDynamicCallExt::typed_call_method_dynamic`].
+    fn typed_new_object_dynamic<R: StrongRef, T: ObjectType>(
+        &self,
+        cls: &Class<R, T>,
+        descriptor: &str,
+        args: &[JValue<'_>],
+    ) -> Result<LocalObject<'_, T>, LocalObject<'_, JavaThrowable>>;
+
+    /// Finds the overload of a method or constructor (named `<init>`) that javac would pick for `args`.
+    ///
+    /// Overloads are listed by reflection, and the most specific one is picked among those applicable
+    /// by subtyping and widening, or else among those applicable by also boxing and unboxing. Varargs are not expanded.
+    ///
+    /// # This
+    ///
+    /// * `&Object<impl StrongRef, Type>` - A typed reference to an object. find instance methods.
+    /// * `&Class<impl StrongRef, Type>` - A typed reference to a class. find static methods or constructors.
+    ///
+    /// # Returns
+    ///
+    /// * `MethodSignature` - The signature of the overload.
+    /// * `NoSuchMethodError` - If there is no applicable overload.
+    /// * `IllegalArgumentException` - If there are ambiguous overloads.
+    fn typed_find_overload<'env, T>(
+        &'env self,
+        this: &T,
+        name: &str,
+        args: &[JValue<'_>],
+    ) -> Result<MethodSignature, LocalObject<'env, JavaThrowable>>
+    where
+        T: TypedRef,
+        T::Target: StrongRef + Sized;
+
+    /// Calls the overload found by [`TypedDynamicCallExt::typed_find_overload`].
+    ///
+    /// NOTE: overloads are listed by reflection on every call.
+    fn typed_call_method_overloaded<'env, T>(
+        &'env self,
+        this: &T,
+        name: &str,
+        args: &[JValue<'_>],
+    ) -> Result<JValue<'env>, LocalObject<'env, JavaThrowable>>
+    where
+        T: TypedRef,
+        T::Target: StrongRef + Sized;
+
+    /// Calls the constructor found by [`TypedDynamicCallExt::typed_find_overload`].
+    ///
+    /// NOTE: overloads are listed by reflection on every call.
+    fn typed_new_object_overloaded<R: StrongRef, T: ObjectType>(
+        &self,
+        cls: &Class<R, T>,
+        args: &[JValue<'_>],
+    ) -> Result<LocalObject<'_, T>, LocalObject<'_, JavaThrowable>>;
+}
+
+impl<'vm> TypedDynamicCallExt for JNIEnv<'vm> {
+    fn typed_call_method_dynamic<'env, T>(
+        &'env self,
+        this: &T,
+        name: &str,
+        descriptor: &str,
+        args: &[JValue<'_>],
+    ) -> Result<JValue<'env>, LocalObject<'env, JavaThrowable>>
+    where
+        T: TypedRef,
+        T::Target: StrongRef + Sized,
+    {
+        let sig = parse_descriptor(self, descriptor)?;
+
+        call_with_signature(self, this, name, &sig, args)
+    }
+
+    fn typed_new_object_dynamic<R: StrongRef, T: ObjectType>(
+        &self,
+        cls: &Class<R, T>,
+        descriptor: &str,
+        args: &[JValue<'_>],
+    ) -> Result<LocalObject<'_, T>, LocalObject<'_, JavaThrowable>> {
+        let sig = parse_descriptor(self, descriptor)?;
+        if sig.ret != OwnedSignature::Void {
+            return Err(illegal_argument(self, &format!("constructor {sig} does not return void")));
+        }
+
+        new_with_signature(self, cls, &sig, args)
+    }
+
+    fn typed_find_overload<'env, T>(
+        &'env self,
+        this: &T,
+        name: &str,
+        args: &[JValue<'_>],
+    ) -> Result<MethodSignature, LocalObject<'env, JavaThrowable>>
+    where
+        T: TypedRef,
+        T::Target: StrongRef + Sized,
+    {
+        if T::STATIC {
+            find_overload(self, &**this, name, name != "<init>", args)
+        } else {
+            find_overload(self, &self.get_object_class(&**this), name, false, args)
+        }
+    }
+
+    fn typed_call_method_overloaded<'env, T>(
+        &'env self,
+        this: &T,
+        name: &str,
+        args: &[JValue<'_>],
+    ) -> Result<JValue<'env>, LocalObject<'env, JavaThrowable>>
+    where
+        T: TypedRef,
+        T::Target: StrongRef + Sized,
+    {
+        let sig = self.typed_find_overload(this, name, args)?;
+
+        call_with_signature(self, this, name, &sig, args)
+    }
+
+    fn typed_new_object_overloaded<R: StrongRef, T: ObjectType>(
+        &self,
+        cls: &Class<R, T>,
+        args: &[JValue<'_>],
+    ) -> Result<LocalObject<'_, T>, LocalObject<'_, JavaThrowable>> {
+        let sig = self.typed_find_overload(cls, "<init>", args)?;
+
+        new_with_signature(self, cls, &sig, args)
+    }
+}
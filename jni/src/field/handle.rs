@@ -0,0 +1,175 @@
+use core::marker::PhantomData;
+
+use typed_jni_core::{FieldID, JNIEnv, StrongRef};
+
+use crate::{
+    Class, GlobalClass, LocalObject, Null, Object, ObjectType, Type, TypedClassExt, TypedRef,
+    builtin::JavaThrowable,
+    field::{Got, Value},
+    resolver,
+    resolver::helper::MemberKind,
+    throwable,
+};
+
+/// A field of `Owner` with Java type `V`, resolved once and accessed without any lookup.
+///
+/// The handle holds a global reference to the class, so the field ID stays valid as long as the handle lives.
+/// It can be shared across threads and stored in `static` lazy cells.
+///
+/// `Owner` only names a class, objects of a class with the same name from another class loader are also typed as
+/// `Owner`. Instance fields are accessed only on instances of the resolved class, others are rejected with
+/// `IllegalArgumentException`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::sync::OnceLock;
+///
+/// use typed_jni::{FieldHandle, LocalObject, core::JNIEnv, define_java_class};
+///
+/// define_java_class!(JavaPoint, "org.example.Point"); // with a `public int x` field
+///
+/// static X: OnceLock<FieldHandle<JavaPoint, i32, false>> = OnceLock::new();
+///
+/// fn sum_x(env: &JNIEnv<'static>, points: &[LocalObject<JavaPoint>]) -> i32 {
+///     let x = X.get_or_init(|| FieldHandle::resolve(env, "x").unwrap());
+///
+///     points.iter().map(|p| x.get::<_, i32>(env, p).unwrap()).sum()
+/// }
+/// ```
+pub struct FieldHandle<Owner: ObjectType, V: Type + 'static, const STATIC: bool> {
+    class: GlobalClass<'static, Owner>,
+    field: FieldID<STATIC>,
+    _value: PhantomData<fn() -> V>,
+}
+
+impl<Owner: ObjectType, V: Type + 'static, const STATIC: bool> FieldHandle<Owner, V, STATIC> {
+    /// Resolves the field `name` of the class of `Owner`, which is found with [`TypedClassExt::typed_find_class`].
+    pub fn resolve<'env>(env: &'env JNIEnv<'static>, name: &str) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        let cls = env.typed_find_class::<Owner>()?;
+
+        Self::resolve_in(env, &cls, name)
+    }
+
+    /// Resolves the field `name` of `cls`, use this for classes from other class loaders.
+    pub fn resolve_in<'env, R: StrongRef>(
+        env: &'env JNIEnv<'static>,
+        cls: &Class<R, Owner>,
+        name: &str,
+    ) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        let name = resolver::helper::build_member_name(env, name, MemberKind::Field)?;
+        let signature = resolver::helper::field_signature_of::<V>(env)?;
+
+        let field = resolver::resolve_field::<STATIC, _>(env, &**cls, &name, &signature)?;
+
+        let class = env
+            .new_global_ref(&**cls)
+            .expect("BROKEN: create new global reference failed");
+
+        Ok(Self {
+            class: unsafe { Class::from_ref(class) },
+            field,
+            _value: PhantomData,
+        })
+    }
+
+    /// Returns the class the field is resolved in.
+    pub fn class(&self) -> &GlobalClass<'static, Owner> {
+        &self.class
+    }
+}
+
+impl<Owner: ObjectType, V: Type + 'static> FieldHandle<Owner, V, false> {
+    /// Rejects objects which are not instances of the resolved class, whose fields have other IDs.
+    fn check_instance<'env, R: StrongRef>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, Owner>,
+    ) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        if unsafe { env.is_instance_of(&**obj, &*self.class) } {
+            return Ok(());
+        }
+
+        Err(throwable::helper::new_named_exception(
+            env,
+            c"java/lang/IllegalArgumentException",
+            "object is not an instance of the class the field is resolved in",
+        ))
+    }
+
+    /// Gets the value of the field of `obj`, which must be an instance of [`class`](Self::class).
+    pub fn get<'env, R: StrongRef, G>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, Owner>,
+    ) -> Result<G, LocalObject<'env, JavaThrowable>>
+    where
+        G: Got<'env> + FieldValue<V>,
+    {
+        self.check_instance(env, obj)?;
+
+        unsafe { G::get_of(env, &**obj, self.field) }
+    }
+
+    /// Sets the value of the field of `obj`, which must be an instance of [`class`](Self::class).
+    pub fn set<'env, R: StrongRef, S>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, Owner>,
+        value: S,
+    ) -> Result<(), LocalObject<'env, JavaThrowable>>
+    where
+        S: Value + FieldValue<V>,
+    {
+        self.check_instance(env, obj)?;
+
+        unsafe { value.set_on(env, &**obj, self.field) }
+    }
+}
+
+impl<Owner: ObjectType, V: Type + 'static> FieldHandle<Owner, V, true> {
+    /// Gets the value of the static field.
+    pub fn get<'env, G>(&self, env: &'env JNIEnv) -> Result<G, LocalObject<'env, JavaThrowable>>
+    where
+        G: Got<'env> + FieldValue<V>,
+    {
+        unsafe { G::get_of(env, &*self.class, self.field) }
+    }
+
+    /// Sets the value of the static field.
+    pub fn set<'env, S>(&self, env: &'env JNIEnv, value: S) -> Result<(), LocalObject<'env, JavaThrowable>>
+    where
+        S: Value + FieldValue<V>,
+    {
+        unsafe { value.set_on(env, &*self.class, self.field) }
+    }
+}
+
+/// This trait is implemented for all types that can be got from or set to a field of Java type `V`
+/// through a [`FieldHandle`].
+///
+/// Supported Types:
+///
+/// * Primitive types: `bool`, `i8`, `u16`, `i16`, `i32`, `i64`, `f32`, `f64` of themselves
+/// * Object types: `Object<impl StrongRef, V>`, `&Object<impl StrongRef, V>`, `Option` of them and `Null<V>`
+///
+/// # Safety
+///
+/// This trait should not be implemented manually.
+pub unsafe trait FieldValue<V: Type> {}
+
+macro_rules! impl_field_value_for_primitive {
+    ($($typ:ty),*) => {
+        $(
+            unsafe impl FieldValue<$typ> for $typ {}
+        )*
+    };
+}
+
+impl_field_value_for_primitive!(bool, i8, u16, i16, i32, i64, f32, f64);
+
+unsafe impl<R: StrongRef, T: ObjectType> FieldValue<T> for Object<R, T> {}
+unsafe impl<R: StrongRef, T: ObjectType> FieldValue<T> for &Object<R, T> {}
+unsafe impl<R: StrongRef, T: ObjectType> FieldValue<T> for Option<Object<R, T>> {}
+unsafe impl<R: StrongRef, T: ObjectType> FieldValue<T> for Option<&Object<R, T>> {}
+unsafe impl<T: ObjectType> FieldValue<T> for Null<T> {}
@@ -1,9 +1,14 @@
 mod get;
+mod handle;
 mod set;
 
 use typed_jni_core::{JNIEnv, StrongRef};
 
-pub use self::{get::Got, set::Value};
+pub use self::{
+    get::Got,
+    handle::{FieldHandle, FieldValue},
+    set::Value,
+};
 use crate::{LocalObject, Type, TypedRef, builtin::JavaThrowable, resolver, resolver::helper::MemberKind};
 
 /// Extension methods for typed field access.
@@ -32,7 +37,17 @@ impl<'vm> TypedFieldAccessExt for JNIEnv<'vm> {
     {
         unsafe {
             let name = resolver::helper::build_member_name(self, name, MemberKind::Field)?;
-            let signature = resolver::helper::build_field_signature(self, R::SIGNATURE)?;
+            let signature = resolver::helper::field_signature_of::<R>(self)?;
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!(
+                "get_field",
+                class = %<T::Type as Type>::SIGNATURE,
+                name = ?&*name,
+                sig = ?&*signature,
+                is_static = T::STATIC
+            )
+            .entered();
 
             if T::STATIC {
                 let field = resolver::resolve_field::<true, _>(self, &**this, &name, &signature)?;
@@ -56,7 +71,17 @@ impl<'vm> TypedFieldAccessExt for JNIEnv<'vm> {
     {
         unsafe {
             let name = resolver::helper::build_member_name(self, name, MemberKind::Field)?;
-            let signature = resolver::helper::build_field_signature(self, V::SIGNATURE)?;
+            let signature = resolver::helper::field_signature_of::<V>(self)?;
+
+            #[cfg(feature = "tracing")]
+            let _span = tracing::debug_span!(
+                "set_field",
+                class = %<T::Type as Type>::SIGNATURE,
+                name = ?&*name,
+                sig = ?&*signature,
+                is_static = T::STATIC
+            )
+            .entered();
 
             if T::STATIC {
                 let field = resolver::resolve_field::<true, _>(self, &**this, &name, &signature)?;
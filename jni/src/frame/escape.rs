@@ -0,0 +1,185 @@
+use alloc::{string::String, vec::Vec};
+
+use typed_jni_core::LocalRef;
+
+use crate::{LocalClass, LocalObject, ObjectType, TypedRef};
+
+/// A value that can escape from a local frame.
+///
+/// Supported Types:
+///
+/// * No value: `()`
+/// * Rust primitive types: `bool`, `char`, integers and floats
+/// * Rust string: `String`
+/// * Local references: `LocalObject<Type>`, `LocalClass<Type>`
+/// * Containers: `Option<impl FrameEscape>`, `Result<impl FrameEscape, impl FrameEscape>`, `Vec<impl FrameEscape>`
+/// * Tuples: `(impl FrameEscape, ...)` (Max 8 elements)
+///
+/// # Safety
+///
+/// This trait should not be implemented manually.
+pub unsafe trait FrameEscape {
+    /// The type of value with local references bound to `'env`.
+    type Value<'env>;
+
+    /// The type of value with local references taken out.
+    type Detached;
+
+    /// Takes local references out of the value.
+    fn detach<'env>(value: Self::Value<'env>, refs: &mut Vec<LocalRef<'env>>) -> Self::Detached;
+
+    /// Puts local references back to the value.
+    ///
+    /// # Safety
+    ///
+    /// `refs` must yield references in the same order and the same types as [`FrameEscape::detach`] takes.
+    unsafe fn attach<'env>(detached: Self::Detached, refs: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env>;
+}
+
+macro_rules! impl_frame_escape_for_plain {
+    ($($typ:ty),*) => {
+        $(
+            unsafe impl FrameEscape for $typ {
+                type Value<'env> = Self;
+
+                type Detached = Self;
+
+                fn detach<'env>(value: Self::Value<'env>, _: &mut Vec<LocalRef<'env>>) -> Self::Detached {
+                    value
+                }
+
+                unsafe fn attach<'env>(detached: Self::Detached, _: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env> {
+                    detached
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_escape_for_plain!(
+    (),
+    bool,
+    char,
+    i8,
+    u8,
+    i16,
+    u16,
+    i32,
+    u32,
+    i64,
+    u64,
+    isize,
+    usize,
+    f32,
+    f64,
+    String
+);
+
+macro_rules! impl_frame_escape_for_local {
+    ($variant:ident) => {
+        unsafe impl<'a, T: ObjectType> FrameEscape for $variant<'a, T> {
+            type Value<'env> = $variant<'env, T>;
+
+            type Detached = ();
+
+            fn detach<'env>(value: Self::Value<'env>, refs: &mut Vec<LocalRef<'env>>) -> Self::Detached {
+                refs.push(value.into_ref());
+            }
+
+            unsafe fn attach<'env>(_: Self::Detached, refs: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env> {
+                unsafe { $variant::from_ref(refs.next().expect("BROKEN: escaped reference missing")) }
+            }
+        }
+    };
+}
+
+impl_frame_escape_for_local!(LocalObject);
+impl_frame_escape_for_local!(LocalClass);
+
+unsafe impl<E: FrameEscape> FrameEscape for Option<E> {
+    type Value<'env> = Option<E::Value<'env>>;
+
+    type Detached = Option<E::Detached>;
+
+    fn detach<'env>(value: Self::Value<'env>, refs: &mut Vec<LocalRef<'env>>) -> Self::Detached {
+        value.map(|v| E::detach(v, refs))
+    }
+
+    unsafe fn attach<'env>(detached: Self::Detached, refs: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env> {
+        unsafe { detached.map(|v| E::attach(v, refs)) }
+    }
+}
+
+unsafe impl<O: FrameEscape, E: FrameEscape> FrameEscape for Result<O, E> {
+    type Value<'env> = Result<O::Value<'env>, E::Value<'env>>;
+
+    type Detached = Result<O::Detached, E::Detached>;
+
+    fn detach<'env>(value: Self::Value<'env>, refs: &mut Vec<LocalRef<'env>>) -> Self::Detached {
+        match value {
+            Ok(v) => Ok(O::detach(v, refs)),
+            Err(v) => Err(E::detach(v, refs)),
+        }
+    }
+
+    unsafe fn attach<'env>(detached: Self::Detached, refs: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env> {
+        unsafe {
+            match detached {
+                Ok(v) => Ok(O::attach(v, refs)),
+                Err(v) => Err(E::attach(v, refs)),
+            }
+        }
+    }
+}
+
+unsafe impl<E: FrameEscape> FrameEscape for Vec<E> {
+    type Value<'env> = Vec<E::Value<'env>>;
+
+    type Detached = Vec<E::Detached>;
+
+    fn detach<'env>(value: Self::Value<'env>, refs: &mut Vec<LocalRef<'env>>) -> Self::Detached {
+        value.into_iter().map(|v| E::detach(v, refs)).collect()
+    }
+
+    unsafe fn attach<'env>(detached: Self::Detached, refs: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env> {
+        unsafe { detached.into_iter().map(|v| E::attach(v, refs)).collect() }
+    }
+}
+
+macro_rules! impl_frame_escape_for_tuple {
+    ($($n:ident),*) => {
+        unsafe impl<$($n: FrameEscape),*> FrameEscape for ($($n,)*) {
+            type Value<'env> = ($($n::Value<'env>,)*);
+
+            type Detached = ($($n::Detached,)*);
+
+            fn detach<'env>(value: Self::Value<'env>, refs: &mut Vec<LocalRef<'env>>) -> Self::Detached {
+                #[allow(non_snake_case)]
+                let ($($n,)*) = value;
+
+                ($($n::detach($n, refs),)*)
+            }
+
+            unsafe fn attach<'env>(detached: Self::Detached, refs: &mut impl Iterator<Item = LocalRef<'env>>) -> Self::Value<'env> {
+                unsafe {
+                    #[allow(non_snake_case)]
+                    let ($($n,)*) = detached;
+
+                    ($($n::attach($n, refs),)*)
+                }
+            }
+        }
+    };
+}
+
+#[rustfmt::skip]
+const _: () = {
+    impl_frame_escape_for_tuple!(A1);
+    impl_frame_escape_for_tuple!(A1, A2);
+    impl_frame_escape_for_tuple!(A1, A2, A3);
+    impl_frame_escape_for_tuple!(A1, A2, A3, A4);
+    impl_frame_escape_for_tuple!(A1, A2, A3, A4, A5);
+    impl_frame_escape_for_tuple!(A1, A2, A3, A4, A5, A6);
+    impl_frame_escape_for_tuple!(A1, A2, A3, A4, A5, A6, A7);
+    impl_frame_escape_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8);
+};
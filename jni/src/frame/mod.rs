@@ -0,0 +1,114 @@
+mod escape;
+
+use alloc::vec::Vec;
+
+use typed_jni_core::{GlobalRef, JNIEnv};
+
+pub use self::escape::FrameEscape;
+use crate::{LocalObject, TypedRef, builtin::JavaThrowable};
+
+/// Extension methods for typed local frame maintenance.
+pub trait TypedFrameExt<'vm> {
+    /// Runs `f` with a pushed local frame, all local references created in `f` will be released after it returns
+    /// except those in the returned value, which are moved to the outer frame.
+    ///
+    /// The type of returned value should be specified by `S`, which implements [`FrameEscape`].
+    /// e.g. `(LocalObject<JavaString>, Vec<LocalObject<JavaString>>)`.
+    /// Local references created with the env given to `f` are bound to the lifetime of the frame,
+    /// so they cannot escape in any other way.
+    ///
+    /// # Safety
+    ///
+    /// * `f` must create local references only with the given [`JNIEnv`]. References created with a captured
+    ///   outer env are bound to the outer lifetime, but they are released when the frame is popped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use typed_jni::{LocalObject, TypedFrameExt, TypedStringExt, builtin::JavaString, core::JNIEnv};
+    ///
+    /// fn concat<'env>(env: &'env JNIEnv<'static>) -> LocalObject<'env, JavaString> {
+    ///     unsafe {
+    ///         env.typed_with_local_frame::<LocalObject<JavaString>, _>(16, |env| {
+    ///             let s = (0..1024).map(|i| env.typed_get_string(&env.typed_new_string(i.to_string()))).collect::<String>();
+    ///
+    ///             env.typed_new_string(s)
+    ///         })
+    ///     }
+    ///     .unwrap()
+    /// }
+    /// ```
+    ///
+    /// Capturing the outer env would let references escape the frame, so the call requires `unsafe`:
+    ///
+    /// ```rust,compile_fail,E0133
+    /// use typed_jni::{LocalObject, TypedFrameExt, TypedStringExt, builtin::JavaString, core::JNIEnv};
+    ///
+    /// fn escape<'env>(env: &'env JNIEnv<'static>) -> Option<LocalObject<'env, JavaString>> {
+    ///     let mut escaped = None;
+    ///
+    ///     env.typed_with_local_frame::<(), _>(16, |_| {
+    ///         escaped = Some(env.typed_new_string("x"));
+    ///     })
+    ///     .unwrap();
+    ///
+    ///     escaped
+    /// }
+    /// ```
+    unsafe fn typed_with_local_frame<'env, S, F>(
+        &'env self,
+        capacity: i32,
+        f: F,
+    ) -> Result<S::Value<'env>, LocalObject<'env, JavaThrowable>>
+    where
+        S: FrameEscape,
+        F: for<'scope> FnOnce(&'scope JNIEnv<'vm>) -> S::Value<'scope>;
+}
+
+impl<'vm> TypedFrameExt<'vm> for JNIEnv<'vm> {
+    unsafe fn typed_with_local_frame<'env, S, F>(
+        &'env self,
+        capacity: i32,
+        f: F,
+    ) -> Result<S::Value<'env>, LocalObject<'env, JavaThrowable>>
+    where
+        S: FrameEscape,
+        F: for<'scope> FnOnce(&'scope JNIEnv<'vm>) -> S::Value<'scope>,
+    {
+        unsafe {
+            let ((detached, globals), local) = self
+                .with_push_local_frame(capacity, |env| {
+                    let mut refs = Vec::new();
+
+                    let detached = S::detach(f(env), &mut refs);
+
+                    // a single reference can be carried by PopLocalFrame directly,
+                    // others are carried by global references.
+                    if refs.len() <= 1 {
+                        ((detached, Vec::new()), refs.pop())
+                    } else {
+                        let globals = refs
+                            .iter()
+                            .map(|r| env.new_global_ref(r).expect("BROKEN: create new global reference failed"))
+                            .collect::<Vec<GlobalRef<'vm>>>();
+
+                        ((detached, globals), None)
+                    }
+                })
+                .map_err(|err| LocalObject::from_ref(err))?;
+
+            if !globals.is_empty() {
+                self.ensure_local_capacity(globals.len() as i32)
+                    .map_err(|err| LocalObject::from_ref(err))?;
+            }
+
+            let mut refs = local.into_iter().chain(
+                globals
+                    .iter()
+                    .map(|r| self.new_local_ref(r).expect("BROKEN: create new local reference failed")),
+            );
+
+            Ok(S::attach(detached, &mut refs))
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use alloc::boxed::Box;
+
+use typed_jni_core::{Arg, JNIEnv, LocalRef, TrampolineRef};
+
+use crate::{
+    LocalObject, ObjectType, TrampolineObject, TypedRef,
+    builtin::{
+        JavaBiConsumer, JavaBiFunction, JavaCallable, JavaConsumer, JavaFunction, JavaObject, JavaPredicate, JavaRunnable,
+        JavaSupplier, JavaThrowable,
+    },
+    callback::{self, Callback, CallbackKind},
+    resolver,
+};
+
+/// Converts Rust closures into Java functional interface objects.
+///
+/// The objects are instances of helper classes embedded in this crate, which are defined in the system
+/// class loader on first use, see [`TypedHelperClassesExt`](crate::TypedHelperClassesExt) for other class loaders
+/// and Android. Each invocation calls the closure directly without reflection.
+///
+/// Closures may be called from any Java thread, and are released by `Cleaner` once the objects
+/// are garbage collected. A panic in the closure is thrown as `java.lang.Error`.
+pub trait TypedFunctionExt {
+    /// Creates a `java.lang.Runnable` calling `f`.
+    fn typed_new_runnable<F>(&self, f: F) -> Result<LocalObject<'_, JavaRunnable>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>) -> Result<(), LocalObject<'env, JavaThrowable>> + Send + Sync + 'static;
+
+    /// Creates a `java.util.concurrent.Callable` calling `f`.
+    fn typed_new_callable<F>(&self, f: F) -> Result<LocalObject<'_, JavaCallable>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+
+    /// Creates a `java.util.function.Supplier` calling `f`.
+    fn typed_new_supplier<F>(&self, f: F) -> Result<LocalObject<'_, JavaSupplier>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+
+    /// Creates a `java.util.function.Consumer` calling `f`.
+    fn typed_new_consumer<F>(&self, f: F) -> Result<LocalObject<'_, JavaConsumer>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<(), LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+
+    /// Creates a `java.util.function.BiConsumer` calling `f`.
+    fn typed_new_bi_consumer<F>(&self, f: F) -> Result<LocalObject<'_, JavaBiConsumer>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<(), LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+
+    /// Creates a `java.util.function.Function` calling `f`.
+    fn typed_new_function<F>(&self, f: F) -> Result<LocalObject<'_, JavaFunction>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+
+    /// Creates a `java.util.function.BiFunction` calling `f`.
+    fn typed_new_bi_function<F>(&self, f: F) -> Result<LocalObject<'_, JavaBiFunction>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+
+    /// Creates a `java.util.function.Predicate` calling `f`.
+    fn typed_new_predicate<F>(&self, f: F) -> Result<LocalObject<'_, JavaPredicate>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<bool, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static;
+}
+
+fn new_function<'env, T: ObjectType>(
+    env: &'env JNIEnv,
+    kind: CallbackKind,
+    callback: Box<Callback>,
+) -> Result<LocalObject<'env, T>, LocalObject<'env, JavaThrowable>> {
+    callback::new_native_callback(env, kind, callback).map(|obj| unsafe { LocalObject::from_ref(obj) })
+}
+
+fn to_object<'env>(arg: Option<TrampolineRef<'env>>) -> Option<TrampolineObject<'env, JavaObject>> {
+    arg.map(|arg| unsafe { TrampolineObject::from_ref(arg) })
+}
+
+fn into_raw<'env>(
+    ret: Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>,
+) -> Result<Option<LocalRef<'env>>, LocalRef<'env>> {
+    ret.map(|v| v.map(|v| v.into_ref())).map_err(|err| err.into_ref())
+}
+
+impl<'vm> TypedFunctionExt for JNIEnv<'vm> {
+    fn typed_new_runnable<F>(&self, f: F) -> Result<LocalObject<'_, JavaRunnable>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>) -> Result<(), LocalObject<'env, JavaThrowable>> + Send + Sync + 'static,
+    {
+        new_function(
+            self,
+            CallbackKind::Runnable,
+            Box::new(move |env, _, _| into_raw(f(env).map(|_| None))),
+        )
+    }
+
+    fn typed_new_callable<F>(&self, f: F) -> Result<LocalObject<'_, JavaCallable>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(self, CallbackKind::Callable, Box::new(move |env, _, _| into_raw(f(env))))
+    }
+
+    fn typed_new_supplier<F>(&self, f: F) -> Result<LocalObject<'_, JavaSupplier>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(&'env JNIEnv<'static>) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(self, CallbackKind::Supplier, Box::new(move |env, _, _| into_raw(f(env))))
+    }
+
+    fn typed_new_consumer<F>(&self, f: F) -> Result<LocalObject<'_, JavaConsumer>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<(), LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(
+            self,
+            CallbackKind::Consumer,
+            Box::new(move |env, a, _| into_raw(f(env, to_object(a)).map(|_| None))),
+        )
+    }
+
+    fn typed_new_bi_consumer<F>(&self, f: F) -> Result<LocalObject<'_, JavaBiConsumer>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<(), LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(
+            self,
+            CallbackKind::BiConsumer,
+            Box::new(move |env, a, b| into_raw(f(env, to_object(a), to_object(b)).map(|_| None))),
+        )
+    }
+
+    fn typed_new_function<F>(&self, f: F) -> Result<LocalObject<'_, JavaFunction>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(
+            self,
+            CallbackKind::Function,
+            Box::new(move |env, a, _| into_raw(f(env, to_object(a)))),
+        )
+    }
+
+    fn typed_new_bi_function<F>(&self, f: F) -> Result<LocalObject<'_, JavaBiFunction>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(
+            self,
+            CallbackKind::BiFunction,
+            Box::new(move |env, a, b| into_raw(f(env, to_object(a), to_object(b)))),
+        )
+    }
+
+    fn typed_new_predicate<F>(&self, f: F) -> Result<LocalObject<'_, JavaPredicate>, LocalObject<'_, JavaThrowable>>
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                Option<TrampolineObject<'env, JavaObject>>,
+            ) -> Result<bool, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        new_function(
+            self,
+            CallbackKind::Predicate,
+            Box::new(move |env, a, _| {
+                let ret = f(env, to_object(a)).map_err(|err| err.into_ref())?;
+
+                unsafe {
+                    let (cls, method) = resolver::resolve_class_and_method::<true>(
+                        env,
+                        c"java/lang/Boolean",
+                        c"valueOf",
+                        c"(Z)Ljava/lang/Boolean;",
+                    )
+                    .map_err(|err| err.into_ref())?;
+
+                    env.call_object_method(&cls, method, [Arg::Boolean(ret)])
+                }
+            }),
+        )
+    }
+}
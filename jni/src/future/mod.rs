@@ -0,0 +1,413 @@
+use alloc::{boxed::Box, string::String, sync::Arc};
+use core::{
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::atomic::{AtomicU8, Ordering},
+    task::{Context, Poll, Waker},
+};
+use std::{
+    sync::{Mutex, PoisonError},
+    task::Wake,
+};
+
+use typed_jni_core::{Arg, GlobalRef, JNIEnv, JavaVM, StrongRef};
+
+use crate::{
+    GlobalObject, LocalObject, Object, ObjectType, TypedCallExt, TypedClassExt, TypedRef,
+    builtin::{JavaCompletableFuture, JavaObject, JavaThrowable},
+    callback, resolver, throwable,
+};
+
+/// A value that can complete a Java `CompletableFuture`.
+///
+/// Supported Types:
+/// - `()`: completes with `null`
+/// - Primitives: `bool`, `i8`, `u16`, `i16`, `i32`, `i64`, `f32`, `f64`, completes with boxed values
+/// - `String`: completes with `java.lang.String`
+/// - `GlobalObject<'static, T>`: completes with the object
+/// - `Option<T>`: `None` completes with `null`
+pub trait CompletionValue: Send + 'static {
+    /// Converts the value to a Java object.
+    fn into_java<'env>(
+        self,
+        env: &'env JNIEnv,
+    ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>;
+}
+
+impl CompletionValue for () {
+    fn into_java<'env>(self, _: &'env JNIEnv) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+        Ok(None)
+    }
+}
+
+macro_rules! impl_completion_value_for_primitive {
+    ($typ:ty, $class:literal, $sig:literal) => {
+        impl CompletionValue for $typ {
+            fn into_java<'env>(
+                self,
+                env: &'env JNIEnv,
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+                unsafe {
+                    let (cls, method) = resolver::resolve_class_and_method::<true>(env, $class, c"valueOf", $sig)?;
+
+                    env.call_object_method(&cls, method, [Arg::from(self)])
+                        .map(|v| v.map(|v| LocalObject::from_ref(v)))
+                        .map_err(|err| LocalObject::from_ref(err))
+                }
+            }
+        }
+    };
+}
+
+impl_completion_value_for_primitive!(bool, c"java/lang/Boolean", c"(Z)Ljava/lang/Boolean;");
+impl_completion_value_for_primitive!(i8, c"java/lang/Byte", c"(B)Ljava/lang/Byte;");
+impl_completion_value_for_primitive!(u16, c"java/lang/Character", c"(C)Ljava/lang/Character;");
+impl_completion_value_for_primitive!(i16, c"java/lang/Short", c"(S)Ljava/lang/Short;");
+impl_completion_value_for_primitive!(i32, c"java/lang/Integer", c"(I)Ljava/lang/Integer;");
+impl_completion_value_for_primitive!(i64, c"java/lang/Long", c"(J)Ljava/lang/Long;");
+impl_completion_value_for_primitive!(f32, c"java/lang/Float", c"(F)Ljava/lang/Float;");
+impl_completion_value_for_primitive!(f64, c"java/lang/Double", c"(D)Ljava/lang/Double;");
+
+impl CompletionValue for String {
+    fn into_java<'env>(
+        self,
+        env: &'env JNIEnv,
+    ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+        unsafe { Ok(Some(LocalObject::from_ref(env.new_string(self)))) }
+    }
+}
+
+impl<T: ObjectType + Send> CompletionValue for GlobalObject<'static, T> {
+    fn into_java<'env>(
+        self,
+        env: &'env JNIEnv,
+    ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+        unsafe { Ok(env.new_local_ref(&*self).map(|v| LocalObject::from_ref(v))) }
+    }
+}
+
+impl<T: CompletionValue> CompletionValue for Option<T> {
+    fn into_java<'env>(
+        self,
+        env: &'env JNIEnv,
+    ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+        match self {
+            Some(v) => v.into_java(env),
+            None => Ok(None),
+        }
+    }
+}
+
+/// An error that can complete a Java `CompletableFuture` exceptionally.
+///
+/// Supported Types:
+/// - `GlobalObject<'static, JavaThrowable>`: completes with the throwable
+/// - `String`, `&'static str`: completes with `java.lang.RuntimeException` with the message
+pub trait CompletionError: Send + 'static {
+    /// Converts the error to a Java throwable.
+    fn into_java<'env>(self, env: &'env JNIEnv) -> LocalObject<'env, JavaThrowable>;
+}
+
+impl CompletionError for GlobalObject<'static, JavaThrowable> {
+    fn into_java<'env>(self, env: &'env JNIEnv) -> LocalObject<'env, JavaThrowable> {
+        unsafe { LocalObject::from_ref(env.new_local_ref(&*self).expect("BROKEN: create new local reference failed")) }
+    }
+}
+
+impl CompletionError for String {
+    fn into_java<'env>(self, env: &'env JNIEnv) -> LocalObject<'env, JavaThrowable> {
+        throwable::helper::new_named_exception(env, c"java/lang/RuntimeException", &self)
+    }
+}
+
+impl CompletionError for &'static str {
+    fn into_java<'env>(self, env: &'env JNIEnv) -> LocalObject<'env, JavaThrowable> {
+        throwable::helper::new_named_exception(env, c"java/lang/RuntimeException", self)
+    }
+}
+
+const IDLE: u8 = 0;
+const POLLING: u8 = 1;
+const NOTIFIED: u8 = 2;
+const DONE: u8 = 3;
+
+/// A task that polls the future on the thread that wakes it.
+struct Task<F> {
+    state: AtomicU8,
+    future: Mutex<Option<Pin<Box<F>>>>,
+    vm: &'static JavaVM,
+    /// Taken when the task completes.
+    target: Mutex<Option<GlobalRef<'static>>>,
+}
+
+impl<F, T, E> Task<F>
+where
+    F: Future<Output = Result<T, E>> + Send + 'static,
+    T: CompletionValue,
+    E: CompletionError,
+{
+    fn schedule(self: &Arc<Self>) {
+        loop {
+            match self.state.load(Ordering::Acquire) {
+                IDLE => {
+                    if self
+                        .state
+                        .compare_exchange(IDLE, POLLING, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        return self.run();
+                    }
+                }
+                POLLING => {
+                    if self
+                        .state
+                        .compare_exchange(POLLING, NOTIFIED, Ordering::AcqRel, Ordering::Acquire)
+                        .is_ok()
+                    {
+                        return;
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn run(self: &Arc<Self>) {
+        let waker = Waker::from(self.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            let mut future = self.future.lock().unwrap_or_else(PoisonError::into_inner);
+
+            let Some(pinned) = future.as_mut() else {
+                return;
+            };
+
+            let output = match std::panic::catch_unwind(AssertUnwindSafe(|| pinned.as_mut().poll(&mut cx))) {
+                Ok(Poll::Pending) => None,
+                Ok(Poll::Ready(output)) => Some(Ok(output)),
+                Err(panic) => Some(Err(panic)),
+            };
+
+            if let Some(output) = output {
+                *future = None;
+                drop(future);
+
+                self.state.store(DONE, Ordering::Release);
+
+                return self.complete(output);
+            }
+
+            drop(future);
+
+            if self
+                .state
+                .compare_exchange(POLLING, IDLE, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return;
+            }
+
+            // woken while polling, poll again
+            self.state.store(POLLING, Ordering::Release);
+        }
+    }
+
+    fn complete(&self, output: Result<Result<T, E>, Box<dyn core::any::Any + Send>>) {
+        let Some(target) = self.target.lock().unwrap_or_else(PoisonError::into_inner).take() else {
+            return;
+        };
+        let mut output = Some(output);
+
+        let attached = self.vm.with_attached_thread(true, |env| unsafe {
+            let ret = match output.take().expect("BROKEN: output taken twice") {
+                Ok(Ok(value)) => value.into_java(env),
+                Ok(Err(err)) => Err(err.into_java(env)),
+                Err(panic) => Err(callback::new_panic_error(env, panic)),
+            };
+
+            let c_future = env.get_object_class(&target);
+
+            // nothing can be done if the completion itself failed
+            let _ = match ret {
+                Ok(value) => resolver::resolve_method::<false, _>(env, &c_future, c"complete", c"(Ljava/lang/Object;)Z")
+                    .and_then(|method| {
+                        env.call_boolean_method(&target, method, [Arg::Object(value.as_ref().map(|v| &**v as _))])
+                            .map_err(|err| LocalObject::<JavaThrowable>::from_ref(err))
+                    }),
+                Err(ex) => {
+                    resolver::resolve_method::<false, _>(env, &c_future, c"completeExceptionally", c"(Ljava/lang/Throwable;)Z")
+                        .and_then(|method| {
+                            env.call_boolean_method(&target, method, [Arg::Object(Some(&*ex))])
+                                .map_err(|err| LocalObject::<JavaThrowable>::from_ref(err))
+                        })
+                }
+            };
+        });
+
+        if attached.is_err() {
+            // global references in the output can not be deleted without an attached thread, leak them with the target
+            core::mem::forget(output);
+            core::mem::forget(target);
+
+            std::eprintln!("[typed-jni] attach thread to javavm failed, CompletableFuture is left incomplete");
+        }
+    }
+}
+
+impl<F, T, E> Wake for Task<F>
+where
+    F: Future<Output = Result<T, E>> + Send + 'static,
+    T: CompletionValue,
+    E: CompletionError,
+{
+    fn wake(self: Arc<Self>) {
+        self.schedule();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.schedule();
+    }
+}
+
+type JavaFutureOutput = Result<Option<GlobalObject<'static, JavaObject>>, GlobalObject<'static, JavaThrowable>>;
+
+#[derive(Default)]
+struct Completion {
+    output: Option<JavaFutureOutput>,
+    waker: Option<Waker>,
+}
+
+/// A Rust [`Future`] that resolves when a Java `CompletableFuture` completes.
+///
+/// Resolves to `Ok(value)` if the `CompletableFuture` completed normally, or `Err(throwable)` otherwise.
+pub struct JavaFuture {
+    completion: Arc<Mutex<Completion>>,
+}
+
+impl Future for JavaFuture {
+    type Output = JavaFutureOutput;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut completion = self.completion.lock().unwrap_or_else(PoisonError::into_inner);
+
+        match completion.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                if !completion.waker.as_ref().is_some_and(|w| w.will_wake(cx.waker())) {
+                    completion.waker = Some(cx.waker().clone());
+                }
+
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Extension methods for bridging Rust futures and Java `CompletableFuture`.
+pub trait TypedFutureExt {
+    /// Creates a Java `CompletableFuture` that completes with the output of the Rust future.
+    ///
+    /// The future is polled on the thread that wakes it, and the `CompletableFuture` is completed
+    /// from the thread the future finishes on, attaching it to the Java VM if needed.
+    /// If the thread cannot be attached, the `CompletableFuture` is left incomplete and the output is leaked.
+    fn typed_new_completable_future<F, T, E>(
+        &self,
+        future: F,
+    ) -> Result<LocalObject<'_, JavaCompletableFuture>, LocalObject<'_, JavaThrowable>>
+    where
+        F: Future<Output = Result<T, E>> + Send + 'static,
+        T: CompletionValue,
+        E: CompletionError;
+
+    /// Creates a Rust [`Future`] that resolves when the Java `CompletableFuture` completes.
+    fn typed_into_future<R: StrongRef>(
+        &self,
+        future: &Object<R, JavaCompletableFuture>,
+    ) -> Result<JavaFuture, LocalObject<'_, JavaThrowable>>;
+}
+
+impl<'vm> TypedFutureExt for JNIEnv<'vm> {
+    fn typed_new_completable_future<F, T, E>(
+        &self,
+        future: F,
+    ) -> Result<LocalObject<'_, JavaCompletableFuture>, LocalObject<'_, JavaThrowable>>
+    where
+        F: Future<Output = Result<T, E>> + Send + 'static,
+        T: CompletionValue,
+        E: CompletionError,
+    {
+        let c_future = self.typed_find_class::<JavaCompletableFuture>()?;
+        let o_future = self.typed_new_object(&c_future, ())?;
+
+        unsafe {
+            // the task may outlive this env, the Java VM is never destroyed before the process exits.
+            let static_env: &JNIEnv<'static> = JNIEnv::from_raw(self.as_raw_ptr());
+
+            let task = Arc::new(Task {
+                state: AtomicU8::new(IDLE),
+                future: Mutex::new(Some(Box::pin(future))),
+                vm: JavaVM::from_raw(self.vm().as_raw_ptr()),
+                target: Mutex::new(Some(
+                    static_env
+                        .new_global_ref(&*o_future)
+                        .expect("BROKEN: create new global reference failed"),
+                )),
+            });
+
+            task.schedule();
+        }
+
+        Ok(o_future)
+    }
+
+    fn typed_into_future<R: StrongRef>(
+        &self,
+        future: &Object<R, JavaCompletableFuture>,
+    ) -> Result<JavaFuture, LocalObject<'_, JavaThrowable>> {
+        let completion = Arc::new(Mutex::new(Completion::default()));
+
+        let o_callback = callback::new_native_callback(self, callback::CallbackKind::BiConsumer, {
+            let completion = completion.clone();
+
+            Box::new(move |env, value, ex| unsafe {
+                let output = match ex {
+                    Some(ex) => Err(GlobalObject::from_ref(
+                        env.new_global_ref(&ex).expect("BROKEN: create new global reference failed"),
+                    )),
+                    None => Ok(value.map(|v| {
+                        GlobalObject::from_ref(env.new_global_ref(&v).expect("BROKEN: create new global reference failed"))
+                    })),
+                };
+
+                let waker = {
+                    let mut completion = completion.lock().unwrap_or_else(PoisonError::into_inner);
+
+                    completion.output = Some(output);
+                    completion.waker.take()
+                };
+
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+
+                Ok(None)
+            })
+        })?;
+
+        unsafe {
+            let (_, method) = resolver::resolve_class_and_method::<false>(
+                self,
+                c"java/util/concurrent/CompletableFuture",
+                c"whenComplete",
+                c"(Ljava/util/function/BiConsumer;)Ljava/util/concurrent/CompletableFuture;",
+            )?;
+
+            self.call_object_method(&**future, method, [Arg::Object(Some(&o_callback))])
+                .map_err(|err| LocalObject::from_ref(err))?;
+        }
+
+        Ok(JavaFuture { completion })
+    }
+}
@@ -0,0 +1,291 @@
+use alloc::boxed::Box;
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicIsize, Ordering},
+};
+
+use typed_jni_core::{JNIEnv, StrongRef};
+
+use crate::{LocalObject, Object, ObjectType, TypedFieldAccessExt, builtin::JavaThrowable, throwable};
+
+const EXCLUSIVE: isize = -1;
+
+struct Slot<T> {
+    // 0: not borrowed, > 0: shared borrows, EXCLUSIVE: exclusive borrow
+    state: AtomicIsize,
+    // whether the slot is freed by a cleaner instead of `NativeHandle::take`
+    cleaned: bool,
+    value: UnsafeCell<Option<T>>,
+}
+
+/// A Rust value owned by a Java object through a `long` field.
+///
+/// The field holds a pointer to the value, `0` means no value. All accesses to the field
+/// hold the monitor of the object, and borrows are checked at runtime like `RefCell`,
+/// which fail with `IllegalStateException` instead of blocking.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use typed_jni::{LocalObject, NativeHandle, core::JNIEnv, define_java_class};
+///
+/// define_java_class!(JavaPeer, "org.example.Peer"); // with a `private long nativeHandle` field
+///
+/// static HANDLE: NativeHandle<Vec<u8>> = unsafe { NativeHandle::new("nativeHandle") };
+///
+/// fn append<'env>(env: &'env JNIEnv, peer: &LocalObject<'env, JavaPeer>, data: &[u8]) {
+///     HANDLE.borrow_mut(env, peer).unwrap().extend_from_slice(data);
+/// }
+/// ```
+pub struct NativeHandle<T> {
+    field: &'static str,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> NativeHandle<T> {
+    /// Creates a handle stored in the `long` field named `field`.
+    ///
+    /// # Safety
+    ///
+    /// The value of the field is treated as a pointer to `T`, so the field must be private to this handle:
+    /// it must not be written by Java code, and must be written only through handles with the same `T`.
+    pub const unsafe fn new(field: &'static str) -> Self {
+        Self {
+            field,
+            _value: PhantomData,
+        }
+    }
+
+    /// Returns the name of the field.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    fn get_slot<'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, C>,
+    ) -> Result<*mut Slot<T>, LocalObject<'env, JavaThrowable>> {
+        let ptr: i64 = env.typed_get_field(obj, self.field)?;
+
+        Ok(ptr as usize as *mut Slot<T>)
+    }
+
+    fn put<'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, C>,
+        value: T,
+        cleaned: bool,
+    ) -> Result<*mut Slot<T>, LocalObject<'env, JavaThrowable>> {
+        let _guard = env.monitor_enter(&**obj);
+
+        if !self.get_slot(env, obj)?.is_null() {
+            return Err(self.new_error(env, "is already set"));
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            state: AtomicIsize::new(0),
+            cleaned,
+            value: UnsafeCell::new(Some(value)),
+        }));
+
+        if let Err(err) = env.typed_set_field(obj, self.field, slot as usize as i64) {
+            unsafe { drop(Box::from_raw(slot)) };
+
+            return Err(err);
+        }
+
+        Ok(slot)
+    }
+
+    /// Stores `value` in the field of `obj`.
+    ///
+    /// The value is leaked if it is not taken back by [`NativeHandle::take`],
+    /// use [`NativeHandle::store_with_cleaner`] to drop it with the object.
+    ///
+    /// Returns `Err(IllegalStateException)` if the field already holds a value.
+    pub fn store<'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, C>,
+        value: T,
+    ) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        self.put(env, obj, value, false).map(|_| ())
+    }
+
+    /// Stores `value` in the field of `obj`, and drops it once `obj` becomes unreachable
+    /// if it is not taken back by [`NativeHandle::take`].
+    ///
+    /// The value is dropped on the cleaner thread of the Java VM.
+    ///
+    /// Returns `Err(IllegalStateException)` if the field already holds a value.
+    #[cfg(feature = "std")]
+    pub fn store_with_cleaner<'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, C>,
+        value: T,
+    ) -> Result<(), LocalObject<'env, JavaThrowable>> {
+        // holds the monitor until the cleaner is registered, the slot is freed if the registration fails
+        let _guard = env.monitor_enter(&**obj);
+
+        let slot = self.put(env, obj, value, true)? as usize;
+
+        crate::callback::register_cleaner(
+            env,
+            &**obj,
+            Box::new(move || unsafe { drop(Box::from_raw(slot as *mut Slot<T>)) }),
+        )
+        .inspect_err(|_| {
+            let _ = env.typed_set_field(obj, self.field, 0i64);
+        })
+    }
+
+    /// Borrows the value in the field of `obj` immutably.
+    ///
+    /// Returns `Err(IllegalStateException)` if the field holds no value or the value is borrowed mutably.
+    pub fn borrow<'a, 'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &'a Object<R, C>,
+    ) -> Result<HandleRef<'a, T>, LocalObject<'env, JavaThrowable>> {
+        let _guard = env.monitor_enter(&**obj);
+
+        let slot = unsafe { self.get_slot(env, obj)?.as_ref() }.ok_or_else(|| self.new_error(env, "is not set"))?;
+
+        if slot
+            .state
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+                (state != EXCLUSIVE).then_some(state + 1)
+            })
+            .is_err()
+        {
+            return Err(self.new_error(env, "is already mutably borrowed"));
+        }
+
+        Ok(HandleRef { slot, _obj: PhantomData })
+    }
+
+    /// Borrows the value in the field of `obj` mutably.
+    ///
+    /// Returns `Err(IllegalStateException)` if the field holds no value or the value is borrowed.
+    pub fn borrow_mut<'a, 'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &'a Object<R, C>,
+    ) -> Result<HandleRefMut<'a, T>, LocalObject<'env, JavaThrowable>> {
+        let _guard = env.monitor_enter(&**obj);
+
+        let slot = unsafe { self.get_slot(env, obj)?.as_ref() }.ok_or_else(|| self.new_error(env, "is not set"))?;
+
+        if slot
+            .state
+            .compare_exchange(0, EXCLUSIVE, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(self.new_error(env, "is already borrowed"));
+        }
+
+        Ok(HandleRefMut { slot, _obj: PhantomData })
+    }
+
+    /// Takes the value back from the field of `obj`, leaving the field `0`.
+    ///
+    /// Returns `Err(IllegalStateException)` if the field holds no value or the value is borrowed.
+    pub fn take<'env, R: StrongRef, C: ObjectType>(
+        &self,
+        env: &'env JNIEnv,
+        obj: &Object<R, C>,
+    ) -> Result<T, LocalObject<'env, JavaThrowable>> {
+        let _guard = env.monitor_enter(&**obj);
+
+        let ptr = self.get_slot(env, obj)?;
+        let slot = unsafe { ptr.as_ref() }.ok_or_else(|| self.new_error(env, "is not set"))?;
+
+        if slot.state.load(Ordering::Acquire) != 0 {
+            return Err(self.new_error(env, "is already borrowed"));
+        }
+
+        env.typed_set_field(obj, self.field, 0i64)?;
+
+        unsafe {
+            let value = (*slot.value.get()).take().expect("BROKEN: native handle slot is empty");
+
+            // a cleaned slot is freed by the cleaner once the object becomes unreachable
+            if !slot.cleaned {
+                drop(Box::from_raw(ptr));
+            }
+
+            Ok(value)
+        }
+    }
+
+    fn new_error<'env>(&self, env: &'env JNIEnv, reason: &str) -> LocalObject<'env, JavaThrowable> {
+        throwable::helper::new_named_exception(
+            env,
+            c"java/lang/IllegalStateException",
+            &alloc::format!("native handle {} {reason}", self.field),
+        )
+    }
+}
+
+/// An immutable borrow of a value stored by [`NativeHandle`].
+pub struct HandleRef<'a, T> {
+    slot: &'a Slot<T>,
+    _obj: PhantomData<&'a T>,
+}
+
+impl<T> Deref for HandleRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {
+            (*self.slot.value.get())
+                .as_ref()
+                .expect("BROKEN: native handle slot is empty")
+        }
+    }
+}
+
+impl<T> Drop for HandleRef<'_, T> {
+    fn drop(&mut self) {
+        self.slot.state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+/// A mutable borrow of a value stored by [`NativeHandle`].
+pub struct HandleRefMut<'a, T> {
+    slot: &'a Slot<T>,
+    _obj: PhantomData<&'a mut T>,
+}
+
+impl<T> Deref for HandleRefMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {
+            (*self.slot.value.get())
+                .as_ref()
+                .expect("BROKEN: native handle slot is empty")
+        }
+    }
+}
+
+impl<T> DerefMut for HandleRefMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe {
+            (*self.slot.value.get())
+                .as_mut()
+                .expect("BROKEN: native handle slot is empty")
+        }
+    }
+}
+
+impl<T> Drop for HandleRefMut<'_, T> {
+    fn drop(&mut self) {
+        self.slot.state.store(0, Ordering::Release);
+    }
+}
@@ -0,0 +1,278 @@
+use alloc::{boxed::Box, format, string::ToString, vec};
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    sync::{Mutex, PoisonError},
+};
+
+use typed_jni_core::{JNIEnv, StrongRef, TrampolineRef};
+
+use crate::{
+    Array, LocalObject, Object, TrampolineObject, TypedCallExt, TypedObjectExt, TypedPrimitiveArrayExt, TypedRef,
+    builtin::{JavaInputStream, JavaOutputStream, JavaThrowable},
+    callback::{self, StreamKind},
+    throwable,
+};
+
+/// Bytes copied across the boundary in a single call at most.
+const BUFFER_LEN: usize = 8192;
+
+/// A [`Read`] implementation over a `java.io.InputStream`.
+///
+/// Bytes are read through a reusable Java byte array of 8 KiB, exceptions thrown by the stream are
+/// converted to [`io::Error`]s with their descriptions.
+///
+/// # Example
+///
+/// ```rust
+/// use std::io::Read;
+///
+/// use typed_jni::{JavaStreamReader, LocalObject, builtin::JavaInputStream, core::JNIEnv};
+///
+/// fn read_all(env: &JNIEnv, stream: LocalObject<JavaInputStream>) -> Vec<u8> {
+///     let mut data = Vec::new();
+///
+///     JavaStreamReader::new(env, stream).unwrap().read_to_end(&mut data).unwrap();
+///
+///     data
+/// }
+/// ```
+pub struct JavaStreamReader<'env, R: StrongRef> {
+    env: &'env JNIEnv<'env>,
+    stream: Object<R, JavaInputStream>,
+    buffer: LocalObject<'env, Array<i8>>,
+}
+
+impl<'env, R: StrongRef> JavaStreamReader<'env, R> {
+    /// Creates a reader over `stream`.
+    pub fn new(env: &'env JNIEnv, stream: Object<R, JavaInputStream>) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        let buffer = env.typed_new_primitive_array(BUFFER_LEN as i32)?;
+
+        Ok(Self { env, stream, buffer })
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> Object<R, JavaInputStream> {
+        self.stream
+    }
+}
+
+impl<'env, R: StrongRef> Read for JavaStreamReader<'env, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let len = buf.len().min(BUFFER_LEN);
+
+        let n: i32 = self
+            .env
+            .typed_call_method(&self.stream, "read", (&self.buffer, 0i32, len as i32))
+            .map_err(|err| to_io_error(self.env, err))?;
+        if n < 0 {
+            return Ok(0);
+        }
+        if n as usize > len {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("stream read {n} bytes into a buffer of {len} bytes"),
+            ));
+        }
+
+        self.env
+            .typed_get_bytes_array_region(&self.buffer, 0, &mut buf[..n as usize])
+            .map_err(|err| to_io_error(self.env, err))?;
+
+        Ok(n as usize)
+    }
+}
+
+/// A [`Write`] implementation over a `java.io.OutputStream`.
+///
+/// Bytes are written through a reusable Java byte array of 8 KiB, exceptions thrown by the stream are
+/// converted to [`io::Error`]s with their descriptions.
+pub struct JavaStreamWriter<'env, R: StrongRef> {
+    env: &'env JNIEnv<'env>,
+    stream: Object<R, JavaOutputStream>,
+    buffer: LocalObject<'env, Array<i8>>,
+}
+
+impl<'env, R: StrongRef> JavaStreamWriter<'env, R> {
+    /// Creates a writer over `stream`.
+    pub fn new(env: &'env JNIEnv, stream: Object<R, JavaOutputStream>) -> Result<Self, LocalObject<'env, JavaThrowable>> {
+        let buffer = env.typed_new_primitive_array(BUFFER_LEN as i32)?;
+
+        Ok(Self { env, stream, buffer })
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> Object<R, JavaOutputStream> {
+        self.stream
+    }
+}
+
+impl<'env, R: StrongRef> Write for JavaStreamWriter<'env, R> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(BUFFER_LEN);
+        if len == 0 {
+            return Ok(0);
+        }
+
+        self.env
+            .typed_set_bytes_array_region(&self.buffer, 0, &buf[..len])
+            .map_err(|err| to_io_error(self.env, err))?;
+
+        self.env
+            .typed_call_method::<(), _, _>(&self.stream, "write", (&self.buffer, 0i32, len as i32))
+            .map_err(|err| to_io_error(self.env, err))?;
+
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.env
+            .typed_call_method::<(), _, _>(&self.stream, "flush", ())
+            .map_err(|err| to_io_error(self.env, err))
+    }
+}
+
+/// Converts Rust readers and writers into Java streams.
+///
+/// The streams are instances of helper classes embedded in this crate, see [`TypedFunctionExt`](crate::TypedFunctionExt)
+/// for how they are defined and released. Errors are thrown as `java.io.IOException` with their descriptions.
+pub trait TypedStreamExt {
+    /// Creates a `java.io.InputStream` reading from `reader`, `close()` drops `reader`.
+    fn typed_new_input_stream<R>(&self, reader: R) -> Result<LocalObject<'_, JavaInputStream>, LocalObject<'_, JavaThrowable>>
+    where
+        R: Read + Send + 'static;
+
+    /// Creates a `java.io.OutputStream` writing to `writer`, `close()` flushes and drops `writer`.
+    fn typed_new_output_stream<W>(&self, writer: W) -> Result<LocalObject<'_, JavaOutputStream>, LocalObject<'_, JavaThrowable>>
+    where
+        W: Write + Send + 'static;
+}
+
+impl<'vm> TypedStreamExt for JNIEnv<'vm> {
+    fn typed_new_input_stream<R>(&self, reader: R) -> Result<LocalObject<'_, JavaInputStream>, LocalObject<'_, JavaThrowable>>
+    where
+        R: Read + Send + 'static,
+    {
+        let reader = Mutex::new(Some(reader));
+
+        let stream = callback::new_native_stream(
+            self,
+            StreamKind::Input,
+            Box::new(move |env, op, array, offset, len| {
+                let mut reader = reader.lock().unwrap_or_else(PoisonError::into_inner);
+
+                let ret = match op {
+                    callback::STREAM_READ => (|| {
+                        let reader = reader.as_mut().ok_or_else(|| closed(env))?;
+                        let array = array_of(array);
+
+                        let mut buf = vec![0u8; (len as usize).min(BUFFER_LEN)];
+                        let n = loop {
+                            match reader.read(&mut buf) {
+                                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                                ret => break ret.map_err(|err| to_io_exception(env, err))?,
+                            }
+                        };
+
+                        if n == 0 {
+                            return Ok(-1);
+                        }
+
+                        env.typed_set_bytes_array_region(&array, offset, &buf[..n])?;
+
+                        Ok(n as i32)
+                    })(),
+                    callback::STREAM_CLOSE => {
+                        drop(reader.take());
+
+                        Ok(0)
+                    }
+                    op => Err(unsupported(env, op)),
+                };
+
+                ret.map_err(|err| err.into_ref())
+            }),
+        )?;
+
+        unsafe { Ok(LocalObject::from_ref(stream)) }
+    }
+
+    fn typed_new_output_stream<W>(&self, writer: W) -> Result<LocalObject<'_, JavaOutputStream>, LocalObject<'_, JavaThrowable>>
+    where
+        W: Write + Send + 'static,
+    {
+        let writer = Mutex::new(Some(writer));
+
+        let stream = callback::new_native_stream(
+            self,
+            StreamKind::Output,
+            Box::new(move |env, op, array, offset, len| {
+                let mut writer = writer.lock().unwrap_or_else(PoisonError::into_inner);
+
+                let ret = match op {
+                    callback::STREAM_WRITE => (|| {
+                        let writer = writer.as_mut().ok_or_else(|| closed(env))?;
+                        let array = array_of(array);
+                        let len = len as usize;
+
+                        let mut buf = vec![0u8; len.min(BUFFER_LEN)];
+                        for chunk in (0..len).step_by(BUFFER_LEN) {
+                            let buf = &mut buf[..(len - chunk).min(BUFFER_LEN)];
+
+                            env.typed_get_bytes_array_region(&array, offset + chunk as i32, buf)?;
+                            writer.write_all(buf).map_err(|err| to_io_exception(env, err))?;
+                        }
+
+                        Ok(0)
+                    })(),
+                    callback::STREAM_FLUSH => writer
+                        .as_mut()
+                        .ok_or_else(|| closed(env))
+                        .and_then(|writer| writer.flush().map_err(|err| to_io_exception(env, err)))
+                        .map(|_| 0),
+                    callback::STREAM_CLOSE => match writer.take() {
+                        Some(mut writer) => writer.flush().map_err(|err| to_io_exception(env, err)).map(|_| 0),
+                        None => Ok(0),
+                    },
+                    op => Err(unsupported(env, op)),
+                };
+
+                ret.map_err(|err| err.into_ref())
+            }),
+        )?;
+
+        unsafe { Ok(LocalObject::from_ref(stream)) }
+    }
+}
+
+fn to_io_error(env: &JNIEnv, err: LocalObject<JavaThrowable>) -> io::Error {
+    let description = env
+        .typed_to_string(&err)
+        .unwrap_or_else(|_| "java exception thrown".to_string());
+
+    io::Error::other(description)
+}
+
+fn to_io_exception<'env>(env: &'env JNIEnv, err: io::Error) -> LocalObject<'env, JavaThrowable> {
+    throwable::helper::new_named_exception(env, c"java/io/IOException", &err.to_string())
+}
+
+fn closed<'env>(env: &'env JNIEnv) -> LocalObject<'env, JavaThrowable> {
+    throwable::helper::new_named_exception(env, c"java/io/IOException", "stream closed")
+}
+
+fn unsupported<'env>(env: &'env JNIEnv, op: i32) -> LocalObject<'env, JavaThrowable> {
+    throwable::helper::new_named_exception(
+        env,
+        c"java/lang/UnsupportedOperationException",
+        &format!("unsupported stream operation {op}"),
+    )
+}
+
+/// Takes the `byte[]` argument of `read` and `write`, which is checked to be non-null by the stream.
+fn array_of(array: Option<TrampolineRef>) -> TrampolineObject<Array<i8>> {
+    unsafe { TrampolineObject::from_ref(array.expect("BROKEN: stream array is null")) }
+}
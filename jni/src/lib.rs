@@ -8,6 +8,10 @@
 //!
 //! * `std` - Enables the use standard library. (default)
 //! * `cache` - Enables the use cache for class and member lookups. (default, requires `std`)
+//! * `track-local-refs` - Enables detection of local reference overflows and leaks, intended for debug builds. (requires `std`)
+//! * `tracing` - Enables [`tracing`](https://docs.rs/tracing) spans for typed method calls, field accesses and member lookups, recording class, member name and signature, and whether lookups are served from the cache.
+//! * `testing` - Enables `typed_jni::core::testing`, an in-process fake JNI environment for unit tests without a Java VM and fault injection for JNI calls. (requires `std`)
+//! * `verify-members` - Describes failed method and field lookups with the actual members of the class listed by reflection, spelling out the expected signature and the closest candidates. (always enabled in debug builds)
 //!
 //! ## Getting Started
 //!
@@ -151,97 +155,39 @@ extern crate alloc;
 mod array;
 pub mod builtin;
 mod call;
+#[cfg(feature = "std")]
+mod callback;
 mod class;
+mod dynamic;
 mod field;
+mod frame;
+#[cfg(feature = "std")]
+mod function;
+#[cfg(feature = "std")]
+mod future;
+mod handle;
+#[cfg(feature = "std")]
+mod io;
+mod monitor;
 mod object;
+#[cfg(feature = "std")]
+mod proxy;
 mod reference;
 mod resolver;
+mod signature;
 mod string;
 mod throwable;
 
-use ::core::{
-    fmt::{Display, Formatter, Write},
-    marker::PhantomData,
-    ops::Deref,
-};
+use ::core::{marker::PhantomData, ops::Deref};
 pub use typed_jni_core as core;
 use typed_jni_core::{GlobalRef, LocalRef, Ref, TrampolineRef, WeakGlobalRef};
 
-pub use self::{array::*, call::*, class::*, field::*, object::*, reference::*, string::*, throwable::*};
-
-/// A signature of a JNI type.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub enum Signature {
-    Void,
-    Boolean,
-    Byte,
-    Char,
-    Short,
-    Int,
-    Long,
-    Float,
-    Double,
-    Object(&'static str),
-    Array(&'static Signature),
-}
-
-impl Signature {
-    pub const fn size_hint(&self) -> usize {
-        match self {
-            Signature::Void
-            | Signature::Boolean
-            | Signature::Byte
-            | Signature::Char
-            | Signature::Short
-            | Signature::Int
-            | Signature::Long
-            | Signature::Float
-            | Signature::Double => 1, // single char
-            Signature::Object(s) => s.len() + 2,              // L<name>;
-            Signature::Array(inner) => inner.size_hint() + 1, // [<inner>
-        }
-    }
-
-    pub fn write_to<W: Write>(&self, w: &mut W) -> ::core::fmt::Result {
-        match self {
-            Signature::Void => w.write_str("V"),
-            Signature::Boolean => w.write_str("Z"),
-            Signature::Byte => w.write_str("B"),
-            Signature::Char => w.write_str("C"),
-            Signature::Short => w.write_str("S"),
-            Signature::Int => w.write_str("I"),
-            Signature::Long => w.write_str("J"),
-            Signature::Float => w.write_str("F"),
-            Signature::Double => w.write_str("D"),
-            Signature::Object(name) => {
-                w.write_str("L")?;
-                w.write_str(name)?;
-                w.write_str(";")
-            }
-            Signature::Array(inner) => {
-                w.write_str("[")?;
-                inner.write_to(w)
-            }
-        }
-    }
-
-    pub fn write_as_class_name_to<W: Write>(&self, w: &mut W) -> ::core::fmt::Result {
-        match self {
-            Signature::Object(name) => w.write_str(name),
-            Signature::Array(inner) => {
-                w.write_str("[")?;
-                inner.write_to(w)
-            }
-            _ => self.write_to(w),
-        }
-    }
-}
-
-impl Display for Signature {
-    fn fmt(&self, f: &mut Formatter<'_>) -> alloc::fmt::Result {
-        self.write_to(f)
-    }
-}
+pub use self::{
+    array::*, call::*, class::*, dynamic::*, field::*, frame::*, handle::*, monitor::*, object::*, reference::*, signature::*,
+    string::*, throwable::*,
+};
+#[cfg(feature = "std")]
+pub use self::{callback::TypedHelperClassesExt, function::*, future::*, io::*, proxy::*};
 
 /// A Java type.
 pub trait Type {
@@ -0,0 +1,101 @@
+use core::{ffi::CStr, time::Duration};
+
+use typed_jni_core::{Arg, JNIEnv, MonitorGuard, StrongRef};
+
+use crate::{LocalObject, Object, ObjectType, TypedRef, builtin::JavaThrowable, resolver};
+
+/// A guard that releases the monitor of the typed object when dropped.
+///
+/// Failures of releasing the monitor are ignored when dropped, use [`TypedMonitorGuard::exit`] to handle them.
+pub struct TypedMonitorGuard<'a, 'vm, R: StrongRef + 'a, T: ObjectType> {
+    obj: &'a Object<R, T>,
+    guard: MonitorGuard<'a, 'vm, R>,
+}
+
+impl<'a, 'vm, R: StrongRef + 'a, T: ObjectType> TypedMonitorGuard<'a, 'vm, R, T> {
+    /// Returns the object whose monitor is held.
+    pub fn object(&self) -> &'a Object<R, T> {
+        self.obj
+    }
+
+    fn call_object_method<const N_ARGS: usize>(
+        &self,
+        name: &CStr,
+        sig: &CStr,
+        args: [Arg<'_>; N_ARGS],
+    ) -> Result<(), LocalObject<'a, JavaThrowable>> {
+        let env = self.guard.env();
+
+        let (_, method) = resolver::resolve_class_and_method::<false>(env, c"java/lang/Object", name, sig)?;
+
+        unsafe {
+            env.call_void_method(&**self.obj, method, args)
+                .map_err(|err| LocalObject::from_ref(err))
+        }
+    }
+
+    /// Causes the current thread to wait until it is notified or interrupted, see `Object.wait()`.
+    ///
+    /// Returns `Err(InterruptedException)` if the current thread is interrupted.
+    pub fn wait(&self) -> Result<(), LocalObject<'a, JavaThrowable>> {
+        self.call_object_method(c"wait", c"()V", [])
+    }
+
+    /// Causes the current thread to wait until it is notified or interrupted,
+    /// or the timeout elapses, see `Object.wait(long, int)`.
+    ///
+    /// Unlike `Object.wait`, a zero `timeout` does not wait forever.
+    ///
+    /// Returns `Err(InterruptedException)` if the current thread is interrupted.
+    pub fn wait_timeout(&self, timeout: Duration) -> Result<(), LocalObject<'a, JavaThrowable>> {
+        let (millis, nanos) = if timeout.is_zero() {
+            (0, 1)
+        } else {
+            (
+                i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX),
+                (timeout.subsec_nanos() % 1_000_000) as i32,
+            )
+        };
+
+        self.call_object_method(c"wait", c"(JI)V", [Arg::Long(millis), Arg::Int(nanos)])
+    }
+
+    /// Wakes up a single thread waiting on the monitor, see `Object.notify()`.
+    pub fn notify(&self) -> Result<(), LocalObject<'a, JavaThrowable>> {
+        self.call_object_method(c"notify", c"()V", [])
+    }
+
+    /// Wakes up all threads waiting on the monitor, see `Object.notifyAll()`.
+    pub fn notify_all(&self) -> Result<(), LocalObject<'a, JavaThrowable>> {
+        self.call_object_method(c"notifyAll", c"()V", [])
+    }
+
+    /// Releases the monitor.
+    ///
+    /// Returns `Err(IllegalMonitorStateException)` if the current thread does not own the monitor.
+    pub fn exit(self) -> Result<(), LocalObject<'a, JavaThrowable>> {
+        self.guard.exit().map_err(|err| unsafe { LocalObject::from_ref(err) })
+    }
+}
+
+/// Extension methods for typed object monitors.
+pub trait TypedMonitorExt<'vm> {
+    /// Enter the monitor of the object, same as `synchronized (obj)` in Java.
+    #[must_use]
+    fn typed_monitor_enter<'a, R: StrongRef + 'a, T: ObjectType>(
+        &'a self,
+        obj: &'a Object<R, T>,
+    ) -> TypedMonitorGuard<'a, 'vm, R, T>;
+}
+
+impl<'vm> TypedMonitorExt<'vm> for JNIEnv<'vm> {
+    fn typed_monitor_enter<'a, R: StrongRef + 'a, T: ObjectType>(
+        &'a self,
+        obj: &'a Object<R, T>,
+    ) -> TypedMonitorGuard<'a, 'vm, R, T> {
+        TypedMonitorGuard {
+            obj,
+            guard: self.monitor_enter(&**obj),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use alloc::{boxed::Box, collections::BTreeMap, format, string::String, vec::Vec};
+
+use typed_jni_core::{Arg, JNIEnv, LocalRef, StrongRef, TrampolineRef};
+
+use crate::{
+    Class, LocalObject, Object, ObjectType, Signature, TypedClassExt, TypedRef,
+    builtin::{JavaClass, JavaClassLoader, JavaObject, JavaThrowable},
+    callback, resolver, throwable,
+};
+
+type ProxyMethod = dyn for<'env> Fn(
+        &'env JNIEnv<'static>,
+        &[Option<LocalObject<'env, JavaObject>>],
+    ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+    + Send
+    + Sync;
+
+/// A handler of method invocations on a proxy created by [`ProxyBuilder`].
+///
+/// Primitive arguments are boxed, and a primitive return value must be boxed as well.
+/// Checked exceptions not declared by the method are wrapped into `UndeclaredThrowableException`.
+pub trait ProxyHandler: Send + Sync + 'static {
+    /// Handles an invocation of method `name` with JNI `signature`, e.g. `onClick` with `(Landroid/view/View;)V`.
+    fn invoke<'env>(
+        &self,
+        env: &'env JNIEnv<'static>,
+        name: &str,
+        signature: &str,
+        args: &[Option<LocalObject<'env, JavaObject>>],
+    ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>;
+}
+
+/// A builder of Java objects implementing Java interfaces in Rust, backed by `java.lang.reflect.Proxy`.
+///
+/// Invocations are dispatched by method name and signature to closures added by [`ProxyBuilder::method`],
+/// then to the handler set by [`ProxyBuilder::handler`]. Unhandled methods throw `UnsupportedOperationException`,
+/// and `equals`, `hashCode` and `toString` use the identity of the proxy.
+///
+/// The proxy class is defined in the class loader set by [`ProxyBuilder::class_loader`], or the class loader
+/// of the first interface otherwise, all interfaces must be visible from that class loader.
+///
+/// The Rust state is released when the proxy is garbage collected.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use typed_jni::{LocalObject, ProxyBuilder, core::JNIEnv, define_java_class};
+///
+/// define_java_class!(JavaRunnable, "java.lang.Runnable");
+///
+/// fn new_runnable<'env>(env: &'env JNIEnv) -> LocalObject<'env, JavaRunnable> {
+///     ProxyBuilder::new()
+///         .method("run", "()V", |_, _| {
+///             println!("run from Java");
+///
+///             Ok(None)
+///         })
+///         .build(env)
+///         .unwrap()
+/// }
+/// ```
+#[derive(Default)]
+pub struct ProxyBuilder<'a> {
+    interfaces: Vec<(Signature, &'a dyn StrongRef)>,
+    class_loader: Option<&'a dyn StrongRef>,
+    methods: BTreeMap<String, Box<ProxyMethod>>,
+    handler: Option<Box<dyn ProxyHandler>>,
+}
+
+impl<'a> ProxyBuilder<'a> {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an interface to be implemented.
+    ///
+    /// The interface passed to [`ProxyBuilder::build`] is found with [`TypedClassExt::typed_find_class`]
+    /// if it is not added, use this to add interfaces from other class loaders.
+    pub fn interface<R: StrongRef, T: ObjectType>(mut self, cls: &'a Class<R, T>) -> Self {
+        self.interfaces.push((T::SIGNATURE, &**cls));
+        self
+    }
+
+    /// Sets the class loader to define the proxy class in.
+    ///
+    /// Required if the interfaces are not all visible from the class loader of the first one,
+    /// e.g. implementing `java.lang.Runnable` along with an interface from an application class loader.
+    pub fn class_loader<R: StrongRef>(mut self, loader: &'a Object<R, JavaClassLoader>) -> Self {
+        self.class_loader = Some(&**loader);
+        self
+    }
+
+    /// Handles method `name` with JNI `signature` with the given closure.
+    pub fn method<F>(mut self, name: &str, signature: &str, f: F) -> Self
+    where
+        F: for<'env> Fn(
+                &'env JNIEnv<'static>,
+                &[Option<LocalObject<'env, JavaObject>>],
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.methods.insert(format!("{name}{signature}"), Box::new(f));
+        self
+    }
+
+    /// Handles methods without closures with the given handler.
+    pub fn handler(mut self, handler: impl ProxyHandler) -> Self {
+        self.handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Creates a proxy implementing `I` and other added interfaces.
+    ///
+    /// `I` is the first interface unless it is added by [`ProxyBuilder::interface`].
+    pub fn build<'env, I: ObjectType>(self, env: &'env JNIEnv) -> Result<LocalObject<'env, I>, LocalObject<'env, JavaThrowable>> {
+        let Self {
+            interfaces,
+            class_loader,
+            methods,
+            handler,
+        } = self;
+
+        let mut classes: Vec<LocalRef<'env>> = Vec::with_capacity(interfaces.len() + 1);
+        if !interfaces.iter().any(|(sig, _)| *sig == I::SIGNATURE) {
+            classes.push(env.typed_find_class::<I>()?.into_ref());
+        }
+        for (_, cls) in interfaces {
+            classes.push(env.new_local_ref(cls).expect("BROKEN: create new local reference failed"));
+        }
+
+        let o_handler = callback::new_native_callback(
+            env,
+            callback::CallbackKind::InvocationHandler,
+            Box::new(move |env, descriptor, args| {
+                dispatch(env, &methods, handler.as_deref(), descriptor, args)
+                    .map(|ret| ret.map(|v| v.into_ref()))
+                    .map_err(|err| err.into_ref())
+            }),
+        )?;
+
+        unsafe {
+            let c_class = env.typed_find_class::<JavaClass>()?;
+            let a_classes = env
+                .new_object_array(&*c_class, classes.len() as i32)
+                .map_err(|err| LocalObject::from_ref(err))?;
+            for (idx, cls) in classes.iter().enumerate() {
+                env.set_object_array_element(&a_classes, idx as i32, Some(cls))
+                    .map_err(|err| LocalObject::from_ref(err))?;
+            }
+
+            let o_loader = match class_loader {
+                Some(loader) => Some(env.new_local_ref(loader).expect("BROKEN: create new local reference failed")),
+                None => {
+                    let m_get_class_loader =
+                        resolver::resolve_method::<false, _>(env, &*c_class, c"getClassLoader", c"()Ljava/lang/ClassLoader;")?;
+
+                    env.call_object_method(&classes[0], m_get_class_loader, [])
+                        .map_err(|err| LocalObject::from_ref(err))?
+                }
+            };
+
+            let (c_proxy, m_new_proxy_instance) = resolver::resolve_class_and_method::<true>(
+                env,
+                c"java/lang/reflect/Proxy",
+                c"newProxyInstance",
+                c"(Ljava/lang/ClassLoader;[Ljava/lang/Class;Ljava/lang/reflect/InvocationHandler;)Ljava/lang/Object;",
+            )?;
+
+            let o_proxy = env
+                .call_object_method(
+                    &c_proxy,
+                    m_new_proxy_instance,
+                    [
+                        Arg::Object(o_loader.as_ref().map(|v| v as _)),
+                        Arg::Object(Some(&a_classes)),
+                        Arg::Object(Some(&o_handler)),
+                    ],
+                )
+                .map_err(|err| LocalObject::from_ref(err))?
+                .expect("BROKEN: Proxy.newProxyInstance returns null");
+
+            Ok(LocalObject::from_ref(o_proxy))
+        }
+    }
+}
+
+fn dispatch<'env>(
+    env: &'env JNIEnv<'static>,
+    methods: &BTreeMap<String, Box<ProxyMethod>>,
+    handler: Option<&dyn ProxyHandler>,
+    descriptor: Option<TrampolineRef<'env>>,
+    args: Option<TrampolineRef<'env>>,
+) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+    unsafe {
+        let descriptor = env.get_string(&descriptor.expect("BROKEN: proxy method descriptor is null"));
+
+        let args = match args {
+            Some(args) => {
+                let len = env.get_array_length(&args).map_err(|err| LocalObject::from_ref(err))?;
+
+                (0..len)
+                    .map(|idx| {
+                        env.get_object_array_element(&args, idx)
+                            .map(|v| v.map(|v| LocalObject::from_ref(v)))
+                            .map_err(|err| LocalObject::from_ref(err))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            None => Vec::new(),
+        };
+
+        if let Some(method) = methods.get(&descriptor) {
+            return method(env, &args);
+        }
+
+        let (name, signature) = descriptor.split_at(descriptor.find('(').expect("BROKEN: invalid proxy method descriptor"));
+
+        match handler {
+            Some(handler) => handler.invoke(env, name, signature, &args),
+            None => Err(throwable::helper::new_named_exception(
+                env,
+                c"java/lang/UnsupportedOperationException",
+                &format!("{name}{signature} is not implemented"),
+            )),
+        }
+    }
+}
@@ -6,12 +6,18 @@ use std::{
     sync::Once,
 };
 
-use typed_jni_core::{AttachHook, FieldID, JNIEnv, JavaVM, LocalRef, MethodID, StrongRef, WeakGlobalRef, sys};
+use typed_jni_core::{FieldID, JNIEnv, JavaVM, LocalRef, MethodID, StrongRef, WeakGlobalRef, sys};
 use uluru::LRUCache;
 
+const CLASS_CAPACITY: usize = 8;
 const METHOD_WITH_CLASS_CAPACITY: usize = 8;
 const MEMBER_ONLY_CAPACITY: usize = 32;
 
+struct Class {
+    cls: WeakGlobalRef<'static>,
+    class: CString,
+}
+
 struct MethodWithClass {
     cls: WeakGlobalRef<'static>,
     method: sys::jmethodID,
@@ -29,6 +35,7 @@ struct Member<T> {
 
 #[derive(Default)]
 struct Cached {
+    classes: LRUCache<Class, CLASS_CAPACITY>,
     static_method_with_class: LRUCache<MethodWithClass, METHOD_WITH_CLASS_CAPACITY>,
     instance_method_with_class: LRUCache<MethodWithClass, METHOD_WITH_CLASS_CAPACITY>,
     static_methods: LRUCache<Member<sys::jmethodID>, MEMBER_ONLY_CAPACITY>,
@@ -57,6 +64,9 @@ impl Drop for AttachOnClean {
                 }
             };
 
+            for entry in c.classes.iter() {
+                handle_vm(entry.cls.vm());
+            }
             for c in [&c.static_method_with_class, &c.instance_method_with_class] {
                 for entry in c.iter() {
                     handle_vm(entry.cls.vm());
@@ -91,27 +101,49 @@ thread_local! {
 }
 
 fn setup_cache() {
-    static mut PREV_HOOK: Option<AttachHook> = None;
-
-    fn cleanup_cache_with_vm(vm: &JavaVM) {
-        unsafe {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        JavaVM::add_detach_hook(|_| {
             let cached = CACHED.try_with(|v| v.take());
             drop(cached);
+        })
+        .forget();
+    })
+}
 
-            if let Some(hook) = PREV_HOOK {
-                hook(vm)
-            }
-        }
-    }
+pub fn find_class<'env>(env: &'env JNIEnv, cls: &CStr) -> Option<LocalRef<'env>> {
+    CACHED.with_borrow_mut(|AttachOnClean(v)| {
+        let v = v.as_mut()?;
 
-    static ONCE: Once = Once::new();
-    ONCE.call_once(|| unsafe {
-        let prev = JavaVM::set_detach_hook(cleanup_cache_with_vm);
+        let vm = env.vm();
+        let entry = v
+            .classes
+            .find(|v| v.cls.vm().as_raw_ptr() == vm.as_raw_ptr() && v.class == cls)?;
 
-        PREV_HOOK = prev;
+        env.new_local_ref(&entry.cls)
     })
 }
 
+pub fn put_class<R: StrongRef>(env: &JNIEnv, class: &CStr, cls: &R) {
+    setup_cache();
+
+    CACHED.with_borrow_mut(|AttachOnClean(v)| {
+        let v = v.get_or_insert_default();
+
+        let cls = match env.new_weak_global_ref(cls) {
+            Some(cls) => cls,
+            None => return,
+        };
+
+        let entry = Class {
+            cls: unsafe { core::mem::transmute::<WeakGlobalRef<'_>, WeakGlobalRef<'static>>(cls) },
+            class: class.to_owned(),
+        };
+
+        v.classes.insert(entry);
+    });
+}
+
 pub fn find_class_and_method<'env, const STATIC: bool>(
     env: &'env JNIEnv,
     cls: &CStr,
@@ -3,11 +3,14 @@ use alloc::{
     ffi::CString,
     string::{String, ToString},
 };
-use core::{ffi::CStr, fmt, fmt::Write};
+use core::{ffi::CStr, fmt, fmt::Write, ops::Deref};
 
 use typed_jni_core::JNIEnv;
 
-use crate::{LocalObject, Signature, builtin::JavaThrowable, throwable};
+use crate::{Args, Descriptor, LocalObject, Signature, Type, builtin::JavaThrowable, throwable};
+
+/// Member names shorter than this are terminated on stack.
+const INLINE_NAME_CAPACITY: usize = 64;
 
 pub fn build_class_name<'env>(
     env: &'env JNIEnv,
@@ -55,18 +58,44 @@ pub enum MemberKind {
     Field,
 }
 
+/// A NUL-terminated member name, which is only allocated on heap if it is too long.
+pub enum MemberName<'s> {
+    Borrowed(&'s CStr),
+    Inline([u8; INLINE_NAME_CAPACITY]),
+    Owned(CString),
+}
+
+impl Deref for MemberName<'_> {
+    type Target = CStr;
+
+    fn deref(&self) -> &CStr {
+        match self {
+            MemberName::Borrowed(s) => s,
+            MemberName::Inline(buf) => CStr::from_bytes_until_nul(buf).expect("BROKEN: inline name is not terminated"),
+            MemberName::Owned(s) => s,
+        }
+    }
+}
+
 pub fn build_member_name<'env, 's>(
     env: &'env JNIEnv,
     name: &'s str,
     member: MemberKind,
-) -> Result<Cow<'s, CStr>, LocalObject<'env, JavaThrowable>> {
+) -> Result<MemberName<'s>, LocalObject<'env, JavaThrowable>> {
     if name.as_bytes().last() == Some(&b'\0')
         && let Ok(s) = CStr::from_bytes_with_nul(name.as_bytes())
     {
-        return Ok(Cow::Borrowed(s));
+        return Ok(MemberName::Borrowed(s));
+    }
+
+    if name.len() < INLINE_NAME_CAPACITY && !name.as_bytes().contains(&0) {
+        let mut buf = [0u8; INLINE_NAME_CAPACITY];
+        buf[..name.len()].copy_from_slice(name.as_bytes());
+
+        return Ok(MemberName::Inline(buf));
     }
 
-    CString::new(name).map(Cow::Owned).map_err(|err| {
+    CString::new(name).map(MemberName::Owned).map_err(|err| {
         throwable::helper::new_named_exception(
             env,
             match member {
@@ -78,6 +107,33 @@ pub fn build_member_name<'env, 's>(
     })
 }
 
+/// Returns the class name of `T`, which is built at compile time if possible.
+pub fn class_name_of<'env, T: Type>(env: &'env JNIEnv) -> Result<Cow<'static, CStr>, LocalObject<'env, JavaThrowable>> {
+    match Descriptor::<T>::CLASS {
+        Some(name) => Ok(Cow::Borrowed(name)),
+        None => build_class_name(env, T::SIGNATURE, false).map(Cow::Owned),
+    }
+}
+
+/// Returns the field descriptor of `T`, which is built at compile time if possible.
+pub fn field_signature_of<'env, T: Type>(env: &'env JNIEnv) -> Result<Cow<'static, CStr>, LocalObject<'env, JavaThrowable>> {
+    match Descriptor::<T>::FIELD {
+        Some(sig) => Ok(Cow::Borrowed(sig)),
+        None => build_field_signature(env, T::SIGNATURE).map(Cow::Owned),
+    }
+}
+
+/// Returns the method descriptor of `args` returning `R`, which is built at compile time if possible.
+pub fn method_signature_of<'env, R: Type, A: Args>(
+    env: &'env JNIEnv,
+    args: &A,
+) -> Result<Cow<'static, CStr>, LocalObject<'env, JavaThrowable>> {
+    match Descriptor::<fn(A) -> R>::METHOD {
+        Some(sig) => Ok(Cow::Borrowed(sig)),
+        None => build_method_signature(env, R::SIGNATURE, args.signature()).map(Cow::Owned),
+    }
+}
+
 pub fn build_field_signature<'env>(env: &'env JNIEnv, v: Signature) -> Result<CString, LocalObject<'env, JavaThrowable>> {
     let mut sig = String::with_capacity(v.size_hint() + 1);
 
@@ -1,6 +1,11 @@
 #[cfg(feature = "cache")]
 mod cache;
 pub(crate) mod helper;
+// members and fields are only described by `verify`
+#[cfg_attr(not(any(feature = "verify-members", debug_assertions)), allow(dead_code))]
+pub(crate) mod reflect;
+#[cfg(any(feature = "verify-members", debug_assertions))]
+mod verify;
 
 use core::ffi::CStr;
 
@@ -8,22 +13,67 @@ use typed_jni_core::{FieldID, JNIEnv, LocalRef, MethodID, StrongRef};
 
 use crate::{LocalObject, TypedRef, builtin::JavaThrowable};
 
+pub fn resolve_class<'env>(env: &'env JNIEnv, cls: &CStr) -> Result<LocalRef<'env>, LocalObject<'env, JavaThrowable>> {
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!("resolve_class", class = ?cls, cached = tracing::field::Empty).entered();
+
+    #[cfg(feature = "cache")]
+    if let Some(cls) = cache::find_class(env, cls) {
+        #[cfg(feature = "tracing")]
+        span.record("cached", true);
+
+        return Ok(cls);
+    }
+
+    #[cfg(feature = "tracing")]
+    span.record("cached", false);
+
+    unsafe {
+        let cls_obj = env.find_class(cls).map_err(|err| LocalObject::from_ref(err))?;
+
+        #[cfg(feature = "cache")]
+        cache::put_class(env, cls, &cls_obj);
+
+        Ok(cls_obj)
+    }
+}
+
 pub fn resolve_class_and_method<'env, const STATIC: bool>(
     env: &'env JNIEnv,
     cls: &CStr,
     name: &CStr,
     sig: &CStr,
 ) -> Result<(LocalRef<'env>, MethodID<STATIC>), LocalObject<'env, JavaThrowable>> {
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "resolve_class_and_method",
+        class = ?cls,
+        name = ?name,
+        sig = ?sig,
+        is_static = STATIC,
+        cached = tracing::field::Empty
+    )
+    .entered();
+
     #[cfg(feature = "cache")]
     if let Some((cls, method)) = cache::find_class_and_method::<STATIC>(env, cls, name, sig) {
+        #[cfg(feature = "tracing")]
+        span.record("cached", true);
+
         return Ok((cls, method));
     }
 
+    #[cfg(feature = "tracing")]
+    span.record("cached", false);
+
     unsafe {
         let cls_obj = env.find_class(cls).map_err(|err| LocalObject::from_ref(err))?;
-        let method = env
-            .get_method_id(&cls_obj, name, sig)
-            .map_err(|err| LocalObject::from_ref(err))?;
+        let method = env.get_method_id(&cls_obj, name, sig);
+
+        #[cfg(any(feature = "verify-members", debug_assertions))]
+        let method = method.map_err(|err| verify::method_error::<STATIC, _>(env, &cls_obj, name, sig, err));
+
+        let method = method.map_err(|err| LocalObject::from_ref(err))?;
 
         #[cfg(feature = "cache")]
         cache::put_class_and_method::<STATIC, _>(env, cls, name, sig, &cls_obj, method);
@@ -38,15 +88,34 @@ pub fn resolve_method<'env, const STATIC: bool, C: StrongRef>(
     name: &CStr,
     signature: &CStr,
 ) -> Result<MethodID<STATIC>, LocalObject<'env, JavaThrowable>> {
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "resolve_method",
+        name = ?name,
+        sig = ?signature,
+        is_static = STATIC,
+        cached = tracing::field::Empty
+    )
+    .entered();
+
     #[cfg(feature = "cache")]
     if let Some(method) = cache::find_method::<STATIC, _>(env, cls, name, signature) {
+        #[cfg(feature = "tracing")]
+        span.record("cached", true);
+
         return Ok(method);
     }
 
+    #[cfg(feature = "tracing")]
+    span.record("cached", false);
+
     unsafe {
-        let method = env
-            .get_method_id(cls, name, signature)
-            .map_err(|err| LocalObject::from_ref(err))?;
+        let method = env.get_method_id(cls, name, signature);
+
+        #[cfg(any(feature = "verify-members", debug_assertions))]
+        let method = method.map_err(|err| verify::method_error::<STATIC, _>(env, cls, name, signature, err));
+
+        let method = method.map_err(|err| LocalObject::from_ref(err))?;
 
         #[cfg(feature = "cache")]
         cache::put_method::<STATIC, _>(env, cls, name, signature, method);
@@ -61,15 +130,34 @@ pub fn resolve_field<'env, const STATIC: bool, C: StrongRef>(
     name: &CStr,
     signature: &CStr,
 ) -> Result<FieldID<STATIC>, LocalObject<'env, JavaThrowable>> {
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!(
+        "resolve_field",
+        name = ?name,
+        sig = ?signature,
+        is_static = STATIC,
+        cached = tracing::field::Empty
+    )
+    .entered();
+
     #[cfg(feature = "cache")]
     if let Some(field) = cache::find_field::<STATIC, _>(env, cls, name, signature) {
+        #[cfg(feature = "tracing")]
+        span.record("cached", true);
+
         return Ok(field);
     }
 
+    #[cfg(feature = "tracing")]
+    span.record("cached", false);
+
     unsafe {
-        let field = env
-            .get_field_id(cls, name, signature)
-            .map_err(|err| LocalObject::from_ref(err))?;
+        let field = env.get_field_id(cls, name, signature);
+
+        #[cfg(any(feature = "verify-members", debug_assertions))]
+        let field = field.map_err(|err| verify::field_error::<STATIC, _>(env, cls, name, signature, err));
+
+        let field = field.map_err(|err| LocalObject::from_ref(err))?;
 
         #[cfg(feature = "cache")]
         cache::put_field::<STATIC, _>(env, cls, name, signature, field);
@@ -0,0 +1,308 @@
+//! Reflection on classes and their members.
+//!
+//! Reflection only goes through [`JNIEnv`] directly, so failures in here never recurse into the resolver.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::{ffi::CStr, fmt::Write};
+
+use typed_jni_core::{JNIEnv, LocalRef, MethodID};
+
+use crate::{MethodSignature, OwnedSignature, Signature};
+
+/// Modifier bit of static members, see `java.lang.reflect.Modifier`.
+pub const MODIFIER_STATIC: i32 = 0x0008;
+/// Modifier bit of bridge methods, see `java.lang.reflect.Modifier`.
+pub const MODIFIER_BRIDGE: i32 = 0x0040;
+/// Modifier bit of synthetic members, see `java.lang.reflect.Modifier`.
+pub const MODIFIER_SYNTHETIC: i32 = 0x1000;
+
+/// A method, constructor or field.
+pub struct Member {
+    pub name: String,
+    pub is_static: bool,
+    /// Parameters of methods, `None` for fields.
+    pub params: Option<Vec<OwnedSignature>>,
+    /// Return type of methods or type of fields.
+    pub ty: OwnedSignature,
+}
+
+impl Member {
+    /// Describes in Java source style with descriptor, e.g. `static int parseInt(java.lang.String) (Ljava/lang/String;)I`.
+    pub fn describe(&self, class_name: &str) -> String {
+        let mut ret = String::new();
+
+        if self.is_static {
+            ret.push_str("static ");
+        }
+
+        match &self.params {
+            Some(params) if self.name == "<init>" => {
+                write!(ret, "{class_name}(").unwrap();
+                write_params(&mut ret, params);
+                ret.push(')');
+            }
+            Some(params) => {
+                write!(ret, "{} {}(", self.ty.to_java_name(), self.name).unwrap();
+                write_params(&mut ret, params);
+                ret.push(')');
+            }
+            None => write!(ret, "{} {}", self.ty.to_java_name(), self.name).unwrap(),
+        }
+
+        match &self.params {
+            Some(params) => write!(
+                ret,
+                " {}",
+                MethodSignature {
+                    params: params.clone(),
+                    ret: self.ty.clone(),
+                }
+            )
+            .unwrap(),
+            None => write!(ret, " {}", self.ty).unwrap(),
+        }
+
+        ret
+    }
+}
+
+fn write_params(s: &mut String, params: &[OwnedSignature]) {
+    for (index, param) in params.iter().enumerate() {
+        if index > 0 {
+            s.push_str(", ");
+        }
+        s.push_str(&param.to_java_name());
+    }
+}
+
+/// Resolved reflection methods.
+pub struct Reflection<'env> {
+    env: &'env JNIEnv<'env>,
+    get_name: MethodID<false>,
+    get_superclass: MethodID<false>,
+    get_interfaces: MethodID<false>,
+    get_declared_methods: MethodID<false>,
+    get_declared_constructors: MethodID<false>,
+    get_declared_fields: MethodID<false>,
+    member_get_name: MethodID<false>,
+    member_get_modifiers: MethodID<false>,
+    get_parameter_types: MethodID<false>,
+    get_return_type: MethodID<false>,
+    get_type: MethodID<false>,
+}
+
+impl<'env> Reflection<'env> {
+    pub fn new(env: &'env JNIEnv<'env>) -> Result<Self, LocalRef<'env>> {
+        let method = |cls: &CStr, name: &CStr, sig: &CStr| unsafe {
+            let cls = env.find_class(cls)?;
+
+            env.get_method_id::<false, _>(&cls, name, sig)
+        };
+
+        Ok(Self {
+            env,
+            get_name: method(c"java/lang/Class", c"getName", c"()Ljava/lang/String;")?,
+            get_superclass: method(c"java/lang/Class", c"getSuperclass", c"()Ljava/lang/Class;")?,
+            get_interfaces: method(c"java/lang/Class", c"getInterfaces", c"()[Ljava/lang/Class;")?,
+            get_declared_methods: method(c"java/lang/Class", c"getDeclaredMethods", c"()[Ljava/lang/reflect/Method;")?,
+            get_declared_constructors: method(
+                c"java/lang/Class",
+                c"getDeclaredConstructors",
+                c"()[Ljava/lang/reflect/Constructor;",
+            )?,
+            get_declared_fields: method(c"java/lang/Class", c"getDeclaredFields", c"()[Ljava/lang/reflect/Field;")?,
+            member_get_name: method(c"java/lang/reflect/Member", c"getName", c"()Ljava/lang/String;")?,
+            member_get_modifiers: method(c"java/lang/reflect/Member", c"getModifiers", c"()I")?,
+            get_parameter_types: method(c"java/lang/reflect/Executable", c"getParameterTypes", c"()[Ljava/lang/Class;")?,
+            get_return_type: method(c"java/lang/reflect/Method", c"getReturnType", c"()Ljava/lang/Class;")?,
+            get_type: method(c"java/lang/reflect/Field", c"getType", c"()Ljava/lang/Class;")?,
+        })
+    }
+
+    fn call(&self, this: &LocalRef<'env>, method: MethodID<false>) -> Result<Option<LocalRef<'env>>, LocalRef<'env>> {
+        unsafe { self.env.call_object_method(this, method, []) }
+    }
+
+    fn string(&self, this: &LocalRef<'env>, method: MethodID<false>) -> Result<String, LocalRef<'env>> {
+        Ok(self
+            .call(this, method)?
+            .map(|s| unsafe { self.env.get_string(&s) })
+            .unwrap_or_default())
+    }
+
+    /// Calls `method` returning an array and visits its elements.
+    pub fn for_each(
+        &self,
+        this: &LocalRef<'env>,
+        method: MethodID<false>,
+        mut f: impl FnMut(LocalRef<'env>) -> Result<(), LocalRef<'env>>,
+    ) -> Result<(), LocalRef<'env>> {
+        let Some(array) = self.call(this, method)? else {
+            return Ok(());
+        };
+
+        unsafe {
+            for index in 0..self.env.get_array_length(&array)? {
+                if let Some(element) = self.env.get_object_array_element(&array, index)? {
+                    f(element)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn class_name(&self, cls: &LocalRef<'env>) -> Result<String, LocalRef<'env>> {
+        self.string(cls, self.get_name)
+    }
+
+    pub fn signature(&self, cls: &LocalRef<'env>) -> Result<OwnedSignature, LocalRef<'env>> {
+        let name = self.class_name(cls)?;
+
+        // array classes are named like descriptors with dots, e.g. `[Ljava.lang.String;`
+        let signature = if name.starts_with('[') {
+            Signature::parse(&name.replace('.', "/")).ok()
+        } else {
+            OwnedSignature::from_java_name(&name).ok()
+        };
+
+        // hidden classes are named like `org.example.Foo$$Lambda/0x0000000800c03000`
+        Ok(signature.unwrap_or_else(|| OwnedSignature::Object(name.replace('.', "/"))))
+    }
+
+    fn member(&self, member: &LocalRef<'env>, params: bool, ty: Option<MethodID<false>>) -> Result<Member, LocalRef<'env>> {
+        let name = self.member_name(member)?;
+        let modifiers = self.modifiers(member)?;
+
+        let params = match params {
+            true => {
+                let mut params = Vec::new();
+                self.for_each(member, self.get_parameter_types, |param| {
+                    params.push(self.signature(&param)?);
+
+                    Ok(())
+                })?;
+
+                Some(params)
+            }
+            false => None,
+        };
+
+        let ty = match ty.map(|ty| self.call(member, ty)).transpose()?.flatten() {
+            Some(ty) => self.signature(&ty)?,
+            None => OwnedSignature::Void,
+        };
+
+        Ok(Member {
+            name,
+            is_static: modifiers & MODIFIER_STATIC != 0,
+            params,
+            ty,
+        })
+    }
+
+    /// Visits `cls`, its superclasses and superinterfaces, which are looked up by `GetMethodID` and `GetFieldID`.
+    fn for_each_class(
+        &self,
+        cls: &LocalRef<'env>,
+        mut f: impl FnMut(&LocalRef<'env>) -> Result<(), LocalRef<'env>>,
+    ) -> Result<(), LocalRef<'env>> {
+        let mut visited = Vec::new();
+        let mut pending = vec![self.env.new_local_ref(cls).expect("BROKEN: class is null")];
+
+        while let Some(cls) = pending.pop() {
+            let name = self.class_name(&cls)?;
+            if visited.contains(&name) {
+                continue;
+            }
+
+            f(&cls)?;
+
+            if let Some(superclass) = self.call(&cls, self.get_superclass)? {
+                pending.push(superclass);
+            }
+            self.for_each(&cls, self.get_interfaces, |interface| {
+                pending.push(interface);
+
+                Ok(())
+            })?;
+
+            visited.push(name);
+        }
+
+        Ok(())
+    }
+
+    /// Visits declared constructors of `cls`, or declared methods of `cls`, its superclasses and superinterfaces.
+    pub fn for_each_method(
+        &self,
+        cls: &LocalRef<'env>,
+        constructors: bool,
+        mut f: impl FnMut(LocalRef<'env>) -> Result<(), LocalRef<'env>>,
+    ) -> Result<(), LocalRef<'env>> {
+        if constructors {
+            self.for_each(cls, self.get_declared_constructors, f)
+        } else {
+            self.for_each_class(cls, |cls| self.for_each(cls, self.get_declared_methods, &mut f))
+        }
+    }
+
+    pub fn member_name(&self, member: &LocalRef<'env>) -> Result<String, LocalRef<'env>> {
+        self.string(member, self.member_get_name)
+    }
+
+    pub fn modifiers(&self, member: &LocalRef<'env>) -> Result<i32, LocalRef<'env>> {
+        unsafe { self.env.call_int_method(member, self.member_get_modifiers, []) }
+    }
+
+    pub fn parameter_types(&self, method: &LocalRef<'env>) -> Result<Vec<LocalRef<'env>>, LocalRef<'env>> {
+        let mut params = Vec::new();
+        self.for_each(method, self.get_parameter_types, |param| {
+            params.push(param);
+
+            Ok(())
+        })?;
+
+        Ok(params)
+    }
+
+    /// Returns the return type of a method, `None` for constructors.
+    pub fn return_type(&self, method: &LocalRef<'env>, constructor: bool) -> Result<Option<LocalRef<'env>>, LocalRef<'env>> {
+        match constructor {
+            true => Ok(None),
+            false => self.call(method, self.get_return_type),
+        }
+    }
+
+    pub fn methods(&self, cls: &LocalRef<'env>, constructors: bool) -> Result<Vec<Member>, LocalRef<'env>> {
+        let mut members = Vec::new();
+
+        self.for_each_method(cls, constructors, |method| {
+            let ty = (!constructors).then_some(self.get_return_type);
+            let mut member = self.member(&method, true, ty)?;
+            if constructors {
+                member.name = "<init>".into();
+            }
+
+            members.push(member);
+
+            Ok(())
+        })?;
+
+        Ok(members)
+    }
+
+    pub fn fields(&self, cls: &LocalRef<'env>) -> Result<Vec<Member>, LocalRef<'env>> {
+        let mut members = Vec::new();
+
+        self.for_each_class(cls, |cls| {
+            self.for_each(cls, self.get_declared_fields, |field| {
+                members.push(self.member(&field, false, Some(self.get_type))?);
+
+                Ok(())
+            })
+        })?;
+
+        Ok(members)
+    }
+}
@@ -0,0 +1,192 @@
+//! Describes member lookup failures with the actual members of the class, listed by reflection.
+
+use alloc::{format, string::String, vec::Vec};
+use core::{ffi::CStr, fmt::Write};
+
+use typed_jni_core::{Arg, JNIEnv, LocalRef, StrongRef};
+
+use super::reflect::{Member, Reflection};
+use crate::{MethodSignature, Signature};
+
+/// Closest candidates listed in the message at most.
+const MAX_CANDIDATES: usize = 5;
+
+/// Wraps `err` into a `NoSuchMethodError` describing the expected and actual overloads of `name`.
+///
+/// Returns `err` as is if it is not a `NoSuchMethodError` or reflection fails.
+pub fn method_error<'env, const STATIC: bool, C: StrongRef>(
+    env: &'env JNIEnv,
+    cls: &C,
+    name: &CStr,
+    sig: &CStr,
+    err: LocalRef<'env>,
+) -> LocalRef<'env> {
+    let expected = sig.to_str().ok().and_then(|sig| MethodSignature::parse(sig).ok());
+    let expected = expected.map(|expected| Member {
+        name: name.to_string_lossy().into_owned(),
+        is_static: STATIC,
+        params: Some(expected.params),
+        ty: expected.ret,
+    });
+
+    let lookup = Lookup {
+        kind: "method",
+        error: c"java/lang/NoSuchMethodError",
+        name,
+        sig,
+        expected,
+    };
+
+    describe(env, cls, lookup, err, |r, cls| r.methods(cls, name == c"<init>"))
+}
+
+/// Wraps `err` into a `NoSuchFieldError` describing the expected and actual fields named like `name`.
+///
+/// Returns `err` as is if it is not a `NoSuchFieldError` or reflection fails.
+pub fn field_error<'env, const STATIC: bool, C: StrongRef>(
+    env: &'env JNIEnv,
+    cls: &C,
+    name: &CStr,
+    sig: &CStr,
+    err: LocalRef<'env>,
+) -> LocalRef<'env> {
+    let expected = sig.to_str().ok().and_then(|sig| Signature::parse(sig).ok());
+    let expected = expected.map(|expected| Member {
+        name: name.to_string_lossy().into_owned(),
+        is_static: STATIC,
+        params: None,
+        ty: expected,
+    });
+
+    let lookup = Lookup {
+        kind: "field",
+        error: c"java/lang/NoSuchFieldError",
+        name,
+        sig,
+        expected,
+    };
+
+    describe(env, cls, lookup, err, |r, cls| r.fields(cls))
+}
+
+/// A failed member lookup.
+struct Lookup<'a> {
+    kind: &'static str,
+    error: &'static CStr,
+    name: &'a CStr,
+    sig: &'a CStr,
+    /// The expected member, `None` if the descriptor is malformed.
+    expected: Option<Member>,
+}
+
+fn describe<'env, C: StrongRef>(
+    env: &'env JNIEnv,
+    cls: &C,
+    lookup: Lookup<'_>,
+    err: LocalRef<'env>,
+    list: impl FnOnce(&Reflection<'env>, &LocalRef<'env>) -> Result<Vec<Member>, LocalRef<'env>>,
+) -> LocalRef<'env> {
+    let Lookup {
+        kind,
+        error,
+        name,
+        sig,
+        expected,
+    } = lookup;
+
+    let message = || -> Result<Option<String>, LocalRef<'env>> {
+        let c_error = env.find_class(error)?;
+        if unsafe { !env.is_instance_of(&err, &c_error) } {
+            return Ok(None);
+        }
+
+        let r = Reflection::new(env)?;
+        let cls = env.new_local_ref(cls).expect("BROKEN: class is null");
+        let class_name = r.class_name(&cls)?;
+
+        let members = list(&r, &cls)?;
+
+        let name = name.to_string_lossy();
+        let mut candidates = members
+            .iter()
+            .filter_map(|member| {
+                let distance = edit_distance(&name, &member.name);
+                let mismatches = expected
+                    .as_ref()
+                    .map(|expected| expected.mismatches(member))
+                    .unwrap_or_default();
+
+                (distance <= name.len().max(6) / 3).then(|| ((distance, mismatches), member.describe(&class_name)))
+            })
+            .collect::<Vec<_>>();
+        candidates.sort();
+        candidates.dedup_by(|(_, a), (_, b)| a == b);
+
+        let mut message = match &expected {
+            Some(expected) => format!("{class_name}: no {kind} `{}`", expected.describe(&class_name)),
+            None => format!("{class_name}: no {kind} `{name}` with descriptor {}", sig.to_string_lossy()),
+        };
+        if candidates.is_empty() {
+            message.push_str(", no members with similar names");
+        } else {
+            message.push_str(", closest candidates:");
+            for (_, candidate) in candidates.iter().take(MAX_CANDIDATES) {
+                write!(message, "\n    {candidate}").unwrap();
+            }
+        }
+
+        Ok(Some(message))
+    };
+
+    let message = match message() {
+        Ok(Some(message)) => message,
+        Ok(None) | Err(_) => return err,
+    };
+
+    unsafe {
+        let described = env.find_class(error).and_then(|c_error| {
+            let init = env.get_method_id::<false, _>(&c_error, c"<init>", c"(Ljava/lang/String;)V")?;
+            let described = env.new_object(&c_error, init, [Arg::Object(Some(&env.new_string(&message)))])?;
+
+            let c_throwable = env.find_class(c"java/lang/Throwable")?;
+            let init_cause =
+                env.get_method_id::<false, _>(&c_throwable, c"initCause", c"(Ljava/lang/Throwable;)Ljava/lang/Throwable;")?;
+            env.call_object_method(&described, init_cause, [Arg::Object(Some(&err))])?;
+
+            Ok(described)
+        });
+
+        described.unwrap_or(err)
+    }
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.bytes().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+
+        for (j, &cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = (prev + (ca != cb) as usize).min(row[j] + 1).min(cur + 1);
+            prev = cur;
+        }
+    }
+
+    row[b.len()]
+}
+
+impl Member {
+    /// Counts differences to `other` in staticness, parameters and type.
+    fn mismatches(&self, other: &Member) -> usize {
+        let params = match (&self.params, &other.params) {
+            (Some(a), Some(b)) => a.iter().zip(b).filter(|(a, b)| a != b).count() + a.len().abs_diff(b.len()),
+            _ => 0,
+        };
+
+        (self.is_static != other.is_static) as usize + params + (self.ty != other.ty) as usize
+    }
+}
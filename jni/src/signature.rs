@@ -0,0 +1,633 @@
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::{
+    ffi::CStr,
+    fmt::{Display, Formatter, Write},
+    marker::PhantomData,
+    str::FromStr,
+};
+
+use crate::{Args, Type};
+
+/// A signature of a JNI type.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Signature {
+    Void,
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Object(&'static str),
+    Array(&'static Signature),
+}
+
+impl Signature {
+    pub const fn size_hint(&self) -> usize {
+        match self {
+            Signature::Void
+            | Signature::Boolean
+            | Signature::Byte
+            | Signature::Char
+            | Signature::Short
+            | Signature::Int
+            | Signature::Long
+            | Signature::Float
+            | Signature::Double => 1, // single char
+            Signature::Object(s) => s.len() + 2,              // L<name>;
+            Signature::Array(inner) => inner.size_hint() + 1, // [<inner>
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_descriptor(self, w)
+    }
+
+    pub fn write_as_class_name_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_class_name(self, w)
+    }
+
+    /// Writes the Java source-style name, e.g. `int` or `java.lang.String[]`.
+    pub fn write_as_java_name_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_java_name(self, w)
+    }
+
+    /// Parses a field descriptor (or `V`), e.g. `I` or `[Ljava/lang/String;`.
+    pub fn parse(s: &str) -> Result<OwnedSignature, SignatureError> {
+        s.parse()
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_to(f)
+    }
+}
+
+/// An owned signature of a JNI type, e.g. parsed by [`Signature::parse`].
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum OwnedSignature {
+    Void,
+    Boolean,
+    Byte,
+    Char,
+    Short,
+    Int,
+    Long,
+    Float,
+    Double,
+    Object(String),
+    Array(Box<OwnedSignature>),
+}
+
+impl OwnedSignature {
+    pub fn size_hint(&self) -> usize {
+        match self {
+            OwnedSignature::Object(s) => s.len() + 2,
+            OwnedSignature::Array(inner) => inner.size_hint() + 1,
+            _ => 1,
+        }
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_descriptor(self, w)
+    }
+
+    pub fn write_as_class_name_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_class_name(self, w)
+    }
+
+    /// Writes the Java source-style name, e.g. `int` or `java.lang.String[]`.
+    pub fn write_as_java_name_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        write_java_name(self, w)
+    }
+
+    /// Returns the Java source-style name, e.g. `int` or `java.lang.String[]`.
+    pub fn to_java_name(&self) -> String {
+        let mut name = String::with_capacity(self.size_hint());
+
+        self.write_as_java_name_to(&mut name).unwrap();
+
+        name
+    }
+
+    /// Parses a Java source-style name, e.g. `int` or `java.lang.String[]`.
+    pub fn from_java_name(s: &str) -> Result<Self, SignatureError> {
+        let bytes = s.as_bytes();
+
+        let mut end = bytes.len();
+        let mut dimensions = 0;
+        while end > 0 && bytes[end - 1] == b']' {
+            if end < 2 || bytes[end - 2] != b'[' {
+                return Err(SignatureError::new(end - 1, SignatureErrorKind::UnexpectedByte(b']')));
+            }
+
+            end -= 2;
+            dimensions += 1;
+        }
+
+        let base = &s[..end];
+        let mut ret = match base {
+            "" => return Err(SignatureError::new(0, SignatureErrorKind::InvalidClassName)),
+            "void" if dimensions > 0 => return Err(SignatureError::new(0, SignatureErrorKind::UnexpectedVoid)),
+            "void" => OwnedSignature::Void,
+            "boolean" => OwnedSignature::Boolean,
+            "byte" => OwnedSignature::Byte,
+            "char" => OwnedSignature::Char,
+            "short" => OwnedSignature::Short,
+            "int" => OwnedSignature::Int,
+            "long" => OwnedSignature::Long,
+            "float" => OwnedSignature::Float,
+            "double" => OwnedSignature::Double,
+            _ => {
+                validate_class_name(base, b'.', 0)?;
+
+                OwnedSignature::Object(base.replace('.', "/"))
+            }
+        };
+
+        for _ in 0..dimensions {
+            ret = OwnedSignature::Array(Box::new(ret));
+        }
+
+        Ok(ret)
+    }
+}
+
+impl Display for OwnedSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_to(f)
+    }
+}
+
+impl FromStr for OwnedSignature {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            bytes: s.as_bytes(),
+            pos: 0,
+        };
+
+        let ret = parser.parse_type(true)?;
+        parser.finish()?;
+
+        Ok(ret)
+    }
+}
+
+impl From<Signature> for OwnedSignature {
+    fn from(value: Signature) -> Self {
+        match value {
+            Signature::Void => OwnedSignature::Void,
+            Signature::Boolean => OwnedSignature::Boolean,
+            Signature::Byte => OwnedSignature::Byte,
+            Signature::Char => OwnedSignature::Char,
+            Signature::Short => OwnedSignature::Short,
+            Signature::Int => OwnedSignature::Int,
+            Signature::Long => OwnedSignature::Long,
+            Signature::Float => OwnedSignature::Float,
+            Signature::Double => OwnedSignature::Double,
+            Signature::Object(name) => OwnedSignature::Object(name.to_owned()),
+            Signature::Array(inner) => OwnedSignature::Array(Box::new((*inner).into())),
+        }
+    }
+}
+
+impl PartialEq<Signature> for OwnedSignature {
+    fn eq(&self, other: &Signature) -> bool {
+        match (self.node(), other.node()) {
+            (Node::Primitive(a), Node::Primitive(b)) => a == b,
+            (Node::Object(a), Node::Object(b)) => a == b,
+            (Node::Array(a), Node::Array(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+/// An owned signature of a JNI method, e.g. `(ILjava/lang/String;)V`.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MethodSignature {
+    pub params: Vec<OwnedSignature>,
+    pub ret: OwnedSignature,
+}
+
+impl MethodSignature {
+    /// Parses a method descriptor, e.g. `(ILjava/lang/String;)V`.
+    pub fn parse(s: &str) -> Result<Self, SignatureError> {
+        s.parse()
+    }
+
+    pub fn size_hint(&self) -> usize {
+        self.params.iter().map(OwnedSignature::size_hint).sum::<usize>() + self.ret.size_hint() + 2
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> core::fmt::Result {
+        w.write_str("(")?;
+        for param in &self.params {
+            param.write_to(w)?;
+        }
+        w.write_str(")")?;
+
+        self.ret.write_to(w)
+    }
+}
+
+impl Display for MethodSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_to(f)
+    }
+}
+
+impl FromStr for MethodSignature {
+    type Err = SignatureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            bytes: s.as_bytes(),
+            pos: 0,
+        };
+
+        parser.expect(b'(')?;
+
+        let mut params = Vec::new();
+        while parser.peek() != Some(b')') {
+            params.push(parser.parse_type(false)?);
+        }
+
+        parser.expect(b')')?;
+
+        let ret = parser.parse_type(true)?;
+        parser.finish()?;
+
+        Ok(Self { params, ret })
+    }
+}
+
+/// A kind of [`SignatureError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureErrorKind {
+    /// The descriptor ends unexpectedly.
+    UnexpectedEnd,
+    /// An unexpected byte.
+    UnexpectedByte(u8),
+    /// `void` in a parameter or an array element.
+    UnexpectedVoid,
+    /// An empty class name, an empty package segment or an invalid byte in a class name.
+    InvalidClassName,
+    /// Bytes after a complete descriptor.
+    TrailingBytes,
+}
+
+/// An error of parsing a descriptor, which points at the offending byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignatureError {
+    /// The offset of the offending byte.
+    pub offset: usize,
+    pub kind: SignatureErrorKind,
+}
+
+impl SignatureError {
+    fn new(offset: usize, kind: SignatureErrorKind) -> Self {
+        Self { offset, kind }
+    }
+}
+
+impl Display for SignatureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            SignatureErrorKind::UnexpectedEnd => write!(f, "unexpected end at byte {}", self.offset),
+            SignatureErrorKind::UnexpectedByte(b) => write!(f, "unexpected {:?} at byte {}", b as char, self.offset),
+            SignatureErrorKind::UnexpectedVoid => write!(f, "unexpected void at byte {}", self.offset),
+            SignatureErrorKind::InvalidClassName => write!(f, "invalid class name at byte {}", self.offset),
+            SignatureErrorKind::TrailingBytes => write!(f, "trailing bytes at byte {}", self.offset),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SignatureError {}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Result<u8, SignatureError> {
+        let b = self
+            .peek()
+            .ok_or(SignatureError::new(self.pos, SignatureErrorKind::UnexpectedEnd))?;
+        self.pos += 1;
+
+        Ok(b)
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), SignatureError> {
+        match self.next()? {
+            b if b == expected => Ok(()),
+            b => Err(SignatureError::new(self.pos - 1, SignatureErrorKind::UnexpectedByte(b))),
+        }
+    }
+
+    fn finish(&self) -> Result<(), SignatureError> {
+        match self.pos < self.bytes.len() {
+            true => Err(SignatureError::new(self.pos, SignatureErrorKind::TrailingBytes)),
+            false => Ok(()),
+        }
+    }
+
+    fn parse_type(&mut self, allow_void: bool) -> Result<OwnedSignature, SignatureError> {
+        let start = self.pos;
+
+        let ret = match self.next()? {
+            b'V' if allow_void => OwnedSignature::Void,
+            b'V' => return Err(SignatureError::new(start, SignatureErrorKind::UnexpectedVoid)),
+            b'Z' => OwnedSignature::Boolean,
+            b'B' => OwnedSignature::Byte,
+            b'C' => OwnedSignature::Char,
+            b'S' => OwnedSignature::Short,
+            b'I' => OwnedSignature::Int,
+            b'J' => OwnedSignature::Long,
+            b'F' => OwnedSignature::Float,
+            b'D' => OwnedSignature::Double,
+            b'L' => {
+                let name_start = self.pos;
+                let len = self.bytes[name_start..]
+                    .iter()
+                    .position(|&b| b == b';')
+                    .ok_or(SignatureError::new(self.bytes.len(), SignatureErrorKind::UnexpectedEnd))?;
+
+                // the descriptor is a `str`, so slicing at ASCII bytes is always valid
+                let name = core::str::from_utf8(&self.bytes[name_start..name_start + len]).expect("BROKEN: invalid UTF-8");
+                validate_class_name(name, b'/', name_start)?;
+
+                self.pos = name_start + len + 1;
+
+                OwnedSignature::Object(name.to_owned())
+            }
+            b'[' => OwnedSignature::Array(Box::new(self.parse_type(false)?)),
+            b => return Err(SignatureError::new(start, SignatureErrorKind::UnexpectedByte(b))),
+        };
+
+        Ok(ret)
+    }
+}
+
+/// Validates a class name with package separator `separator`, `offset` is the offset of the name in the input.
+fn validate_class_name(name: &str, separator: u8, offset: usize) -> Result<(), SignatureError> {
+    let err = |index: usize| Err(SignatureError::new(offset + index, SignatureErrorKind::InvalidClassName));
+
+    let mut segment_start = 0;
+    for (index, &b) in name.as_bytes().iter().enumerate() {
+        match b {
+            b if b == separator => {
+                if index == segment_start {
+                    return err(index);
+                }
+
+                segment_start = index + 1;
+            }
+            b'.' | b'/' | b';' | b'[' | b']' | b'\0' => return err(index),
+            _ => (),
+        }
+    }
+
+    if segment_start == name.len() {
+        return err(name.len());
+    }
+
+    Ok(())
+}
+
+enum Node<'a, S> {
+    /// Descriptor char and Java name of a primitive type or void.
+    Primitive((&'static str, &'static str)),
+    Object(&'a str),
+    Array(&'a S),
+}
+
+trait SignatureTree: Sized {
+    fn node(&self) -> Node<'_, Self>;
+}
+
+const VOID: (&str, &str) = ("V", "void");
+const BOOLEAN: (&str, &str) = ("Z", "boolean");
+const BYTE: (&str, &str) = ("B", "byte");
+const CHAR: (&str, &str) = ("C", "char");
+const SHORT: (&str, &str) = ("S", "short");
+const INT: (&str, &str) = ("I", "int");
+const LONG: (&str, &str) = ("J", "long");
+const FLOAT: (&str, &str) = ("F", "float");
+const DOUBLE: (&str, &str) = ("D", "double");
+
+impl SignatureTree for Signature {
+    fn node(&self) -> Node<'_, Self> {
+        match self {
+            Signature::Void => Node::Primitive(VOID),
+            Signature::Boolean => Node::Primitive(BOOLEAN),
+            Signature::Byte => Node::Primitive(BYTE),
+            Signature::Char => Node::Primitive(CHAR),
+            Signature::Short => Node::Primitive(SHORT),
+            Signature::Int => Node::Primitive(INT),
+            Signature::Long => Node::Primitive(LONG),
+            Signature::Float => Node::Primitive(FLOAT),
+            Signature::Double => Node::Primitive(DOUBLE),
+            Signature::Object(name) => Node::Object(name),
+            Signature::Array(inner) => Node::Array(inner),
+        }
+    }
+}
+
+impl SignatureTree for OwnedSignature {
+    fn node(&self) -> Node<'_, Self> {
+        match self {
+            OwnedSignature::Void => Node::Primitive(VOID),
+            OwnedSignature::Boolean => Node::Primitive(BOOLEAN),
+            OwnedSignature::Byte => Node::Primitive(BYTE),
+            OwnedSignature::Char => Node::Primitive(CHAR),
+            OwnedSignature::Short => Node::Primitive(SHORT),
+            OwnedSignature::Int => Node::Primitive(INT),
+            OwnedSignature::Long => Node::Primitive(LONG),
+            OwnedSignature::Float => Node::Primitive(FLOAT),
+            OwnedSignature::Double => Node::Primitive(DOUBLE),
+            OwnedSignature::Object(name) => Node::Object(name),
+            OwnedSignature::Array(inner) => Node::Array(inner),
+        }
+    }
+}
+
+fn write_descriptor<S: SignatureTree, W: Write>(s: &S, w: &mut W) -> core::fmt::Result {
+    match s.node() {
+        Node::Primitive((descriptor, _)) => w.write_str(descriptor),
+        Node::Object(name) => {
+            w.write_str("L")?;
+            w.write_str(name)?;
+            w.write_str(";")
+        }
+        Node::Array(inner) => {
+            w.write_str("[")?;
+            write_descriptor(inner, w)
+        }
+    }
+}
+
+fn write_class_name<S: SignatureTree, W: Write>(s: &S, w: &mut W) -> core::fmt::Result {
+    match s.node() {
+        Node::Object(name) => w.write_str(name),
+        _ => write_descriptor(s, w),
+    }
+}
+
+fn write_java_name<S: SignatureTree, W: Write>(s: &S, w: &mut W) -> core::fmt::Result {
+    match s.node() {
+        Node::Primitive((_, name)) => w.write_str(name),
+        Node::Object(name) => {
+            for (index, part) in name.split('/').enumerate() {
+                if index > 0 {
+                    w.write_str(".")?;
+                }
+                w.write_str(part)?;
+            }
+
+            Ok(())
+        }
+        Node::Array(inner) => {
+            write_java_name(inner, w)?;
+            w.write_str("[]")
+        }
+    }
+}
+
+/// Capacity of descriptors built at compile time, including the NUL terminator.
+pub const DESCRIPTOR_CAPACITY: usize = 256;
+
+struct DescriptorBuf {
+    bytes: [u8; DESCRIPTOR_CAPACITY],
+    len: usize,
+    overflow: bool,
+}
+
+impl DescriptorBuf {
+    const fn new() -> Self {
+        Self {
+            bytes: [0; DESCRIPTOR_CAPACITY],
+            len: 0,
+            overflow: false,
+        }
+    }
+
+    const fn push(mut self, b: u8) -> Self {
+        // keeps the last byte for the NUL terminator
+        if self.len + 1 < DESCRIPTOR_CAPACITY {
+            self.bytes[self.len] = b;
+            self.len += 1;
+        } else {
+            self.overflow = true;
+        }
+
+        self
+    }
+
+    const fn push_str(mut self, s: &str) -> Self {
+        let bytes = s.as_bytes();
+
+        let mut index = 0;
+        while index < bytes.len() {
+            self = self.push(bytes[index]);
+            index += 1;
+        }
+
+        self
+    }
+
+    const fn push_signature(self, signature: &Signature) -> Self {
+        match signature {
+            Signature::Void => self.push(b'V'),
+            Signature::Boolean => self.push(b'Z'),
+            Signature::Byte => self.push(b'B'),
+            Signature::Char => self.push(b'C'),
+            Signature::Short => self.push(b'S'),
+            Signature::Int => self.push(b'I'),
+            Signature::Long => self.push(b'J'),
+            Signature::Float => self.push(b'F'),
+            Signature::Double => self.push(b'D'),
+            Signature::Object(name) => self.push(b'L').push_str(name).push(b';'),
+            Signature::Array(inner) => self.push(b'[').push_signature(inner),
+        }
+    }
+
+    const fn push_class_name(self, signature: &Signature) -> Self {
+        match signature {
+            Signature::Object(name) => self.push_str(name),
+            _ => self.push_signature(signature),
+        }
+    }
+
+    const fn finish(this: &'static Self) -> Option<&'static CStr> {
+        if this.overflow {
+            return None;
+        }
+
+        match CStr::from_bytes_with_nul(this.bytes.split_at(this.len + 1).0) {
+            Ok(s) => Some(s),
+            Err(_) => None,
+        }
+    }
+}
+
+/// JNI descriptors built at compile time.
+///
+/// A descriptor is `None` if it is longer than [`DESCRIPTOR_CAPACITY`], contains NUL or is not known at compile time,
+/// typed calls build it at runtime instead.
+///
+/// # Example
+///
+/// ```rust
+/// use typed_jni::{Descriptor, builtin::JavaString};
+///
+/// assert_eq!(Descriptor::<JavaString>::CLASS, Some(c"java/lang/String"));
+/// assert_eq!(Descriptor::<JavaString>::FIELD, Some(c"Ljava/lang/String;"));
+/// assert_eq!(Descriptor::<fn((i32, i64)) -> bool>::METHOD, Some(c"(IJ)Z"));
+/// ```
+pub struct Descriptor<T: ?Sized>(PhantomData<T>);
+
+impl<T: Type> Descriptor<T> {
+    const CLASS_BUF: DescriptorBuf = DescriptorBuf::new().push_class_name(&T::SIGNATURE);
+    const FIELD_BUF: DescriptorBuf = DescriptorBuf::new().push_signature(&T::SIGNATURE);
+
+    /// Class name of `T` used by `FindClass`, e.g. `java/lang/String` or `[I`.
+    pub const CLASS: Option<&'static CStr> = DescriptorBuf::finish(&Self::CLASS_BUF);
+
+    /// Field descriptor of `T`, e.g. `Ljava/lang/String;`.
+    pub const FIELD: Option<&'static CStr> = DescriptorBuf::finish(&Self::FIELD_BUF);
+}
+
+impl<R: Type, A: Args> Descriptor<fn(A) -> R> {
+    const METHOD_BUF: DescriptorBuf = match A::SIGNATURES {
+        Some(args) => {
+            let mut buf = DescriptorBuf::new().push(b'(');
+
+            let mut index = 0;
+            while index < args.len() {
+                buf = buf.push_signature(&args[index]);
+                index += 1;
+            }
+
+            buf.push(b')').push_signature(&R::SIGNATURE)
+        }
+        None => DescriptorBuf {
+            overflow: true,
+            ..DescriptorBuf::new()
+        },
+    };
+
+    /// Method descriptor of arguments `A` returning `R`, e.g. `(IJ)Z`.
+    pub const METHOD: Option<&'static CStr> = DescriptorBuf::finish(&Self::METHOD_BUF);
+}
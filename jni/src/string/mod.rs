@@ -1,8 +1,12 @@
 use alloc::string::String;
+use core::char::DecodeUtf16;
 
-use typed_jni_core::{JNIEnv, StrongRef};
+use typed_jni_core::{JNIEnv, StringUnits, StrongRef};
 
-use crate::{LocalObject, Object, TypedRef, builtin::JavaString};
+use crate::{
+    LocalObject, Object, TypedRef,
+    builtin::{JavaString, JavaThrowable},
+};
 
 /// Extension methods for typed string maintenance.
 pub trait TypedStringExt {
@@ -11,6 +15,35 @@ pub trait TypedStringExt {
 
     /// Returns the string slice of the given string object.
     fn typed_get_string(&self, s: &Object<impl StrongRef, JavaString>) -> String;
+
+    /// Returns the length of the string in UTF-16 code units.
+    fn typed_get_string_length(&self, s: &Object<impl StrongRef, JavaString>) -> i32;
+
+    /// Reads UTF-16 code units of the string starting at `offset` into the provided slice.
+    fn typed_get_string_region(
+        &self,
+        s: &Object<impl StrongRef, JavaString>,
+        offset: i32,
+        out: &mut [u16],
+    ) -> Result<(), LocalObject<'_, JavaThrowable>>;
+
+    /// Reads `len` UTF-16 code units of the string starting at `offset` into the provided slice in modified UTF-8,
+    /// returns the number of bytes written.
+    ///
+    /// Throws `IllegalArgumentException` if the slice is shorter than `len * 3 + 1` bytes.
+    fn typed_get_string_utf_region(
+        &self,
+        s: &Object<impl StrongRef, JavaString>,
+        offset: i32,
+        len: i32,
+        out: &mut [u8],
+    ) -> Result<usize, LocalObject<'_, JavaThrowable>>;
+
+    /// Returns an iterator over UTF-16 code units of the string, which are copied in chunks.
+    fn typed_iter_string_units<'a, R: StrongRef>(&'a self, s: &'a Object<R, JavaString>) -> StringUnits<'a, R>;
+
+    /// Returns an iterator over chars of the string, which are copied in chunks.
+    fn typed_iter_string_chars<'a, R: StrongRef>(&'a self, s: &'a Object<R, JavaString>) -> DecodeUtf16<StringUnits<'a, R>>;
 }
 
 impl<'vm> TypedStringExt for JNIEnv<'vm> {
@@ -21,4 +54,41 @@ impl<'vm> TypedStringExt for JNIEnv<'vm> {
     fn typed_get_string(&self, s: &Object<impl StrongRef, JavaString>) -> String {
         unsafe { self.get_string(&**s) }
     }
+
+    fn typed_get_string_length(&self, s: &Object<impl StrongRef, JavaString>) -> i32 {
+        unsafe { self.get_string_length(&**s) }
+    }
+
+    fn typed_get_string_region(
+        &self,
+        s: &Object<impl StrongRef, JavaString>,
+        offset: i32,
+        out: &mut [u16],
+    ) -> Result<(), LocalObject<'_, JavaThrowable>> {
+        unsafe {
+            self.get_string_region(&**s, offset, out)
+                .map_err(|err| LocalObject::from_ref(err))
+        }
+    }
+
+    fn typed_get_string_utf_region(
+        &self,
+        s: &Object<impl StrongRef, JavaString>,
+        offset: i32,
+        len: i32,
+        out: &mut [u8],
+    ) -> Result<usize, LocalObject<'_, JavaThrowable>> {
+        unsafe {
+            self.get_string_utf_region(&**s, offset, len, out)
+                .map_err(|err| LocalObject::from_ref(err))
+        }
+    }
+
+    fn typed_iter_string_units<'a, R: StrongRef>(&'a self, s: &'a Object<R, JavaString>) -> StringUnits<'a, R> {
+        unsafe { self.iter_string_units(&**s) }
+    }
+
+    fn typed_iter_string_chars<'a, R: StrongRef>(&'a self, s: &'a Object<R, JavaString>) -> DecodeUtf16<StringUnits<'a, R>> {
+        unsafe { self.iter_string_chars(&**s) }
+    }
 }
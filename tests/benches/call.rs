@@ -0,0 +1,96 @@
+//! Measures typed calls and field accesses, run with `cargo bench -p tests`.
+//!
+//! Tuple arguments use descriptors built at compile time, slices of `&dyn DynArg` build them at runtime.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use typed_jni::{
+    DynArg, LocalClass, TypedCallExt, TypedClassExt, TypedFieldAccessExt,
+    core::{JNIEnv, JavaVM},
+    define_java_class,
+};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ITERATIONS: usize = 100_000;
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    // warm up caches
+    for _ in 0..1000 {
+        f();
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        f();
+    }
+
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+    println!(
+        "{name:<40} {:>8.1} ns/iter {:>6.2} allocs/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64,
+        allocations as f64 / ITERATIONS as f64
+    );
+}
+
+define_java_class!(JavaMath, "java.lang.Math");
+define_java_class!(JavaInteger, "java.lang.Integer");
+
+fn run(env: &JNIEnv) {
+    let c_math: LocalClass<JavaMath> = env.typed_find_class().unwrap();
+    let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+
+    bench("call Math.max (const descriptor)", || {
+        let ret: i32 = env.typed_call_method(&c_math, "max", (black_box(1i32), 2i32)).unwrap();
+        black_box(ret);
+    });
+
+    bench("call Math.max (runtime descriptor)", || {
+        let args: &[&dyn DynArg] = &[&black_box(1i32), &2i32];
+        let ret: i32 = env.typed_call_method(&c_math, "max", args).unwrap();
+        black_box(ret);
+    });
+
+    bench("get Integer.MAX_VALUE (const descriptor)", || {
+        let ret: i32 = env.typed_get_field(&c_integer, "MAX_VALUE").unwrap();
+        black_box(ret);
+    });
+
+    bench("find class java.lang.Integer", || {
+        let cls: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        black_box(cls);
+    });
+}
+
+fn main() {
+    let vm = jni::JavaVM::new(jni::InitArgsBuilder::new().build().unwrap()).unwrap();
+    let vm: &JavaVM = unsafe { JavaVM::from_raw(vm.get_java_vm_pointer() as _) };
+
+    vm.with_attached_thread(false, run).unwrap();
+}
@@ -1,6 +1,7 @@
 use typed_jni::{
-    Array, LocalClass, LocalObject, TypedArrayExt, TypedCallExt, TypedClassExt, TypedObjectArrayExt, TypedPrimitiveArrayExt,
-    TypedStringExt, builtin::JavaString,
+    Array, JValue, LocalClass, LocalObject, ObjectType, TypedArrayExt, TypedCallExt, TypedClassExt, TypedDynamicCallExt,
+    TypedNestedArrayExt, TypedObjectArrayExt, TypedObjectExt, TypedPrimitiveArrayExt, TypedRefExt, TypedStringExt,
+    builtin::JavaString, core::JNIEnv, define_java_class,
 };
 
 use crate::with_java_vm;
@@ -113,3 +114,210 @@ fn test_bytes_access() {
         assert_eq!(&*env.typed_get_bytes_array_elements(&array).unwrap(), s.as_bytes());
     })
 }
+
+define_java_class!(JavaArrays, "java.util.Arrays");
+
+fn deep_to_string<T: ObjectType>(env: &JNIEnv, c_arrays: &LocalClass<JavaArrays>, array: &LocalObject<T>) -> String {
+    let array = env.typed_new_local_ref(array);
+
+    match env
+        .typed_call_method_overloaded(c_arrays, "deepToString", &[array.into()])
+        .unwrap()
+    {
+        JValue::Object(Some(s)) => env.typed_to_string(&s).unwrap(),
+        _ => panic!("deepToString returned non-string"),
+    }
+}
+
+#[test]
+fn test_nested_arrays() {
+    with_java_vm(|env| {
+        let c_arrays: LocalClass<JavaArrays> = env.typed_find_class().unwrap();
+
+        let ints = vec![vec![1, 2, 3], vec![], vec![4]];
+        let o_ints: LocalObject<Array<Array<i32>>> = env.typed_new_nested_array(&ints).unwrap();
+        assert_eq!(deep_to_string(env, &c_arrays, &o_ints), "[[1, 2, 3], [], [4]]");
+        assert_eq!(env.typed_get_nested_array::<Vec<i32>, _>(&o_ints).unwrap(), ints);
+
+        let strings = vec![vec![Some("a".to_string()), None], vec![Some("b".to_string())]];
+        let o_strings: LocalObject<Array<Array<JavaString>>> = env.typed_new_nested_array(&strings).unwrap();
+        assert_eq!(deep_to_string(env, &c_arrays, &o_strings), "[[a, null], [b]]");
+        assert_eq!(
+            env.typed_get_nested_array::<Vec<Option<String>>, _>(&o_strings).unwrap(),
+            strings
+        );
+
+        let err = env.typed_get_nested_array::<Vec<String>, _>(&o_strings).unwrap_err();
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "null element in array");
+
+        let bytes = vec![vec![vec![1i8, 2], vec![3]], vec![vec![-4]]];
+        let o_bytes: LocalObject<Array<Array<Array<i8>>>> = env.typed_new_nested_array(&bytes).unwrap();
+        assert_eq!(deep_to_string(env, &c_arrays, &o_bytes), "[[[1, 2], [3]], [[-4]]]");
+        assert_eq!(env.typed_get_nested_array::<Vec<Vec<i8>>, _>(&o_bytes).unwrap(), bytes);
+    })
+}
+
+#[test]
+fn test_large_nested_array() {
+    with_java_vm(|env| {
+        let matrix = (0..4096)
+            .map(|i| (0..4).map(|j| format!("{i}:{j}")).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let o_matrix: LocalObject<Array<Array<JavaString>>> = env.typed_new_nested_array(&matrix).unwrap();
+        assert_eq!(env.typed_get_array_length(&o_matrix).unwrap(), 4096);
+        assert_eq!(env.typed_get_nested_array::<Vec<String>, _>(&o_matrix).unwrap(), matrix);
+    })
+}
+
+#[test]
+fn test_primitive_array_conversions() {
+    with_java_vm(|env| {
+        let ints = env.typed_new_array_from_slice(&[1, 2, 3]).unwrap();
+        assert_eq!(env.typed_array_to_vec(&ints).unwrap(), [1, 2, 3]);
+
+        let doubles = env.typed_new_array_from_iter((0..4).map(|v| v as f64 / 2.0)).unwrap();
+        assert_eq!(env.typed_array_to_vec(&doubles).unwrap(), [0.0, 0.5, 1.0, 1.5]);
+
+        let empty = env.typed_new_array_from_slice::<i64>(&[]).unwrap();
+        assert_eq!(env.typed_get_array_length(&empty).unwrap(), 0);
+        assert!(env.typed_array_to_vec(&empty).unwrap().is_empty());
+
+        let bytes = env.typed_new_bytes_array(&[0, 127, 128, 255]).unwrap();
+        assert_eq!(env.typed_array_to_vec(&bytes).unwrap(), [0, 127, -128, -1]);
+        assert_eq!(env.typed_bytes_array_to_vec(&bytes).unwrap(), [0, 127, 128, 255]);
+    })
+}
+
+#[test]
+fn test_bool_array_raw_jbooleans() {
+    with_java_vm(|env| {
+        let bools = env.typed_new_array_from_slice(&[true, false, true]).unwrap();
+        assert_eq!(env.typed_array_to_vec(&bools).unwrap(), [true, false, true]);
+
+        // write a jboolean other than JNI_TRUE, as native code might do
+        let mut elements = env.typed_get_array_elements(&bools).unwrap();
+        elements[1] = 2;
+        elements.commit();
+
+        let values = env.typed_array_to_vec(&bools).unwrap();
+        assert_eq!(values.iter().map(|&v| v as u8).collect::<Vec<_>>(), [1, 1, 1]);
+
+        let elements = env.typed_get_array_elements(&bools).unwrap();
+        assert_eq!(*elements, [1, 2, 1]);
+    })
+}
+
+#[test]
+fn test_char_array_conversions() {
+    with_java_vm(|env| {
+        let s = "Hello你好😀";
+
+        let chars = env.typed_new_char_array_from_str(s).unwrap();
+        assert_eq!(env.typed_get_array_length(&chars).unwrap(), s.encode_utf16().count() as i32);
+        assert_eq!(env.typed_char_array_to_string(&chars).unwrap(), s);
+
+        let java_s: LocalObject<JavaString> = env
+            .typed_new_object(&env.typed_find_class::<JavaString>().unwrap(), (&chars,))
+            .unwrap();
+        assert_eq!(env.typed_get_string(&java_s), s);
+
+        let chars = env.typed_new_char_array_from_chars(&['a', '😀', 'b']).unwrap();
+        assert_eq!(env.typed_array_to_vec(&chars).unwrap(), [0x61, 0xd83d, 0xde00, 0x62]);
+        assert_eq!(env.typed_char_array_to_chars(&chars).unwrap(), ['a', '😀', 'b']);
+
+        let unpaired = env.typed_new_array_from_slice(&[0x61u16, 0xd83d, 0x62, 0xde00]).unwrap();
+        assert_eq!(env.typed_char_array_to_string(&unpaired).unwrap(), "a\u{fffd}b\u{fffd}");
+    })
+}
+
+#[test]
+fn test_iter_object_array() {
+    with_java_vm(|env| {
+        let c_string: LocalClass<JavaString> = env.typed_find_class().unwrap();
+
+        let strings = ["a", "b", "c"];
+        let o_array = env
+            .typed_collect_array(&c_string, strings.iter().map(|s| env.typed_new_string(s)))
+            .unwrap();
+
+        let values = env
+            .typed_iter_array_elements(&o_array)
+            .unwrap()
+            .map(|s| env.typed_get_string(&s.unwrap().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(values, strings);
+
+        let reversed = env
+            .typed_iter_array_elements(&o_array)
+            .unwrap()
+            .rev()
+            .map(|s| env.typed_get_string(&s.unwrap().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(reversed, ["c", "b", "a"]);
+
+        let nullable = [Some("x"), None, Some("y")];
+        let o_nullable = env
+            .typed_collect_array(&c_string, nullable.iter().map(|s| s.map(|s| env.typed_new_string(s))))
+            .unwrap();
+
+        let elements = env.typed_iter_array_elements(&o_nullable).unwrap();
+        assert_eq!(elements.len(), 3);
+
+        let values = elements
+            .map(|s| s.unwrap().map(|s| env.typed_get_string(&s)))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [Some("x".to_string()), None, Some("y".to_string())]);
+    })
+}
+
+#[test]
+fn test_for_each_array_element_in_frame() {
+    with_java_vm(|env| {
+        let c_string: LocalClass<JavaString> = env.typed_find_class().unwrap();
+
+        let len = 8192;
+        let o_array = env
+            .typed_collect_array(
+                &c_string,
+                (0..len).map(|i| (i % 2 == 0).then(|| env.typed_new_string(i.to_string()))),
+            )
+            .unwrap();
+
+        let mut visited = 0;
+        let mut total = 0;
+        unsafe {
+            env.typed_for_each_array_element_in_frame(&o_array, |env, index, s| {
+                assert_eq!(index, visited);
+                visited += 1;
+
+                if let Some(s) = s {
+                    // leaked on purpose, released with the frame
+                    let s = env.typed_new_string(env.typed_get_string(&s));
+                    core::mem::forget(env.typed_new_local_ref(&s));
+
+                    total += env.typed_get_string(&s).parse::<i32>().unwrap();
+                }
+
+                Ok(())
+            })
+        }
+        .unwrap();
+        assert_eq!(visited, len);
+        assert_eq!(total, (0..len).step_by(2).sum::<i32>());
+
+        let err = unsafe {
+            env.typed_for_each_array_element_in_frame(&o_array, |env, index, _| {
+                if index == 100 {
+                    Err(env.typed_new_object(&env.typed_find_class().unwrap(), (env.typed_new_string("stopped"),))?)
+                } else {
+                    Ok(())
+                }
+            })
+        }
+        .unwrap_err();
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "stopped");
+    })
+}
@@ -1,5 +1,7 @@
 use typed_jni::{
-    DynArg, LocalClass, LocalObject, Null, TypedCallExt, TypedClassExt, TypedObjectExt, TypedStringExt, builtin::JavaString,
+    DynArg, LocalClass, LocalObject, Null, ToArg, TypedCallExt, TypedClassExt, TypedFieldAccessExt, TypedObjectExt,
+    TypedStringExt, VarArgs,
+    builtin::{JavaObject, JavaString},
     define_java_class,
 };
 
@@ -667,3 +669,103 @@ fn test_object_creation() {
         assert!(exception_class_name.contains("IllegalArgumentException"));
     })
 }
+
+#[test]
+fn test_call_varargs() {
+    with_java_vm(|env| {
+        define_java_class!(JavaTest, "Test");
+        define_java_class!(JavaArrays, "java.util.Arrays");
+        define_java_class!(JavaList, "java.util.List");
+        define_java_class!(JavaInteger, "java.lang.Integer");
+
+        let (_dir, loader) = compile_file_and_load_classes(
+            env,
+            "Test",
+            r#"public class Test {
+                public final String joined;
+
+                public Test(String... parts) {
+                    joined = String.join("+", parts);
+                }
+
+                public static int sum(int... values) {
+                    int ret = 0;
+                    for (int v : values) {
+                        ret += v;
+                    }
+                    return ret;
+                }
+
+                public static String join(String separator, String... parts) {
+                    return parts.length + ":" + String.join(separator, parts);
+                }
+            }"#,
+        );
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let sum: i32 = env
+            .typed_call_method(&c_test, "sum", (VarArgs::<i32, _>::new([1, 2, 3]),))
+            .unwrap();
+        assert_eq!(sum, 6);
+
+        let sum: i32 = env
+            .typed_call_method(&c_test, "sum", (VarArgs::<i32, _>::new(core::iter::empty::<i32>()),))
+            .unwrap();
+        assert_eq!(sum, 0);
+
+        let parts = [env.typed_new_string("a"), env.typed_new_string("b")];
+        let joined: LocalObject<JavaString> = env
+            .typed_call_method(
+                &c_test,
+                "join",
+                (env.typed_new_string(", "), VarArgs::<JavaString, _>::new(&parts)),
+            )
+            .unwrap();
+        assert_eq!(env.typed_get_string(&joined), "2:a, b");
+
+        let o_test = env
+            .typed_new_object(&c_test, (VarArgs::<JavaString, _>::new(parts.iter().rev()),))
+            .unwrap();
+        let joined: LocalObject<JavaString> = env.typed_get_field(&o_test, "joined").unwrap();
+        assert_eq!(env.typed_get_string(&joined), "b+a");
+
+        let c_string: LocalClass<JavaString> = env.typed_find_class().unwrap();
+        let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        let count: LocalObject<JavaInteger> = env.typed_call_method(&c_integer, "valueOf", (3i32,)).unwrap();
+        let formatted: LocalObject<JavaString> = env
+            .typed_call_method(
+                &c_string,
+                "format",
+                (
+                    env.typed_new_string("%s has %d items"),
+                    VarArgs::<JavaObject, _>::new([&parts[0] as &dyn ToArg, &count]),
+                ),
+            )
+            .unwrap();
+        assert_eq!(env.typed_get_string(&formatted), "a has 3 items");
+
+        let c_arrays: LocalClass<JavaArrays> = env.typed_find_class().unwrap();
+        let list: LocalObject<JavaList> = env
+            .typed_call_method(
+                &c_arrays,
+                "asList",
+                (VarArgs::<JavaObject, _>::new([
+                    &parts[1] as &dyn ToArg,
+                    &Null::<JavaObject>::NULL,
+                ]),),
+            )
+            .unwrap();
+        assert_eq!(env.typed_to_string(&list).unwrap(), "[b, null]");
+
+        let Err(err) = env.typed_call_method::<LocalObject<JavaList>, _, _>(
+            &c_arrays,
+            "asList",
+            (VarArgs::<JavaObject, _>::new([&1i32 as &dyn ToArg]),),
+        ) else {
+            panic!("int varargs of Object succeeded");
+        };
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "varargs element 0 is not java.lang.Object");
+    })
+}
@@ -0,0 +1,253 @@
+use typed_jni::{
+    JValue, LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedDynamicCallExt, TypedFieldAccessExt, TypedObjectExt,
+    TypedStringExt,
+    builtin::{JavaObject, JavaString, JavaThrowable},
+    core::JNIEnv,
+    define_java_class,
+};
+
+use crate::{compile_file_and_load_classes, with_java_vm};
+
+define_java_class!(JavaTest, "Test");
+define_java_class!(JavaArguments, "Arguments");
+define_java_class!(JavaInteger, "java.lang.Integer");
+
+const SOURCE: &str = r#"public class Test {
+    public final String via;
+
+    public Test(int value) {
+        via = "int";
+    }
+
+    public Test(Object value) {
+        via = "Object";
+    }
+
+    public long add(int a, long b) {
+        return a + b;
+    }
+
+    public static String describe(Object o) {
+        return o.getClass().getSimpleName() + ":" + o;
+    }
+
+    public static int twice(int v) {
+        return v * 2;
+    }
+
+    public static String f(int v) { return "int"; }
+    public static String f(long v) { return "long"; }
+    public static String f(Object v) { return "Object"; }
+    public static String f(String v) { return "String"; }
+    public static String f(Integer v) { return "Integer"; }
+
+    public void nothing() {
+    }
+}"#;
+
+const ARGUMENTS_SOURCE: &str = r#"public class Arguments {
+    public static String self(Arguments a) { return "Arguments"; }
+    public static String string(String s) { return s; }
+    public static int length(String[] a) { return a.length; }
+}"#;
+
+fn string_of(env: &JNIEnv, value: JValue) -> String {
+    let JValue::Object(Some(obj)) = value else {
+        panic!("value is not an object");
+    };
+
+    env.typed_to_string(&obj).unwrap()
+}
+
+fn message_of(env: &JNIEnv, err: &LocalObject<JavaThrowable>) -> String {
+    let message: LocalObject<JavaString> = env.typed_call_method(err, "getMessage", ()).unwrap();
+
+    env.typed_get_string(&message)
+}
+
+fn class_name_of(env: &JNIEnv, err: &LocalObject<JavaThrowable>) -> String {
+    env.typed_to_string(&env.typed_get_object_class(err)).unwrap()
+}
+
+#[test]
+fn test_call_method_dynamic() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Test", SOURCE);
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        let o_test = env.typed_new_object_dynamic(&c_test, "(I)V", &[JValue::Int(1)]).unwrap();
+
+        let via: LocalObject<JavaString> = env.typed_get_field(&o_test, "via").unwrap();
+        assert_eq!(env.typed_get_string(&via), "int");
+
+        let ret = env
+            .typed_call_method_dynamic(&o_test, "add", "(IJ)J", &[JValue::Short(2), JValue::Int(40)])
+            .unwrap();
+        assert!(matches!(ret, JValue::Long(42)));
+
+        let ret = env.typed_call_method_dynamic(&o_test, "nothing", "()V", &[]).unwrap();
+        assert!(matches!(ret, JValue::Void));
+
+        let ret = env
+            .typed_call_method_dynamic(&c_test, "describe", "(Ljava/lang/Object;)Ljava/lang/String;", &[3i32.into()])
+            .unwrap();
+        assert_eq!(string_of(env, ret), "Integer:3");
+
+        let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        let boxed: LocalObject<JavaInteger> = env.typed_call_method(&c_integer, "valueOf", (21i32,)).unwrap();
+        let ret = env
+            .typed_call_method_dynamic(&c_test, "twice", "(I)I", &[boxed.into()])
+            .unwrap();
+        assert!(matches!(ret, JValue::Int(42)));
+
+        let Err(err) = env.typed_call_method_dynamic(&c_test, "twice", "(I", &[]) else {
+            panic!("invalid descriptor succeeded");
+        };
+        assert_eq!(class_name_of(env, &err), "class java.lang.IllegalArgumentException");
+        assert_eq!(
+            message_of(env, &err),
+            "invalid method descriptor \"(I\": unexpected end at byte 2"
+        );
+
+        let Err(err) = env.typed_call_method_dynamic(&c_test, "twice", "(I)I", &[]) else {
+            panic!("missing argument succeeded");
+        };
+        assert_eq!(message_of(env, &err), "(I)I: expected 1 arguments, got 0");
+
+        let Err(err) = env.typed_call_method_dynamic(&c_test, "twice", "(I)I", &[JValue::Long(1)]) else {
+            panic!("narrowing succeeded");
+        };
+        assert_eq!(message_of(env, &err), "(I)I: cannot convert argument 0 of long to int");
+
+        let s: JValue = env.typed_new_string("str").into();
+        let Err(err) = env.typed_call_method_dynamic(&c_test, "twice", "(I)I", &[s]) else {
+            panic!("unboxing string succeeded");
+        };
+        assert_eq!(
+            message_of(env, &err),
+            "(I)I: cannot convert argument 0 of java.lang.String to int"
+        );
+    })
+}
+
+#[test]
+fn test_call_method_dynamic_object_arguments() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Arguments", ARGUMENTS_SOURCE);
+
+        let c_arguments: LocalClass<JavaArguments> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        let o_arguments = env.typed_new_object_dynamic(&c_arguments, "()V", &[]).unwrap();
+
+        let ret = env
+            .typed_call_method_dynamic(&c_arguments, "self", "(LArguments;)Ljava/lang/String;", &[o_arguments.into()])
+            .unwrap();
+        assert_eq!(string_of(env, ret), "Arguments");
+
+        let ret = env
+            .typed_call_method_dynamic(
+                &c_arguments,
+                "string",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                &[JValue::Object(None)],
+            )
+            .unwrap();
+        assert!(matches!(ret, JValue::Object(None)));
+
+        let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        let boxed: LocalObject<JavaInteger> = env.typed_call_method(&c_integer, "valueOf", (1i32,)).unwrap();
+        let Err(err) = env.typed_call_method_dynamic(
+            &c_arguments,
+            "string",
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            &[boxed.into()],
+        ) else {
+            panic!("passing Integer as String succeeded");
+        };
+        assert_eq!(class_name_of(env, &err), "class java.lang.IllegalArgumentException");
+        assert_eq!(
+            message_of(env, &err),
+            "(Ljava/lang/String;)Ljava/lang/String;: cannot convert argument 0 of java.lang.Integer to java.lang.String"
+        );
+
+        let Err(err) = env.typed_call_method_dynamic(
+            &c_arguments,
+            "string",
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            &[JValue::Int(1)],
+        ) else {
+            panic!("boxing int as String succeeded");
+        };
+        assert_eq!(
+            message_of(env, &err),
+            "(Ljava/lang/String;)Ljava/lang/String;: cannot convert argument 0 of int to java.lang.String"
+        );
+
+        let s: JValue = env.typed_new_string("str").into();
+        let Err(err) = env.typed_call_method_dynamic(&c_arguments, "length", "([Ljava/lang/String;)I", &[s]) else {
+            panic!("passing String as String[] succeeded");
+        };
+        assert_eq!(
+            message_of(env, &err),
+            "([Ljava/lang/String;)I: cannot convert argument 0 of java.lang.String to java.lang.String[]"
+        );
+    })
+}
+
+#[test]
+fn test_call_method_overloaded() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Test", SOURCE);
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let call = |arg: JValue| -> String {
+            let ret = env.typed_call_method_overloaded(&c_test, "f", &[arg]).unwrap();
+
+            string_of(env, ret)
+        };
+
+        assert_eq!(call(JValue::Byte(1)), "int");
+        assert_eq!(call(JValue::Char(1)), "int");
+        assert_eq!(call(JValue::Long(1)), "long");
+        assert_eq!(call(JValue::Boolean(true)), "Object");
+        assert_eq!(call(env.typed_new_string("s").into()), "String");
+
+        let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        let boxed: LocalObject<JavaInteger> = env.typed_call_method(&c_integer, "valueOf", (1i32,)).unwrap();
+        assert_eq!(call(boxed.into()), "Integer");
+
+        let sig = env.typed_find_overload(&c_test, "f", &[JValue::Float(1.0)]).unwrap();
+        assert_eq!(sig.to_string(), "(Ljava/lang/Object;)Ljava/lang/String;");
+
+        let sig = env.typed_find_overload(&c_test, "twice", &[JValue::Short(1)]).unwrap();
+        assert_eq!(sig.to_string(), "(I)I");
+
+        let Err(err) = env.typed_find_overload(&c_test, "f", &[JValue::Object(None)]) else {
+            panic!("ambiguous overloads succeeded");
+        };
+        assert_eq!(class_name_of(env, &err), "class java.lang.IllegalArgumentException");
+        assert_eq!(
+            message_of(env, &err),
+            "ambiguous overloads (Ljava/lang/String;)Ljava/lang/String;, (Ljava/lang/Integer;)Ljava/lang/String; for Test.f(null)"
+        );
+
+        let Err(err) = env.typed_find_overload(&c_test, "twice", &[JValue::Double(1.0)]) else {
+            panic!("narrowing overload succeeded");
+        };
+        assert_eq!(class_name_of(env, &err), "class java.lang.NoSuchMethodError");
+        assert_eq!(message_of(env, &err), "no applicable overload for Test.twice(double)");
+
+        let o_test = env.typed_new_object_overloaded(&c_test, &[JValue::Long(1)]).unwrap();
+        let via: LocalObject<JavaString> = env.typed_get_field(&o_test, "via").unwrap();
+        assert_eq!(env.typed_get_string(&via), "Object");
+
+        let ret = env
+            .typed_call_method_overloaded(&o_test, "add", &[JValue::Byte(1), JValue::Int(2)])
+            .unwrap();
+        assert!(matches!(ret, JValue::Long(3)));
+
+        let o_object: LocalObject<JavaObject> = o_test.into_object();
+        let ret = env.typed_call_method_overloaded(&o_object, "hashCode", &[]).unwrap();
+        assert!(matches!(ret, JValue::Int(_)));
+    })
+}
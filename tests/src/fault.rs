@@ -0,0 +1,85 @@
+use typed_jni::{
+    LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedObjectExt, TypedStringExt,
+    builtin::{JavaString, JavaThrowable},
+    core::testing::{Fault, FaultInjector, FaultRule},
+    define_java_class,
+};
+
+use crate::with_java_vm;
+
+define_java_class!(JavaInteger, "java.lang.Integer");
+define_java_class!(JavaOutOfMemoryError, "java.lang.OutOfMemoryError");
+
+#[test]
+fn test_fault_find_class() {
+    with_java_vm(|env| {
+        let faults = FaultInjector::new(env);
+        faults.inject(FaultRule::new("FindClass", Fault::throw("java/lang/NoClassDefFoundError", "injected")).times(1));
+
+        let env = faults.env();
+
+        let Err(err) = env.typed_find_class::<JavaInteger>() else {
+            panic!("find class succeeded with injected fault");
+        };
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "injected");
+
+        assert!(env.typed_find_class::<JavaInteger>().is_ok());
+        assert_eq!(faults.injected(), 1);
+    })
+}
+
+#[test]
+fn test_fault_new_global_ref() {
+    with_java_vm(|env| {
+        let faults = FaultInjector::new(env);
+        faults.inject(FaultRule::new("NewGlobalRef", Fault::Fail));
+
+        let env = faults.env();
+
+        let s = env.typed_new_string("global");
+        assert!(env.new_global_ref(&*s).is_none());
+
+        faults.clear();
+
+        assert!(env.new_global_ref(&*s).is_some());
+    })
+}
+
+#[test]
+fn test_fault_out_of_memory() {
+    with_java_vm(|env| {
+        let faults = FaultInjector::new(env);
+        faults.inject(FaultRule::new("CallStaticIntMethodA", Fault::out_of_memory()).skip(1));
+
+        let env = faults.env();
+
+        let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        let ret: i32 = env
+            .typed_call_method(&c_integer, "parseInt", (env.typed_new_string("42"),))
+            .unwrap();
+        assert_eq!(ret, 42);
+
+        let err = env
+            .typed_call_method::<i32, _, _>(&c_integer, "parseInt", (env.typed_new_string("42"),))
+            .unwrap_err();
+        let c_oom: LocalClass<JavaOutOfMemoryError> = env.typed_find_class().unwrap();
+        let c_throwable: LocalClass<JavaThrowable> = env.typed_find_class().unwrap();
+        assert!(env.typed_is_instance_of(&err, &c_oom));
+        assert!(env.typed_is_instance_of(&err, &c_throwable));
+    })
+}
+
+#[test]
+fn test_fault_probability() {
+    with_java_vm(|env| {
+        let faults = FaultInjector::new(env);
+        faults.inject(FaultRule::new("FindClass", Fault::out_of_memory()).probability(0.5, 42));
+
+        let env = faults.env();
+
+        let failed = (0..64).filter(|_| env.find_class(c"java/lang/Integer").is_err()).count();
+        assert!(failed > 0 && failed < 64);
+        assert_eq!(faults.injected(), failed);
+    })
+}
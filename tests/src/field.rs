@@ -1,6 +1,8 @@
+use std::sync::OnceLock;
+
 use typed_jni::{
-    LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedFieldAccessExt, TypedStringExt, builtin::JavaString,
-    define_java_class,
+    FieldHandle, LocalClass, LocalObject, Null, TypedCallExt, TypedClassExt, TypedFieldAccessExt, TypedObjectExt, TypedRefExt,
+    TypedStringExt, builtin::JavaString, define_java_class,
 };
 
 use crate::{compile_file_and_load_classes, with_java_vm};
@@ -273,3 +275,128 @@ fn test_nullable_field_handling() {
         assert_eq!(env.typed_get_string(&now_not_null_result.unwrap()), "NowNotNull");
     })
 }
+
+#[test]
+fn test_field_handle() {
+    define_java_class!(JavaTest, "Test");
+
+    static VALUE: OnceLock<FieldHandle<JavaTest, i32, false>> = OnceLock::new();
+    static NAME: OnceLock<FieldHandle<JavaTest, JavaString, false>> = OnceLock::new();
+    static COUNT: OnceLock<FieldHandle<JavaTest, i64, true>> = OnceLock::new();
+
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(
+            env,
+            "Test",
+            r#"public class Test {
+                public static long count = 0;
+
+                public int value;
+                public String name;
+
+                public Test(int value) {
+                    this.value = value;
+                    count++;
+                }
+            }"#,
+        );
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let value = VALUE.get_or_init(|| FieldHandle::resolve_in(env, &c_test, "value").unwrap());
+        let name = NAME.get_or_init(|| FieldHandle::resolve_in(env, &c_test, "name").unwrap());
+        let count = COUNT.get_or_init(|| FieldHandle::resolve_in(env, &c_test, "count").unwrap());
+
+        let objects = (0..1000)
+            .map(|i| env.typed_new_object(&c_test, (i,)).unwrap())
+            .collect::<Vec<LocalObject<JavaTest>>>();
+        for obj in &objects {
+            let v: i32 = value.get(env, obj).unwrap();
+            value.set(env, obj, v * 2).unwrap();
+        }
+        let sum = objects.iter().map(|obj| value.get::<_, i32>(env, obj).unwrap()).sum::<i32>();
+        assert_eq!(sum, (0..1000).map(|i| i * 2).sum::<i32>());
+
+        let n: i64 = count.get(env).unwrap();
+        assert_eq!(n, 1000);
+        count.set(env, -1i64).unwrap();
+        let n: i64 = env.typed_get_field(&c_test, "count").unwrap();
+        assert_eq!(n, -1);
+
+        let obj = &objects[0];
+        let s: Option<LocalObject<JavaString>> = name.get(env, obj).unwrap();
+        assert!(s.is_none());
+        name.set(env, obj, env.typed_new_string("first")).unwrap();
+        let s: LocalObject<JavaString> = name.get(env, obj).unwrap();
+        assert_eq!(env.typed_get_string(&s), "first");
+        name.set(env, obj, Null::<JavaString>::NULL).unwrap();
+        let s: Option<LocalObject<JavaString>> = env.typed_get_field(obj, "name").unwrap();
+        assert!(s.is_none());
+
+        // shared with another thread
+        let vm = env.vm();
+        let o_shared = env.typed_new_global_ref(&objects[1]);
+        std::thread::spawn(move || {
+            vm.with_attached_thread(false, |env| {
+                let value = VALUE.get().unwrap();
+
+                let v: i32 = value.get(env, &o_shared).unwrap();
+                value.set(env, &o_shared, v + 40).unwrap();
+            })
+            .unwrap();
+        })
+        .join()
+        .unwrap();
+        let v: i32 = env.typed_get_field(&objects[1], "value").unwrap();
+        assert_eq!(v, 42);
+    })
+}
+
+#[test]
+fn test_field_handle_foreign_object() {
+    define_java_class!(JavaTest, "Test");
+
+    with_java_vm(|env| {
+        let source = "public class Test { public int value; }";
+        let (_dir, loader) = compile_file_and_load_classes(env, "Test", source);
+        let (_other_dir, other_loader) = compile_file_and_load_classes(env, "Test", source);
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        let c_other: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&other_loader).unwrap();
+
+        let value = FieldHandle::<JavaTest, i32, false>::resolve_in(env, &c_test, "value").unwrap();
+
+        let o_other = env.typed_new_object(&c_other, ()).unwrap();
+        for err in [
+            value.get::<_, i32>(env, &o_other).unwrap_err(),
+            value.set(env, &o_other, 1i32).unwrap_err(),
+        ] {
+            let c_error = env.typed_get_object_class(&err);
+            assert_eq!(
+                env.typed_to_string(&c_error).unwrap(),
+                "class java.lang.IllegalArgumentException"
+            );
+        }
+
+        let o_test = env.typed_new_object(&c_test, ()).unwrap();
+        value.set(env, &o_test, 1i32).unwrap();
+        assert_eq!(value.get::<_, i32>(env, &o_test).unwrap(), 1);
+    })
+}
+
+#[test]
+fn test_field_handle_not_found() {
+    define_java_class!(JavaTest, "Test");
+
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Test", "public class Test { public int value; }");
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let Err(err) = FieldHandle::<JavaTest, i64, false>::resolve_in(env, &c_test, "value") else {
+            panic!("field of mismatched type resolved");
+        };
+        let c_error = env.typed_get_object_class(&err);
+        assert_eq!(env.typed_to_string(&c_error).unwrap(), "class java.lang.NoSuchFieldError");
+    })
+}
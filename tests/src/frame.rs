@@ -0,0 +1,90 @@
+use typed_jni::{
+    Array, LocalObject, TypedArrayExt, TypedClassExt, TypedFrameExt, TypedObjectArrayExt, TypedObjectExt, TypedStringExt,
+    builtin::{JavaString, JavaThrowable},
+};
+
+use crate::with_java_vm;
+
+#[test]
+fn test_local_frame_escape_single() {
+    with_java_vm(|env| {
+        let s = unsafe {
+            env.typed_with_local_frame::<LocalObject<JavaString>, _>(4, |env| {
+                for i in 0..128 {
+                    let _ = env.typed_new_string(format!("temp: {i}"));
+                }
+
+                env.typed_new_string("escaped")
+            })
+        }
+        .unwrap();
+
+        assert_eq!(env.typed_get_string(&s), "escaped");
+    })
+}
+
+#[test]
+fn test_local_frame_escape_multiple() {
+    with_java_vm(|env| {
+        let (first, rest, none, length) = unsafe {
+            env.typed_with_local_frame::<(
+                LocalObject<JavaString>,
+                Vec<LocalObject<JavaString>>,
+                Option<LocalObject<JavaString>>,
+                usize,
+            ), _>(65, |env| {
+                let rest = (0..64)
+                    .map(|i| env.typed_new_string(format!("rest: {i}")))
+                    .collect::<Vec<_>>();
+
+                (env.typed_new_string("first"), rest, None, 64)
+            })
+        }
+        .unwrap();
+
+        assert_eq!(env.typed_get_string(&first), "first");
+        assert_eq!(rest.len(), length);
+        for (i, s) in rest.iter().enumerate() {
+            assert_eq!(env.typed_get_string(s), format!("rest: {i}"));
+        }
+        assert!(none.is_none());
+    })
+}
+
+#[test]
+fn test_local_frame_in_large_loop() {
+    with_java_vm(|env| {
+        let length = 100_000;
+
+        let array: LocalObject<Array<JavaString>> = env.typed_new_array(&env.typed_find_class().unwrap(), length).unwrap();
+
+        for i in 0..length {
+            unsafe {
+                env.typed_with_local_frame::<(), _>(4, |env| {
+                    env.typed_set_array_element(&array, i, Some(&env.typed_new_string(i.to_string())))
+                        .unwrap();
+                })
+            }
+            .unwrap();
+        }
+
+        let mut count = 0;
+        for i in 0..env.typed_get_array_length(&array).unwrap() {
+            let ret = unsafe {
+                env.typed_with_local_frame::<Result<i32, LocalObject<JavaThrowable>>, _>(4, |env| {
+                    let s = env.typed_get_array_element(&array, i)?.unwrap();
+                    let copied = env.typed_new_string(env.typed_get_string(&s));
+
+                    env.typed_hash_code(&copied)
+                })
+            }
+            .unwrap();
+
+            assert!(ret.is_ok());
+
+            count += 1;
+        }
+
+        assert_eq!(count, length);
+    })
+}
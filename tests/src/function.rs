@@ -0,0 +1,302 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use typed_jni::{
+    LocalClass, LocalObject, Null, TypedCallExt, TypedClassExt, TypedFrameExt, TypedFunctionExt, TypedHelperClassesExt,
+    TypedObjectExt, TypedStringExt,
+    builtin::{JavaObject, JavaString, JavaThrowable},
+    define_java_class,
+};
+
+use crate::{compile_file_and_load_classes, with_java_vm};
+
+define_java_class!(JavaSystem, "java.lang.System");
+define_java_class!(JavaInteger, "java.lang.Integer");
+
+#[test]
+fn test_function_runnable_and_suppliers() {
+    with_java_vm(|env| {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let o_runnable = env
+            .typed_new_runnable({
+                let counter = counter.clone();
+
+                move |_| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    Ok(())
+                }
+            })
+            .unwrap();
+        for _ in 0..16 {
+            env.typed_call_method::<(), _, _>(&o_runnable, "run", ()).unwrap();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 16);
+
+        let o_callable = env
+            .typed_new_callable(|env| Ok(Some(env.typed_new_string("callable").into_object())))
+            .unwrap();
+        let ret: LocalObject<JavaObject> = env.typed_call_method(&o_callable, "call", ()).unwrap();
+        let ret: LocalObject<JavaString> = env.typed_call_method(&ret, "toString", ()).unwrap();
+        assert_eq!(env.typed_get_string(&ret), "callable");
+
+        let o_supplier = env.typed_new_supplier(|_| Ok(None)).unwrap();
+        let ret: Option<LocalObject<JavaObject>> = env.typed_call_method(&o_supplier, "get", ()).unwrap();
+        assert!(ret.is_none());
+    })
+}
+
+#[test]
+fn test_function_consumers_and_functions() {
+    with_java_vm(|env| {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let o_consumer = env
+            .typed_new_consumer({
+                let counter = counter.clone();
+
+                move |env, value| {
+                    let value: i32 = env.typed_call_method(&value.unwrap(), "hashCode", ())?;
+
+                    counter.fetch_add(value as usize, Ordering::SeqCst);
+
+                    Ok(())
+                }
+            })
+            .unwrap();
+        let c_integer: LocalClass<JavaInteger> = env.typed_find_class().unwrap();
+        let o_value: LocalObject<JavaInteger> = env.typed_call_method(&c_integer, "valueOf", (42i32,)).unwrap();
+        env.typed_call_method::<(), _, _>(&o_consumer, "accept", (&o_value.into_object(),))
+            .unwrap();
+        assert_eq!(counter.load(Ordering::SeqCst), 42);
+
+        let o_bi_consumer = env
+            .typed_new_bi_consumer(|_, a, b| {
+                assert!(a.is_some());
+                assert!(b.is_none());
+
+                Ok(())
+            })
+            .unwrap();
+        env.typed_call_method::<(), _, _>(
+            &o_bi_consumer,
+            "accept",
+            (&env.typed_new_string("a").into_object(), Null::<JavaObject>::NULL),
+        )
+        .unwrap();
+
+        let o_function = env
+            .typed_new_function(|env, value| {
+                let value: LocalObject<JavaString> = env.typed_call_method(&value.unwrap(), "toString", ())?;
+
+                Ok(Some(
+                    env.typed_new_string(format!("{}!", env.typed_get_string(&value)))
+                        .into_object(),
+                ))
+            })
+            .unwrap();
+        let ret: LocalObject<JavaObject> = env
+            .typed_call_method(&o_function, "apply", (&env.typed_new_string("hello").into_object(),))
+            .unwrap();
+        let ret: LocalObject<JavaString> = env.typed_call_method(&ret, "toString", ()).unwrap();
+        assert_eq!(env.typed_get_string(&ret), "hello!");
+
+        let o_bi_function = env
+            .typed_new_bi_function(|env, a, b| {
+                let a: LocalObject<JavaString> = env.typed_call_method(&a.unwrap(), "toString", ())?;
+                let b: LocalObject<JavaString> = env.typed_call_method(&b.unwrap(), "toString", ())?;
+
+                Ok(Some(
+                    env.typed_new_string(format!("{}{}", env.typed_get_string(&a), env.typed_get_string(&b)))
+                        .into_object(),
+                ))
+            })
+            .unwrap();
+        let ret: LocalObject<JavaObject> = env
+            .typed_call_method(
+                &o_bi_function,
+                "apply",
+                (
+                    &env.typed_new_string("114").into_object(),
+                    &env.typed_new_string("514").into_object(),
+                ),
+            )
+            .unwrap();
+        let ret: LocalObject<JavaString> = env.typed_call_method(&ret, "toString", ()).unwrap();
+        assert_eq!(env.typed_get_string(&ret), "114514");
+    })
+}
+
+#[test]
+fn test_function_predicate() {
+    with_java_vm(|env| {
+        let o_predicate = env.typed_new_predicate(|_, value| Ok(value.is_some())).unwrap();
+
+        let ret: bool = env
+            .typed_call_method(&o_predicate, "test", (&env.typed_new_string("some").into_object(),))
+            .unwrap();
+        assert!(ret);
+
+        let ret: bool = env
+            .typed_call_method(&o_predicate, "test", (Null::<JavaObject>::NULL,))
+            .unwrap();
+        assert!(!ret);
+    })
+}
+
+#[test]
+fn test_function_throw_and_panic() {
+    with_java_vm(|env| {
+        let o_runnable = env
+            .typed_new_runnable(|env| {
+                define_java_class!(JavaIllegalStateException, "java.lang.IllegalStateException");
+
+                let ex = env.typed_new_object(
+                    &env.typed_find_class::<JavaIllegalStateException>()?,
+                    (env.typed_new_string("thrown from rust"),),
+                )?;
+
+                Err(env.typed_cast(&ex, &env.typed_find_class::<JavaThrowable>()?)?)
+            })
+            .unwrap();
+        let err = env.typed_call_method::<(), _, _>(&o_runnable, "run", ()).unwrap_err();
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "thrown from rust");
+
+        let o_runnable = env.typed_new_runnable(|_| panic!("boom")).unwrap();
+        let err = env.typed_call_method::<(), _, _>(&o_runnable, "run", ()).unwrap_err();
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "rust panic: boom");
+    })
+}
+
+#[test]
+fn test_function_release_panic_reported() {
+    struct PanicOnDrop;
+
+    impl Drop for PanicOnDrop {
+        fn drop(&mut self) {
+            panic!("dropped");
+        }
+    }
+
+    define_java_class!(JavaReporter, "Reporter");
+
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(
+            env,
+            "Reporter",
+            r#"public class Reporter {
+                private static volatile String reported;
+                private static Thread.UncaughtExceptionHandler previous;
+
+                public static void install() {
+                    previous = Thread.getDefaultUncaughtExceptionHandler();
+
+                    Thread.setDefaultUncaughtExceptionHandler((thread, e) -> reported = e.getMessage());
+                }
+
+                public static String uninstall() {
+                    Thread.setDefaultUncaughtExceptionHandler(previous);
+
+                    return reported;
+                }
+
+                public static boolean isReported() {
+                    return reported != null;
+                }
+            }"#,
+        );
+        let c_reporter: LocalClass<JavaReporter> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        env.typed_call_method::<(), _, _>(&c_reporter, "install", ()).unwrap();
+
+        unsafe {
+            env.typed_with_local_frame::<(), _>(4, |env| {
+                let value = PanicOnDrop;
+
+                env.typed_new_runnable(move |_| {
+                    let _ = &value;
+
+                    Ok(())
+                })
+                .unwrap();
+            })
+        }
+        .unwrap();
+
+        let c_system: LocalClass<JavaSystem> = env.typed_find_class().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !env.typed_call_method::<bool, _, _>(&c_reporter, "isReported", ()).unwrap() {
+            assert!(Instant::now() < deadline, "release panic is not reported");
+
+            env.typed_call_method::<(), _, _>(&c_system, "gc", ()).unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let reported: LocalObject<JavaString> = env.typed_call_method(&c_reporter, "uninstall", ()).unwrap();
+        assert_eq!(env.typed_get_string(&reported), "rust panic: dropped");
+    })
+}
+
+#[test]
+fn test_function_released_after_gc() {
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    with_java_vm(|env| {
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        unsafe {
+            env.typed_with_local_frame::<(), _>(4, |env| {
+                let flag = DropFlag(dropped.clone());
+
+                env.typed_new_runnable(move |_| {
+                    let _ = &flag;
+
+                    Ok(())
+                })
+                .unwrap();
+            })
+        }
+        .unwrap();
+
+        let c_system: LocalClass<JavaSystem> = env.typed_find_class().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !dropped.load(Ordering::SeqCst) {
+            assert!(Instant::now() < deadline, "runnable closure is not released");
+
+            env.typed_call_method::<(), _, _>(&c_system, "gc", ()).unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    })
+}
+
+#[test]
+fn test_helper_classes_class_loader() {
+    with_java_vm(|env| {
+        let o_runnable = env.typed_new_runnable(|_| Ok(())).unwrap();
+
+        let o_loader = env
+            .typed_get_class_loader(&env.typed_get_object_class(&o_runnable))
+            .unwrap()
+            .expect("helper classes defined in the bootstrap class loader");
+
+        let err = env.typed_init_helper_classes(&o_loader).unwrap_err();
+        let c_error = env.typed_get_object_class(&err);
+        assert_eq!(
+            env.typed_to_string(&c_error).unwrap(),
+            "class java.lang.IllegalStateException"
+        );
+    })
+}
@@ -0,0 +1,150 @@
+use std::{
+    future::{Future, poll_fn},
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+    time::Duration,
+};
+
+use typed_jni::{
+    GlobalObject, LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedFutureExt, TypedObjectExt, TypedRefExt,
+    TypedStringExt,
+    builtin::{JavaCompletableFuture, JavaObject, JavaString, JavaThrowable},
+};
+
+use crate::with_java_vm;
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct Unparker(Thread);
+
+    impl Wake for Unparker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unparker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_rust_future_to_completable_future() {
+    with_java_vm(|env| {
+        let o_future = env
+            .typed_new_completable_future(async { Ok::<_, String>(String::from("ready")) })
+            .unwrap();
+        let o_value: LocalObject<JavaObject> = env.typed_call_method(&o_future, "join", ()).unwrap();
+        assert_eq!(env.typed_to_string(&o_value).unwrap(), "ready");
+
+        let o_future = env.typed_new_completable_future(async { Err::<(), _>("failed") }).unwrap();
+        let failed: bool = env.typed_call_method(&o_future, "isCompletedExceptionally", ()).unwrap();
+        assert!(failed);
+    })
+}
+
+#[test]
+fn test_rust_future_completes_on_other_thread() {
+    with_java_vm(|env| {
+        let slot: Arc<Mutex<(Option<i32>, Option<Waker>)>> = Default::default();
+
+        let o_future = env
+            .typed_new_completable_future({
+                let slot = slot.clone();
+
+                poll_fn(move |cx| {
+                    let mut slot = slot.lock().unwrap();
+
+                    match slot.0.take() {
+                        Some(v) => Poll::Ready(Ok::<_, String>(v)),
+                        None => {
+                            slot.1 = Some(cx.waker().clone());
+
+                            Poll::Pending
+                        }
+                    }
+                })
+            })
+            .unwrap();
+
+        let done: bool = env.typed_call_method(&o_future, "isDone", ()).unwrap();
+        assert!(!done);
+
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+
+            let waker = {
+                let mut slot = slot.lock().unwrap();
+                slot.0 = Some(114514);
+                slot.1.take().unwrap()
+            };
+
+            waker.wake();
+        });
+
+        let o_value: LocalObject<JavaObject> = env.typed_call_method(&o_future, "get", ()).unwrap();
+        assert_eq!(env.typed_to_string(&o_value).unwrap(), "114514");
+    })
+}
+
+#[test]
+fn test_completable_future_to_rust_future() {
+    with_java_vm(|env| {
+        let c_future: LocalClass<JavaCompletableFuture> = env.typed_find_class().unwrap();
+        let o_future: GlobalObject<JavaCompletableFuture> =
+            env.typed_new_global_ref(&env.typed_new_object(&c_future, ()).unwrap());
+
+        let future = env.typed_into_future(&o_future).unwrap();
+
+        let vm = env.vm();
+        let completer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+
+            vm.with_attached_thread(false, |env| {
+                let completed: bool = env
+                    .typed_call_method(&o_future, "complete", (env.typed_new_string("completed").into_object(),))
+                    .unwrap();
+                assert!(completed);
+            })
+            .unwrap();
+        });
+
+        let value = block_on(future).unwrap().unwrap();
+        let value: LocalObject<JavaString> = env
+            .typed_cast(&value, &env.typed_find_class::<JavaString>().unwrap())
+            .unwrap();
+        assert_eq!(env.typed_get_string(&value), "completed");
+
+        completer.join().unwrap();
+    })
+}
+
+#[test]
+fn test_completable_future_to_rust_future_exceptionally() {
+    with_java_vm(|env| {
+        let c_future: LocalClass<JavaCompletableFuture> = env.typed_find_class().unwrap();
+        let o_future = env.typed_new_object(&c_future, ()).unwrap();
+
+        let future = env.typed_into_future(&o_future).unwrap();
+
+        let c_exception: LocalClass<JavaThrowable> = env.typed_find_class().unwrap();
+        let o_exception = env.typed_new_object(&c_exception, (env.typed_new_string("failed"),)).unwrap();
+        let completed: bool = env
+            .typed_call_method(&o_future, "completeExceptionally", (o_exception,))
+            .unwrap();
+        assert!(completed);
+
+        let Err(err) = block_on(future) else {
+            panic!("future should complete exceptionally");
+        };
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "failed");
+    })
+}
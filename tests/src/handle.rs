@@ -0,0 +1,115 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use typed_jni::{
+    LocalClass, LocalObject, NativeHandle, TypedCallExt, TypedClassExt, TypedFieldAccessExt, TypedFrameExt, TypedStringExt,
+    builtin::JavaString, define_java_class,
+};
+
+use crate::{compile_file_and_load_classes, with_java_vm};
+
+define_java_class!(JavaPeer, "Peer");
+define_java_class!(JavaSystem, "java.lang.System");
+
+const PEER: &str = r#"
+    public class Peer {
+        private long nativeHandle;
+    }
+"#;
+
+#[test]
+fn test_handle_store_borrow_take() {
+    static HANDLE: NativeHandle<Vec<i32>> = unsafe { NativeHandle::new("nativeHandle") };
+
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Peer", PEER);
+
+        let c_peer: LocalClass<JavaPeer> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        let o_peer = env.typed_new_object(&c_peer, ()).unwrap();
+
+        assert!(HANDLE.borrow(env, &o_peer).is_err());
+
+        HANDLE.store(env, &o_peer, vec![1, 2, 3]).unwrap();
+        assert_ne!(env.typed_get_field::<i64, _>(&o_peer, "nativeHandle").unwrap(), 0);
+
+        let err = HANDLE.store(env, &o_peer, vec![]).unwrap_err();
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "native handle nativeHandle is already set");
+
+        {
+            let a = HANDLE.borrow(env, &o_peer).unwrap();
+            let b = HANDLE.borrow(env, &o_peer).unwrap();
+            assert_eq!(*a, *b);
+
+            assert!(HANDLE.borrow_mut(env, &o_peer).is_err());
+            assert!(HANDLE.take(env, &o_peer).is_err());
+        }
+
+        {
+            let mut v = HANDLE.borrow_mut(env, &o_peer).unwrap();
+            v.push(4);
+
+            assert!(HANDLE.borrow(env, &o_peer).is_err());
+            assert!(HANDLE.borrow_mut(env, &o_peer).is_err());
+        }
+
+        assert_eq!(*HANDLE.borrow(env, &o_peer).unwrap(), [1, 2, 3, 4]);
+
+        assert_eq!(HANDLE.take(env, &o_peer).unwrap(), [1, 2, 3, 4]);
+        assert_eq!(env.typed_get_field::<i64, _>(&o_peer, "nativeHandle").unwrap(), 0);
+        assert!(HANDLE.take(env, &o_peer).is_err());
+    })
+}
+
+#[test]
+fn test_handle_cleaner() {
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    static HANDLE: NativeHandle<DropFlag> = unsafe { NativeHandle::new("nativeHandle") };
+
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Peer", PEER);
+
+        let c_peer: LocalClass<JavaPeer> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        // a taken value is owned by the caller
+        let taken = Arc::new(AtomicBool::new(false));
+        let o_peer = env.typed_new_object(&c_peer, ()).unwrap();
+        HANDLE.store_with_cleaner(env, &o_peer, DropFlag(taken.clone())).unwrap();
+        let flag = HANDLE.take(env, &o_peer).unwrap();
+        assert!(!taken.load(Ordering::SeqCst));
+        drop(flag);
+        assert!(taken.load(Ordering::SeqCst));
+        drop(o_peer);
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        unsafe {
+            env.typed_with_local_frame::<(), _>(4, |env| {
+                let o_peer = env.typed_new_object(&c_peer, ()).unwrap();
+
+                HANDLE.store_with_cleaner(env, &o_peer, DropFlag(dropped.clone())).unwrap();
+            })
+        }
+        .unwrap();
+
+        let c_system: LocalClass<JavaSystem> = env.typed_find_class().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !dropped.load(Ordering::SeqCst) {
+            assert!(Instant::now() < deadline, "native handle is not released");
+
+            env.typed_call_method::<(), _, _>(&c_system, "gc", ()).unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    })
+}
@@ -0,0 +1,253 @@
+use std::{
+    io::{self, Cursor, Read, Write},
+    sync::{Arc, Mutex},
+};
+
+use typed_jni::{
+    Array, JavaStreamReader, JavaStreamWriter, LocalClass, LocalObject, Null, TypedCallExt, TypedClassExt, TypedFieldAccessExt,
+    TypedObjectExt, TypedPrimitiveArrayExt, TypedStreamExt, TypedStringExt,
+    builtin::{JavaInputStream, JavaOutputStream, JavaString},
+    define_java_class,
+};
+
+use crate::{compile_file_and_load_classes, with_java_vm};
+
+define_java_class!(JavaByteArrayInputStream, "java.io.ByteArrayInputStream");
+define_java_class!(JavaByteArrayOutputStream, "java.io.ByteArrayOutputStream");
+
+fn data(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 251) as u8).collect()
+}
+
+#[test]
+fn test_read_java_input_stream() {
+    with_java_vm(|env| {
+        let data = data(20000);
+
+        let c_stream: LocalClass<JavaByteArrayInputStream> = env.typed_find_class().unwrap();
+        let o_stream: LocalObject<JavaByteArrayInputStream> = env
+            .typed_new_object(&c_stream, (&env.typed_new_bytes_array(&data).unwrap(),))
+            .unwrap();
+
+        let o_stream = env
+            .typed_cast(&o_stream, &env.typed_find_class::<JavaInputStream>().unwrap())
+            .unwrap();
+        let mut reader = JavaStreamReader::new(env, o_stream).unwrap();
+
+        let mut head = [0u8; 3];
+        reader.read_exact(&mut head).unwrap();
+        assert_eq!(head, data[..3]);
+
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, data[3..]);
+
+        assert_eq!(reader.read(&mut head).unwrap(), 0);
+    })
+}
+
+#[test]
+fn test_write_java_output_stream() {
+    with_java_vm(|env| {
+        let data = data(20000);
+
+        let c_stream: LocalClass<JavaByteArrayOutputStream> = env.typed_find_class().unwrap();
+        let o_stream: LocalObject<JavaByteArrayOutputStream> = env.typed_new_object(&c_stream, ()).unwrap();
+
+        let o_output = env
+            .typed_cast(&o_stream, &env.typed_find_class::<JavaOutputStream>().unwrap())
+            .unwrap();
+        let mut writer = JavaStreamWriter::new(env, o_output).unwrap();
+        writer.write_all(&data).unwrap();
+        writer.flush().unwrap();
+
+        let bytes: LocalObject<Array<i8>> = env.typed_call_method(&o_stream, "toByteArray", ()).unwrap();
+        assert_eq!(env.typed_bytes_array_to_vec(&bytes).unwrap(), data);
+    })
+}
+
+#[test]
+fn test_java_stream_errors() {
+    with_java_vm(|env| {
+        define_java_class!(JavaTest, "Test");
+
+        let (_dir, loader) = compile_file_and_load_classes(
+            env,
+            "Test",
+            r#"import java.io.*;
+
+            public class Test {
+                public static InputStream failingInput() {
+                    return new InputStream() {
+                        @Override
+                        public int read() throws IOException {
+                            throw new IOException("input failed");
+                        }
+                    };
+                }
+
+                public static InputStream overflowingInput() {
+                    return new InputStream() {
+                        @Override
+                        public int read() {
+                            return 0;
+                        }
+
+                        @Override
+                        public int read(byte[] b, int off, int len) {
+                            return len + 1;
+                        }
+                    };
+                }
+
+                public static OutputStream failingOutput() {
+                    return new OutputStream() {
+                        @Override
+                        public void write(int b) throws IOException {
+                            throw new IOException("output failed");
+                        }
+                    };
+                }
+            }"#,
+        );
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let o_input: LocalObject<JavaInputStream> = env.typed_call_method(&c_test, "failingInput", ()).unwrap();
+        let err = JavaStreamReader::new(env, o_input).unwrap().read(&mut [0; 4]).unwrap_err();
+        assert_eq!(err.to_string(), "java.io.IOException: input failed");
+
+        let o_input: LocalObject<JavaInputStream> = env.typed_call_method(&c_test, "overflowingInput", ()).unwrap();
+        let err = JavaStreamReader::new(env, o_input).unwrap().read(&mut [0; 4]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "stream read 5 bytes into a buffer of 4 bytes");
+
+        let o_output: LocalObject<JavaOutputStream> = env.typed_call_method(&c_test, "failingOutput", ()).unwrap();
+        let err = JavaStreamWriter::new(env, o_output).unwrap().write(&[1, 2]).unwrap_err();
+        assert_eq!(err.to_string(), "java.io.IOException: output failed");
+    })
+}
+
+/// A shared Rust writer, to inspect the written bytes after Java closes the stream.
+#[derive(Clone, Default)]
+struct SharedWriter(Arc<Mutex<(Vec<u8>, usize)>>);
+
+impl Write for SharedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().0.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.lock().unwrap().1 += 1;
+
+        Ok(())
+    }
+}
+
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("rust failed"))
+    }
+}
+
+#[test]
+fn test_native_streams() {
+    with_java_vm(|env| {
+        define_java_class!(JavaTest, "Test");
+
+        let (_dir, loader) = compile_file_and_load_classes(
+            env,
+            "Test",
+            r#"import java.io.*;
+
+            public class Test {
+                public static byte[] readAll(InputStream in) throws IOException {
+                    ByteArrayOutputStream out = new ByteArrayOutputStream();
+
+                    int first = in.read();
+                    if (first >= 0) {
+                        out.write(first);
+                    }
+
+                    byte[] buf = new byte[10000];
+                    for (int n; (n = in.read(buf, 3, buf.length - 3)) >= 0; ) {
+                        out.write(buf, 3, n);
+                    }
+
+                    in.close();
+
+                    return out.toByteArray();
+                }
+
+                public static void writeAll(OutputStream out, byte[] data) throws IOException {
+                    out.write(data[0]);
+                    out.write(data, 1, data.length - 1);
+                    out.flush();
+                    out.close();
+                }
+
+                public static String readFailure(InputStream in) {
+                    try {
+                        in.read();
+
+                        return "no failure";
+                    } catch (IOException e) {
+                        return e.getMessage();
+                    }
+                }
+
+                public static String writeAfterClose(OutputStream out) {
+                    try {
+                        out.write(1);
+
+                        return "no failure";
+                    } catch (IOException e) {
+                        return e.getMessage();
+                    }
+                }
+            }"#,
+        );
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let data = data(30000);
+
+        let o_input = env.typed_new_input_stream(Cursor::new(data.clone())).unwrap();
+        let bytes: LocalObject<Array<i8>> = env.typed_call_method(&c_test, "readAll", (&o_input,)).unwrap();
+        assert_eq!(env.typed_bytes_array_to_vec(&bytes).unwrap(), data);
+
+        let message: LocalObject<JavaString> = env.typed_call_method(&c_test, "readFailure", (&o_input,)).unwrap();
+        assert_eq!(env.typed_get_string(&message), "stream closed");
+
+        let writer = SharedWriter::default();
+        let o_output = env.typed_new_output_stream(writer.clone()).unwrap();
+        env.typed_call_method::<(), _, _>(&c_test, "writeAll", (&o_output, &env.typed_new_bytes_array(&data).unwrap()))
+            .unwrap();
+        {
+            let written = writer.0.lock().unwrap();
+            assert_eq!(written.0, data);
+            // flush() and close()
+            assert_eq!(written.1, 2);
+        }
+
+        let message: LocalObject<JavaString> = env.typed_call_method(&c_test, "writeAfterClose", (&o_output,)).unwrap();
+        assert_eq!(env.typed_get_string(&message), "stream closed");
+
+        let handle: i64 = env.typed_get_field(&o_output, "handle").unwrap();
+        let err = env
+            .typed_call_method::<i32, _, _>(&o_output, "invoke", (handle, 42i32, Null::<Array<i8>>::NULL, 0i32, 0i32))
+            .unwrap_err();
+        assert_eq!(
+            env.typed_to_string(&env.typed_get_object_class(&err)).unwrap(),
+            "class java.lang.UnsupportedOperationException"
+        );
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "unsupported stream operation 42");
+
+        let o_failing = env.typed_new_input_stream(FailingReader).unwrap();
+        let message: LocalObject<JavaString> = env.typed_call_method(&c_test, "readFailure", (&o_failing,)).unwrap();
+        assert_eq!(env.typed_get_string(&message), "rust failed");
+    })
+}
@@ -1,15 +1,41 @@
 #![cfg(test)]
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::let_and_return,
+    clippy::needless_as_bytes,
+    clippy::needless_borrows_for_generic_args
+)]
 
 mod array;
 #[cfg(feature = "cache")]
 mod cache;
 mod call;
 mod class;
+mod dynamic;
+#[cfg(feature = "testing")]
+mod fault;
 mod field;
+mod frame;
+mod function;
+mod future;
+mod handle;
+mod io;
+mod monitor;
 mod native;
 mod object;
+mod proxy;
+mod signature;
 mod string;
+#[cfg(feature = "testing")]
+mod testing;
 mod throwable;
+#[cfg(feature = "tracing")]
+mod trace;
+#[cfg(feature = "track-local-refs")]
+mod tracker;
+#[cfg(any(feature = "verify-members", debug_assertions))]
+mod verify;
+mod vm;
 
 use std::{process::Stdio, sync::OnceLock};
 
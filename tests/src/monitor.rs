@@ -0,0 +1,84 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use typed_jni::{
+    LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedMonitorExt, TypedObjectExt, TypedRefExt,
+    builtin::{JavaObject, JavaThrowable},
+    define_java_class,
+};
+
+use crate::with_java_vm;
+
+#[test]
+fn test_monitor_wait_timeout() {
+    with_java_vm(|env| {
+        let c_object: LocalClass<JavaObject> = env.typed_find_class().unwrap();
+        let lock = env.typed_new_object(&c_object, ()).unwrap();
+
+        let guard = env.typed_monitor_enter(&lock);
+
+        let start = Instant::now();
+        guard.wait_timeout(Duration::from_millis(50)).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+
+        guard.wait_timeout(Duration::ZERO).unwrap();
+
+        guard.exit().unwrap();
+    })
+}
+
+#[test]
+fn test_monitor_wait_notify() {
+    with_java_vm(|env| {
+        let c_object: LocalClass<JavaObject> = env.typed_find_class().unwrap();
+        let lock = env.typed_new_global_ref(&env.typed_new_object(&c_object, ()).unwrap());
+        let notified = AtomicBool::new(false);
+
+        let guard = env.typed_monitor_enter(&lock);
+
+        std::thread::scope(|scope| {
+            let vm = env.vm();
+
+            scope.spawn(|| {
+                vm.with_attached_thread(false, |env| {
+                    let guard = env.typed_monitor_enter(&lock);
+
+                    notified.store(true, Ordering::SeqCst);
+
+                    guard.notify_all().unwrap();
+                })
+                .unwrap()
+            });
+
+            while !notified.load(Ordering::SeqCst) {
+                guard.wait().unwrap();
+            }
+        });
+
+        guard.exit().unwrap();
+    })
+}
+
+#[test]
+fn test_monitor_wait_interrupted() {
+    define_java_class!(JavaThread, "java/lang/Thread");
+    define_java_class!(JavaInterruptedException, "java/lang/InterruptedException");
+
+    with_java_vm(|env| {
+        let c_thread: LocalClass<JavaThread> = env.typed_find_class().unwrap();
+        let o_thread: LocalObject<JavaThread> = env.typed_call_method(&c_thread, "currentThread", ()).unwrap();
+        env.typed_call_method::<(), _, _>(&o_thread, "interrupt", ()).unwrap();
+
+        let c_object: LocalClass<JavaObject> = env.typed_find_class().unwrap();
+        let lock = env.typed_new_object(&c_object, ()).unwrap();
+
+        let guard = env.typed_monitor_enter(&lock);
+
+        let err: LocalObject<JavaThrowable> = guard.wait().unwrap_err();
+        let c_interrupted: LocalClass<JavaInterruptedException> = env.typed_find_class().unwrap();
+        assert!(env.typed_is_instance_of(&err, &c_interrupted));
+        assert!(!env.has_throwable());
+    })
+}
@@ -0,0 +1,229 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use typed_jni::{
+    LocalClass, LocalObject, ProxyBuilder, ProxyHandler, TypedCallExt, TypedClassExt, TypedFrameExt, TypedObjectExt,
+    TypedStringExt,
+    builtin::{JavaObject, JavaString, JavaThrowable},
+    core::JNIEnv,
+    define_java_class,
+};
+
+use crate::{compile_file_and_load_classes, with_java_vm};
+
+define_java_class!(JavaRunnable, "java.lang.Runnable");
+define_java_class!(JavaSystem, "java.lang.System");
+
+#[test]
+fn test_proxy_runnable() {
+    with_java_vm(|env| {
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let o_runnable: LocalObject<JavaRunnable> = ProxyBuilder::new()
+            .method("run", "()V", {
+                let counter = counter.clone();
+
+                move |_, args| {
+                    assert!(args.is_empty());
+
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    Ok(None)
+                }
+            })
+            .build(env)
+            .unwrap();
+
+        for _ in 0..16 {
+            env.typed_call_method::<(), _, _>(&o_runnable, "run", ()).unwrap();
+        }
+
+        assert_eq!(counter.load(Ordering::SeqCst), 16);
+
+        let hash_code: i32 = env.typed_call_method(&o_runnable, "hashCode", ()).unwrap();
+        let identity_hash_code: i32 = env
+            .typed_call_method(
+                &env.typed_find_class::<JavaSystem>().unwrap(),
+                "identityHashCode",
+                (&o_runnable.into_object(),),
+            )
+            .unwrap();
+        assert_eq!(hash_code, identity_hash_code);
+    })
+}
+
+#[test]
+fn test_proxy_custom_interface() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(
+            env,
+            "Calculator",
+            r#"
+                public interface Calculator {
+                    int add(int a, int b);
+
+                    String greet(String name);
+
+                    void unsupported();
+                }
+            "#,
+        );
+
+        define_java_class!(JavaCalculator, "Calculator");
+
+        struct Greeter;
+
+        impl ProxyHandler for Greeter {
+            fn invoke<'env>(
+                &self,
+                env: &'env JNIEnv<'static>,
+                name: &str,
+                signature: &str,
+                args: &[Option<LocalObject<'env, JavaObject>>],
+            ) -> Result<Option<LocalObject<'env, JavaObject>>, LocalObject<'env, JavaThrowable>> {
+                match (name, signature) {
+                    ("greet", "(Ljava/lang/String;)Ljava/lang/String;") => {
+                        let name: LocalObject<JavaString> = env.typed_call_method(args[0].as_ref().unwrap(), "toString", ())?;
+
+                        Ok(Some(
+                            env.typed_new_string(format!("Hello, {}!", env.typed_get_string(&name)))
+                                .into_object(),
+                        ))
+                    }
+                    _ => {
+                        // checked exceptions are wrapped into UndeclaredThrowableException by Proxy
+                        define_java_class!(JavaRuntimeException, "java.lang.RuntimeException");
+
+                        let ex = env.typed_new_object(
+                            &env.typed_find_class::<JavaRuntimeException>()?,
+                            (env.typed_new_string(format!("{name}{signature}")),),
+                        )?;
+
+                        Err(env.typed_cast(&ex, &env.typed_find_class()?)?)
+                    }
+                }
+            }
+        }
+
+        let c_calculator: LocalClass<JavaCalculator> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let o_calculator: LocalObject<JavaCalculator> = ProxyBuilder::new()
+            .interface(&c_calculator)
+            .method("add", "(II)I", |env, args| {
+                let a: i32 = env.typed_call_method(args[0].as_ref().unwrap(), "intValue", ())?;
+                let b: i32 = env.typed_call_method(args[1].as_ref().unwrap(), "intValue", ())?;
+
+                define_java_class!(JavaInteger, "java.lang.Integer");
+
+                let sum: LocalObject<JavaInteger> =
+                    env.typed_call_method(&env.typed_find_class::<JavaInteger>()?, "valueOf", (a + b,))?;
+
+                Ok(Some(sum.into_object()))
+            })
+            .handler(Greeter)
+            .build(env)
+            .unwrap();
+
+        let sum: i32 = env.typed_call_method(&o_calculator, "add", (114000, 514)).unwrap();
+        assert_eq!(sum, 114514);
+
+        let greeting: LocalObject<JavaString> = env
+            .typed_call_method(&o_calculator, "greet", (env.typed_new_string("Rust"),))
+            .unwrap();
+        assert_eq!(env.typed_get_string(&greeting), "Hello, Rust!");
+
+        let err = env
+            .typed_call_method::<(), _, _>(&o_calculator, "unsupported", ())
+            .unwrap_err();
+        let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+        assert_eq!(env.typed_get_string(&message), "unsupported()V");
+    })
+}
+
+#[test]
+fn test_proxy_class_loader() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Named", "public interface Named { String name(); }");
+
+        define_java_class!(JavaNamed, "Named");
+
+        let c_named: LocalClass<JavaNamed> = env.typed_find_class_in_class_loader(&loader).unwrap();
+
+        let builder = || {
+            ProxyBuilder::new()
+                .interface(&c_named)
+                .method("run", "()V", |_, _| Ok(None))
+                .method("name", "()Ljava/lang/String;", |env, _| {
+                    Ok(Some(env.typed_new_string("named").into_object()))
+                })
+        };
+
+        // `Named` is not visible from the bootstrap class loader of `Runnable`
+        assert!(builder().build::<JavaRunnable>(env).is_err());
+
+        let o_runnable: LocalObject<JavaRunnable> = builder().class_loader(&loader).build(env).unwrap();
+        env.typed_call_method::<(), _, _>(&o_runnable, "run", ()).unwrap();
+
+        let o_named = env.typed_cast(&o_runnable.into_object(), &c_named).unwrap();
+        let name: LocalObject<JavaString> = env.typed_call_method(&o_named, "name", ()).unwrap();
+        assert_eq!(env.typed_get_string(&name), "named");
+    })
+}
+
+#[test]
+fn test_proxy_unhandled_method() {
+    with_java_vm(|env| {
+        let o_runnable: LocalObject<JavaRunnable> = ProxyBuilder::new().build(env).unwrap();
+
+        define_java_class!(JavaUnsupportedOperationException, "java.lang.UnsupportedOperationException");
+
+        let err = env.typed_call_method::<(), _, _>(&o_runnable, "run", ()).unwrap_err();
+        let c_unsupported: LocalClass<JavaUnsupportedOperationException> = env.typed_find_class().unwrap();
+        assert!(env.typed_is_instance_of(&err, &c_unsupported));
+    })
+}
+
+#[test]
+fn test_proxy_released_after_gc() {
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    with_java_vm(|env| {
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        unsafe {
+            env.typed_with_local_frame::<(), _>(4, |env| {
+                let flag = DropFlag(dropped.clone());
+
+                let _: LocalObject<JavaRunnable> = ProxyBuilder::new()
+                    .method("run", "()V", move |_, _| {
+                        let _ = &flag;
+
+                        Ok(None)
+                    })
+                    .build(env)
+                    .unwrap();
+            })
+        }
+        .unwrap();
+
+        let c_system: LocalClass<JavaSystem> = env.typed_find_class().unwrap();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !dropped.load(Ordering::SeqCst) {
+            assert!(Instant::now() < deadline, "proxy state is not released");
+
+            env.typed_call_method::<(), _, _>(&c_system, "gc", ()).unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    })
+}
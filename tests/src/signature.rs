@@ -0,0 +1,188 @@
+use typed_jni::{MethodSignature, OwnedSignature, Signature, SignatureError, SignatureErrorKind};
+
+#[test]
+fn test_parse_signature() {
+    assert_eq!(Signature::parse("I").unwrap(), OwnedSignature::Int);
+    assert_eq!(Signature::parse("V").unwrap(), OwnedSignature::Void);
+    assert_eq!(
+        Signature::parse("[[Ljava/lang/String;").unwrap(),
+        Signature::Array(&Signature::Array(&Signature::Object("java/lang/String")))
+    );
+
+    for s in ["Z", "[B", "Ljava/util/Map$Entry;", "[[D"] {
+        assert_eq!(Signature::parse(s).unwrap().to_string(), s);
+    }
+
+    let owned: OwnedSignature = Signature::Array(&Signature::Object("java/lang/Object")).into();
+    assert_eq!(owned.to_string(), "[Ljava/lang/Object;");
+}
+
+#[test]
+fn test_parse_signature_errors() {
+    let err = |s: &str| Signature::parse(s).unwrap_err();
+
+    assert_eq!(
+        err(""),
+        SignatureError {
+            offset: 0,
+            kind: SignatureErrorKind::UnexpectedEnd
+        }
+    );
+    assert_eq!(
+        err("Q"),
+        SignatureError {
+            offset: 0,
+            kind: SignatureErrorKind::UnexpectedByte(b'Q')
+        }
+    );
+    assert_eq!(
+        err("[V"),
+        SignatureError {
+            offset: 1,
+            kind: SignatureErrorKind::UnexpectedVoid
+        }
+    );
+    assert_eq!(
+        err("II"),
+        SignatureError {
+            offset: 1,
+            kind: SignatureErrorKind::TrailingBytes
+        }
+    );
+    assert_eq!(
+        err("Ljava/lang/String"),
+        SignatureError {
+            offset: 17,
+            kind: SignatureErrorKind::UnexpectedEnd
+        }
+    );
+    assert_eq!(
+        err("Ljava//String;"),
+        SignatureError {
+            offset: 6,
+            kind: SignatureErrorKind::InvalidClassName
+        }
+    );
+    assert_eq!(
+        err("Ljava.lang.String;"),
+        SignatureError {
+            offset: 5,
+            kind: SignatureErrorKind::InvalidClassName
+        }
+    );
+    assert_eq!(
+        err("L;"),
+        SignatureError {
+            offset: 1,
+            kind: SignatureErrorKind::InvalidClassName
+        }
+    );
+
+    assert_eq!(err("[V").to_string(), "unexpected void at byte 1");
+}
+
+#[test]
+fn test_parse_method_signature() {
+    let sig = MethodSignature::parse("(I[Ljava/lang/String;J)V").unwrap();
+
+    assert_eq!(
+        sig.params,
+        [
+            OwnedSignature::Int,
+            OwnedSignature::Array(Box::new(OwnedSignature::Object("java/lang/String".into()))),
+            OwnedSignature::Long,
+        ]
+    );
+    assert_eq!(sig.ret, OwnedSignature::Void);
+    assert_eq!(sig.to_string(), "(I[Ljava/lang/String;J)V");
+    assert_eq!(
+        "()Ljava/lang/Object;".parse::<MethodSignature>().unwrap().to_string(),
+        "()Ljava/lang/Object;"
+    );
+
+    let err = |s: &str| MethodSignature::parse(s).unwrap_err();
+
+    assert_eq!(
+        err("I)V"),
+        SignatureError {
+            offset: 0,
+            kind: SignatureErrorKind::UnexpectedByte(b'I')
+        }
+    );
+    assert_eq!(
+        err("(V)V"),
+        SignatureError {
+            offset: 1,
+            kind: SignatureErrorKind::UnexpectedVoid
+        }
+    );
+    assert_eq!(
+        err("(I"),
+        SignatureError {
+            offset: 2,
+            kind: SignatureErrorKind::UnexpectedEnd
+        }
+    );
+    assert_eq!(
+        err("()VI"),
+        SignatureError {
+            offset: 3,
+            kind: SignatureErrorKind::TrailingBytes
+        }
+    );
+}
+
+#[test]
+fn test_java_names() {
+    let cases = [
+        ("int", "I"),
+        ("void", "V"),
+        ("boolean[]", "[Z"),
+        ("java.lang.String", "Ljava/lang/String;"),
+        ("java.lang.String[][]", "[[Ljava/lang/String;"),
+    ];
+
+    for (java, descriptor) in cases {
+        let sig = OwnedSignature::from_java_name(java).unwrap();
+
+        assert_eq!(sig.to_string(), descriptor);
+        assert_eq!(sig.to_java_name(), java);
+    }
+
+    let mut name = String::new();
+    Signature::Array(&Signature::Object("java/lang/Object"))
+        .write_as_java_name_to(&mut name)
+        .unwrap();
+    assert_eq!(name, "java.lang.Object[]");
+
+    let err = |s: &str| OwnedSignature::from_java_name(s).unwrap_err();
+
+    assert_eq!(
+        err("void[]"),
+        SignatureError {
+            offset: 0,
+            kind: SignatureErrorKind::UnexpectedVoid
+        }
+    );
+    assert_eq!(
+        err("int]"),
+        SignatureError {
+            offset: 3,
+            kind: SignatureErrorKind::UnexpectedByte(b']')
+        }
+    );
+    assert_eq!(
+        err("java..String"),
+        SignatureError {
+            offset: 5,
+            kind: SignatureErrorKind::InvalidClassName
+        }
+    );
+    assert_eq!(
+        err("java/lang/String"),
+        SignatureError {
+            offset: 4,
+            kind: SignatureErrorKind::InvalidClassName
+        }
+    );
+}
@@ -1,4 +1,8 @@
-use typed_jni::{LocalObject, TypedStringExt, builtin::JavaString};
+use typed_jni::{
+    LocalObject, TypedCallExt, TypedRef, TypedStringExt,
+    builtin::JavaString,
+    core::{decode_modified_utf8, encode_modified_utf8},
+};
 
 use crate::with_java_vm;
 
@@ -13,3 +17,81 @@ fn test_convert_string() {
         assert_eq!(content, r_content);
     })
 }
+
+#[test]
+fn test_convert_string_modified_utf8() {
+    with_java_vm(|env| {
+        for content in ["", "nul\0inside", "emoji 😀 and 中文", &"\0😀".repeat(200)] {
+            let o_string: LocalObject<JavaString> = env.typed_new_string(content);
+
+            let length: i32 = env.typed_call_method(&o_string, "length", ()).unwrap();
+            assert_eq!(length as usize, content.encode_utf16().count());
+            assert_eq!(env.typed_get_string(&o_string), content);
+
+            let utf = unsafe { env.get_modified_utf8_string(&*o_string) };
+            assert_eq!(&*utf, encode_modified_utf8(content));
+            assert_eq!(utf.to_str().unwrap(), content);
+        }
+    })
+}
+
+#[test]
+fn test_new_modified_utf8_string() {
+    with_java_vm(|env| {
+        let encoded = encode_modified_utf8("a\0😀");
+
+        for utf in [encoded.clone(), [&encoded[..], b"\0"].concat(), encoded.repeat(100)] {
+            let s = env.new_modified_utf8_string(&utf).unwrap();
+            let s = unsafe { LocalObject::<JavaString>::from_ref(s) };
+
+            let expected = decode_modified_utf8(utf.strip_suffix(b"\0").unwrap_or(&utf)).unwrap();
+            assert_eq!(env.typed_get_string(&s), expected);
+        }
+    })
+}
+
+#[test]
+fn test_string_region() {
+    with_java_vm(|env| {
+        let content = "0123456789 😀 中文";
+        let o_string: LocalObject<JavaString> = env.typed_new_string(content);
+
+        let units = content.encode_utf16().collect::<Vec<_>>();
+        assert_eq!(env.typed_get_string_length(&o_string) as usize, units.len());
+
+        let mut buf = [0u16; 4];
+        env.typed_get_string_region(&o_string, 10, &mut buf).unwrap();
+        assert_eq!(buf, units[10..14]);
+
+        let mut utf = [0u8; 4 * 3 + 1];
+        let len = env.typed_get_string_utf_region(&o_string, 10, 4, &mut utf).unwrap();
+        assert_eq!(&utf[..len], encode_modified_utf8(" 😀 "));
+
+        assert!(env.typed_get_string_region(&o_string, 14, &mut [0u16; 16]).is_err());
+        assert!(env.typed_get_string_utf_region(&o_string, -1, 1, &mut utf).is_err());
+        assert!(env.typed_get_string_utf_region(&o_string, 10, 4, &mut [0u8; 4 * 3]).is_err());
+    })
+}
+
+#[test]
+fn test_iter_string() {
+    with_java_vm(|env| {
+        let content = include_str!("../testdata/unicode-test.html");
+        let o_string: LocalObject<JavaString> = env.typed_new_string(content);
+
+        let units = env.typed_iter_string_units(&o_string);
+        assert_eq!(units.len(), content.encode_utf16().count());
+        assert!(units.eq(content.encode_utf16()));
+
+        let chars = env.typed_iter_string_chars(&o_string).map(Result::unwrap);
+        assert!(chars.eq(content.chars()));
+
+        let mut units = env.typed_iter_string_units(&o_string);
+        let mut chunks = 0;
+        while let Some(chunk) = units.next_chunk() {
+            assert!(!chunk.is_empty());
+            chunks += 1;
+        }
+        assert!(chunks > 1);
+    })
+}
@@ -0,0 +1,154 @@
+use typed_jni::{
+    LocalClass, LocalObject, Null, TypedCallExt, TypedClassExt, TypedFieldAccessExt, TypedRef, TypedStringExt,
+    builtin::JavaString,
+    core::{
+        JNIEnv, LocalRef,
+        testing::{FakeJvm, FakeValue},
+    },
+    define_java_class,
+};
+
+define_java_class!(JavaGreeter, "org.example.Greeter");
+
+fn define_greeter(jvm: &FakeJvm) {
+    jvm.class("org/example/Greeter")
+        .field("count", "I")
+        .static_field("prefix", "Ljava/lang/String;")
+        .method("greet", "(Ljava/lang/String;)Ljava/lang/String;", |env, this, args| {
+            let this = unsafe { LocalObject::<JavaGreeter>::from_ref(env.new_local_ref(this).unwrap()) };
+            let FakeValue::Object(Some(name)) = &args[0] else {
+                return Err(new_exception(env, "name is null"));
+            };
+            let name = unsafe { LocalObject::<JavaString>::from_ref(env.new_local_ref(name).unwrap()) };
+
+            let count: i32 = env.typed_get_field(&this, "count").map_err(|err| err.into_ref())?;
+            env.typed_set_field(&this, "count", count + 1).map_err(|err| err.into_ref())?;
+
+            let c_greeter: LocalClass<JavaGreeter> = env.typed_find_class().map_err(|err| err.into_ref())?;
+            let prefix: Option<LocalObject<JavaString>> =
+                env.typed_get_field(&c_greeter, "prefix").map_err(|err| err.into_ref())?;
+            let prefix = prefix.map(|p| env.typed_get_string(&p)).unwrap_or_default();
+
+            let greeting = env.typed_new_string(format!("{prefix}{}!", env.typed_get_string(&name)));
+
+            Ok(FakeValue::Object(Some(greeting.into_ref())))
+        })
+        .static_method("create", "()Lorg/example/Greeter;", |env, cls, _| unsafe {
+            let init = env.get_method_id::<false, _>(cls, c"<init>", c"()V")?;
+
+            Ok(FakeValue::Object(Some(env.new_object(cls, init, [])?)))
+        })
+        .define();
+}
+
+fn new_exception<'env>(env: &'env JNIEnv, message: &str) -> LocalRef<'env> {
+    unsafe {
+        let cls = env.find_class(c"java/lang/IllegalArgumentException").unwrap();
+        let init = env
+            .get_method_id::<false, _>(&cls, c"<init>", c"(Ljava/lang/String;)V")
+            .unwrap();
+
+        env.new_object(&cls, init, [(&env.new_string(message)).into()]).unwrap()
+    }
+}
+
+#[test]
+fn test_fake_call_and_fields() {
+    let jvm = FakeJvm::new();
+    define_greeter(&jvm);
+
+    let env = jvm.env();
+
+    let c_greeter: LocalClass<JavaGreeter> = env.typed_find_class().unwrap();
+    let o_greeter: LocalObject<JavaGreeter> = env.typed_call_method(&c_greeter, "create", ()).unwrap();
+
+    let ret: LocalObject<JavaString> = env
+        .typed_call_method(&o_greeter, "greet", (env.typed_new_string("world"),))
+        .unwrap();
+    assert_eq!(env.typed_get_string(&ret), "world!");
+
+    env.typed_set_field(&c_greeter, "prefix", &env.typed_new_string("hello, "))
+        .unwrap();
+    let ret: LocalObject<JavaString> = env
+        .typed_call_method(&o_greeter, "greet", (env.typed_new_string("fake"),))
+        .unwrap();
+    assert_eq!(env.typed_get_string(&ret), "hello, fake!");
+
+    assert_eq!(env.typed_get_field::<i32, _>(&o_greeter, "count").unwrap(), 2);
+
+    jvm.assert_calls([
+        "FindClass(org/example/Greeter)",
+        "CallStaticObjectMethodA(org/example/Greeter.create()Lorg/example/Greeter;)",
+        "NewObjectA(org/example/Greeter.<init>()V)",
+        "CallObjectMethodA(org/example/Greeter.greet(Ljava/lang/String;)Ljava/lang/String;)",
+        "SetStaticObjectField(org/example/Greeter.prefix:Ljava/lang/String;)",
+        "CallObjectMethodA(org/example/Greeter.greet(Ljava/lang/String;)Ljava/lang/String;)",
+        "GetIntField(org/example/Greeter.count:I)",
+    ]);
+}
+
+#[test]
+fn test_fake_exceptions() {
+    let jvm = FakeJvm::new();
+    define_greeter(&jvm);
+    jvm.class("org/example/Shape")
+        .abstract_method("area", "()D")
+        .method("panic", "()V", |_, _, _| panic!("boom"))
+        .define();
+
+    let env = jvm.env();
+
+    let c_greeter: LocalClass<JavaGreeter> = env.typed_find_class().unwrap();
+    let o_greeter = env.typed_new_object(&c_greeter, ()).unwrap();
+
+    let Err(err) = env.typed_call_method::<LocalObject<JavaString>, _, _>(&o_greeter, "greet", (Null::<JavaString>::NULL,))
+    else {
+        panic!("greet with null name succeeded");
+    };
+    let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+    assert_eq!(env.typed_get_string(&message), "name is null");
+
+    let err = env.typed_get_field::<i64, _>(&o_greeter, "missing").unwrap_err();
+    let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+    assert_eq!(env.typed_get_string(&message), "org/example/Greeter.missing:J");
+
+    define_java_class!(JavaShape, "org.example.Shape");
+
+    let c_shape: LocalClass<JavaShape> = env.typed_find_class().unwrap();
+    let o_shape = env.typed_new_object(&c_shape, ()).unwrap();
+
+    let err = env.typed_call_method::<f64, _, _>(&o_shape, "area", ()).unwrap_err();
+    let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+    assert_eq!(env.typed_get_string(&message), "org/example/Shape.area()D");
+
+    let err = env.typed_call_method::<(), _, _>(&o_shape, "panic", ()).unwrap_err();
+    let message: LocalObject<JavaString> = env.typed_call_method(&err, "getMessage", ()).unwrap();
+    assert_eq!(env.typed_get_string(&message), "rust panic: boom");
+
+    define_java_class!(JavaMissing, "org.example.Missing");
+
+    assert!(env.typed_find_class::<JavaMissing>().is_err());
+}
+
+#[test]
+fn test_fake_local_refs_released() {
+    let jvm = FakeJvm::new();
+    define_greeter(&jvm);
+
+    let env = jvm.env();
+    {
+        let c_greeter: LocalClass<JavaGreeter> = env.typed_find_class().unwrap();
+        let o_greeter: LocalObject<JavaGreeter> = env.typed_call_method(&c_greeter, "create", ()).unwrap();
+
+        for _ in 0..16 {
+            let _: LocalObject<JavaString> = env
+                .typed_call_method(&o_greeter, "greet", (env.typed_new_string("world"),))
+                .unwrap();
+        }
+    }
+
+    assert_eq!(jvm.live_local_refs(), 0);
+
+    jvm.clear_calls();
+    assert!(jvm.calls().is_empty());
+}
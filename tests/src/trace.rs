@@ -0,0 +1,115 @@
+use std::{
+    fmt::Debug,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use tracing::{
+    Event, Metadata, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+};
+use typed_jni::{LocalClass, TypedCallExt, TypedClassExt, define_java_class};
+
+use crate::with_java_vm;
+
+define_java_class!(JavaCharacter, "java.lang.Character");
+
+#[derive(Default, Clone)]
+struct Recorder {
+    next_id: Arc<AtomicU64>,
+    records: Arc<Mutex<Vec<String>>>,
+}
+
+struct FieldWriter<'a>(&'a mut String);
+
+impl Visit for FieldWriter<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.push_str(&format!(" {}={:?}", field.name(), value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut record = span.metadata().name().to_string();
+        span.record(&mut FieldWriter(&mut record));
+
+        self.records.lock().unwrap().push(record);
+
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _: &Id, values: &Record<'_>) {
+        let mut record = String::from("record");
+        values.record(&mut FieldWriter(&mut record));
+
+        self.records.lock().unwrap().push(record);
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut record = String::from("event");
+        event.record(&mut FieldWriter(&mut record));
+
+        self.records.lock().unwrap().push(record);
+    }
+
+    fn enter(&self, _: &Id) {}
+
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_tracing_spans() {
+    with_java_vm(|env| {
+        let recorder = Recorder::default();
+
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let c_character: LocalClass<JavaCharacter> = env.typed_find_class().unwrap();
+
+            for _ in 0..2 {
+                let ret: bool = env.typed_call_method(&c_character, "isLetter", ('a' as i32,)).unwrap();
+                assert!(ret);
+            }
+
+            assert!(
+                env.typed_call_method::<bool, _, _>(&c_character, "toChars", (-1i32,))
+                    .is_err()
+            );
+        });
+
+        let records = recorder.records.lock().unwrap();
+
+        assert!(records.contains(&r#"find_class class="java/lang/Character""#.to_string()));
+        assert!(
+            records.contains(&r#"call_method class=Ljava/lang/Character; name="isLetter" sig="(I)Z" is_static=true"#.to_string())
+        );
+        assert!(records.contains(&r#"resolve_method name="isLetter" sig="(I)Z" is_static=true"#.to_string()));
+        assert!(records.contains(&"jni function=\"CallStaticBooleanMethodA\"".to_string()));
+        assert!(
+            records
+                .iter()
+                .any(|r| r.starts_with("event") && r.contains("exception thrown"))
+        );
+
+        let lookups = records
+            .iter()
+            .skip_while(|r| !r.contains(r#""isLetter""#))
+            .filter(|r| r.starts_with("record cached="))
+            .take(2)
+            .collect::<Vec<_>>();
+
+        if cfg!(feature = "cache") {
+            assert_eq!(lookups.last().unwrap().as_str(), "record cached=true");
+        } else {
+            assert!(lookups.iter().all(|r| r.as_str() == "record cached=false"));
+        }
+    })
+}
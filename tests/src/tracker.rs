@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+
+use typed_jni::core::{GUARANTEED_LOCAL_CAPACITY, JNIEnv, LocalRefReport, LocalRefTrackAction, LocalRefTracker};
+
+use crate::with_java_vm;
+
+thread_local! {
+    static REPORTS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(report: &LocalRefReport) {
+    let report = match report {
+        LocalRefReport::Overflow { live, capacity, .. } => format!("overflow {live}/{capacity}"),
+        LocalRefReport::Leak { backtraces } => format!("leak {}", backtraces.len()),
+    };
+
+    REPORTS.with_borrow_mut(|r| r.push(report));
+}
+
+fn with_recorded_reports(env: &JNIEnv, f: impl for<'env> FnOnce(&'env JNIEnv)) -> Vec<String> {
+    LocalRefTracker::set_action(LocalRefTrackAction::Custom(record));
+    REPORTS.with_borrow_mut(|r| r.clear());
+
+    unsafe {
+        env.with_push_local_frame(GUARANTEED_LOCAL_CAPACITY as i32, |env| {
+            f(env);
+
+            ((), None)
+        })
+        .unwrap();
+    }
+
+    REPORTS.with_borrow_mut(std::mem::take)
+}
+
+#[test]
+fn test_track_local_refs_overflow() {
+    with_java_vm(|env| {
+        let reports = with_recorded_reports(env, |env| {
+            let refs: Vec<_> = (0..=GUARANTEED_LOCAL_CAPACITY).map(|_| env.new_string("overflow")).collect();
+
+            assert_eq!(LocalRefTracker::live_count(), refs.len());
+        });
+
+        assert_eq!(
+            reports,
+            [format!(
+                "overflow {}/{}",
+                GUARANTEED_LOCAL_CAPACITY + 1,
+                GUARANTEED_LOCAL_CAPACITY
+            )]
+        );
+    })
+}
+
+#[test]
+fn test_track_local_refs_overflow_requested_capacity() {
+    with_java_vm(|env| {
+        let reports = with_recorded_reports(env, |env| unsafe {
+            env.with_push_local_frame(4, |env| {
+                let _refs: Vec<_> = (0..5).map(|_| env.new_string("overflow")).collect();
+
+                ((), None)
+            })
+            .unwrap();
+        });
+
+        assert_eq!(reports, ["overflow 5/4"]);
+    })
+}
+
+#[test]
+fn test_track_local_refs_ensure_capacity() {
+    with_java_vm(|env| {
+        let reports = with_recorded_reports(env, |env| {
+            env.ensure_local_capacity(64).unwrap();
+
+            let _refs: Vec<_> = (0..64).map(|_| env.new_string("ensured")).collect();
+        });
+
+        assert!(reports.is_empty(), "{reports:?}");
+    })
+}
+
+#[test]
+fn test_track_local_refs_released() {
+    with_java_vm(|env| {
+        let reports = with_recorded_reports(env, |env| {
+            for _ in 0..1024 {
+                let _ = env.new_string("released");
+            }
+
+            assert_eq!(LocalRefTracker::live_count(), 0);
+        });
+
+        assert!(reports.is_empty(), "{reports:?}");
+    })
+}
+
+#[test]
+fn test_track_local_refs_leak() {
+    with_java_vm(|env| {
+        let reports = with_recorded_reports(env, |env| {
+            std::mem::forget(env.new_string("leaked"));
+            std::mem::forget(env.new_string("leaked"));
+        });
+
+        assert_eq!(reports, ["leak 2"]);
+    })
+}
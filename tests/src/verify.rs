@@ -0,0 +1,130 @@
+use typed_jni::{
+    LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedFieldAccessExt, TypedObjectExt, TypedStringExt,
+    builtin::{JavaString, JavaThrowable},
+    core::JNIEnv,
+    define_java_class,
+};
+
+use crate::{compile_file_and_load_classes, with_java_vm};
+
+define_java_class!(JavaTest, "Test");
+
+const SOURCE: &str = r#"public class Test {
+    public long value = 1;
+    public static String name = "test";
+
+    public Test() {
+    }
+
+    public Test(int value) {
+        this.value = value;
+    }
+
+    public long compute(int a) {
+        return a;
+    }
+
+    public static long compute(int a, int b) {
+        return a + b;
+    }
+
+    public void reset() {
+        value = 0;
+    }
+}"#;
+
+fn message_of(env: &JNIEnv, err: &LocalObject<JavaThrowable>) -> String {
+    let message: LocalObject<JavaString> = env.typed_call_method(err, "getMessage", ()).unwrap();
+
+    env.typed_get_string(&message)
+}
+
+fn class_name_of(env: &JNIEnv, err: &LocalObject<JavaThrowable>) -> String {
+    env.typed_to_string(&env.typed_get_object_class(err)).unwrap()
+}
+
+#[test]
+fn test_verify_method_mismatch() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Test", SOURCE);
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        let o_test: LocalObject<JavaTest> = env.typed_new_object(&c_test, ()).unwrap();
+
+        let Err(err) = env.typed_call_method::<i32, _, _>(&o_test, "compute", (1i32,)) else {
+            panic!("compute returning int succeeded");
+        };
+        let message = message_of(env, &err);
+
+        assert_eq!(class_name_of(env, &err), "class java.lang.NoSuchMethodError");
+        assert!(
+            message.starts_with("Test: no method `int compute(int) (I)I`, closest candidates:"),
+            "{message}"
+        );
+
+        let candidates = message.lines().skip(1).map(str::trim).collect::<Vec<_>>();
+        assert_eq!(candidates, ["long compute(int) (I)J", "static long compute(int, int) (II)J"]);
+
+        let cause: Option<LocalObject<JavaThrowable>> = env.typed_call_method(&err, "getCause", ()).unwrap();
+        assert_eq!(class_name_of(env, &cause.unwrap()), "class java.lang.NoSuchMethodError");
+
+        let Err(err) = env.typed_call_method::<(), _, _>(&c_test, "reset", ()) else {
+            panic!("static reset succeeded");
+        };
+        let message = message_of(env, &err);
+
+        assert!(message.starts_with("Test: no method `static void reset() ()V`"), "{message}");
+        assert!(message.ends_with("\n    void reset() ()V"), "{message}");
+
+        let Err(err) = env.typed_call_method::<(), _, _>(&o_test, "rest", ()) else {
+            panic!("misspelled reset succeeded");
+        };
+        assert!(message_of(env, &err).ends_with("\n    void reset() ()V"));
+
+        let Err(err) = env.typed_call_method::<(), _, _>(&o_test, "unrelated", ()) else {
+            panic!("unrelated succeeded");
+        };
+        assert_eq!(
+            message_of(env, &err),
+            "Test: no method `void unrelated() ()V`, no members with similar names"
+        );
+
+        let Err(err) = env.typed_new_object(&c_test, (1i64,)) else {
+            panic!("constructor with long succeeded");
+        };
+        let message = message_of(env, &err);
+
+        assert!(message.starts_with("Test: no method `Test(long) (J)V`"), "{message}");
+        assert!(message.contains("\n    Test(int) (I)V"), "{message}");
+        assert!(message.contains("\n    Test() ()V"), "{message}");
+    })
+}
+
+#[test]
+fn test_verify_field_mismatch() {
+    with_java_vm(|env| {
+        let (_dir, loader) = compile_file_and_load_classes(env, "Test", SOURCE);
+
+        let c_test: LocalClass<JavaTest> = env.typed_find_class_in_class_loader(&loader).unwrap();
+        let o_test: LocalObject<JavaTest> = env.typed_new_object(&c_test, ()).unwrap();
+
+        let Err(err) = env.typed_get_field::<i32, _>(&o_test, "value") else {
+            panic!("get long field as int succeeded");
+        };
+
+        assert_eq!(class_name_of(env, &err), "class java.lang.NoSuchFieldError");
+        assert_eq!(
+            message_of(env, &err),
+            "Test: no field `int value I`, closest candidates:\n    long value J"
+        );
+
+        let Err(err) = env.typed_get_field::<LocalObject<JavaString>, _>(&o_test, "name") else {
+            panic!("get static field as instance succeeded");
+        };
+
+        assert_eq!(
+            message_of(env, &err),
+            "Test: no field `java.lang.String name Ljava/lang/String;`, closest candidates:\n    static java.lang.String name Ljava/lang/String;"
+        );
+    })
+}
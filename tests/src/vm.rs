@@ -0,0 +1,209 @@
+use std::sync::{Arc, Mutex};
+
+use typed_jni::{
+    GlobalObject, LocalClass, LocalObject, TypedCallExt, TypedClassExt, TypedRefExt, TypedStringExt,
+    builtin::{JavaString, JavaThreadGroup},
+    core::{AttachOptions, JavaVM},
+    define_java_class,
+};
+
+use crate::with_java_vm;
+
+#[test]
+fn test_attached_thread_persistent() {
+    define_java_class!(JavaThread, "java/lang/Thread");
+
+    with_java_vm(|env| {
+        let vm = env.vm();
+
+        let o_thread: GlobalObject<JavaThread> = std::thread::spawn(move || {
+            assert!(unsafe { vm.current_env() }.is_none());
+
+            let (first, o_thread) = vm
+                .with_attached_thread_persistent(false, |env| {
+                    let c_thread: LocalClass<JavaThread> = env.typed_find_class().unwrap();
+                    let o_thread: LocalObject<JavaThread> = env.typed_call_method(&c_thread, "currentThread", ()).unwrap();
+
+                    (env.as_raw_ptr(), env.typed_new_global_ref(&o_thread))
+                })
+                .unwrap();
+
+            assert!(unsafe { vm.current_env() }.is_some());
+
+            for _ in 0..1024 {
+                let second = vm
+                    .with_attached_thread_persistent(false, |env| {
+                        let _: LocalObject<JavaString> = env.typed_new_string("in frame");
+
+                        vm.with_attached_thread_persistent(false, |nested| {
+                            let _: LocalObject<JavaString> = nested.typed_new_string("in nested frame");
+
+                            nested.as_raw_ptr()
+                        })
+                        .unwrap();
+
+                        env.as_raw_ptr()
+                    })
+                    .unwrap();
+
+                assert_eq!(first, second);
+            }
+
+            o_thread
+        })
+        .join()
+        .unwrap();
+
+        let alive: bool = env.typed_call_method(&o_thread, "isAlive", ()).unwrap();
+        assert!(!alive);
+    })
+}
+
+#[test]
+fn test_attached_thread_persistent_after_manual_detach() {
+    with_java_vm(|env| {
+        let vm = env.vm();
+
+        std::thread::spawn(move || unsafe {
+            vm.attach_current_thread_persistent(false).unwrap();
+            vm.detach_current_thread().unwrap();
+            assert!(vm.current_env().is_none());
+
+            let env = vm.attach_current_thread_persistent(false).unwrap();
+            assert!(vm.current_env().is_some());
+
+            let s: LocalObject<JavaString> = env.typed_new_string("attached again");
+            assert_eq!(env.typed_get_string(&s), "attached again");
+        })
+        .join()
+        .unwrap();
+    })
+}
+
+#[test]
+fn test_attach_with_rust_thread_name() {
+    define_java_class!(JavaThread, "java/lang/Thread");
+
+    with_java_vm(|env| {
+        let vm = env.vm();
+
+        let name = std::thread::Builder::new()
+            .name("rust-worker-\u{1F980}".into())
+            .spawn(move || {
+                vm.with_attached_thread(false, |env| {
+                    let c_thread: LocalClass<JavaThread> = env.typed_find_class().unwrap();
+                    let o_thread: LocalObject<JavaThread> = env.typed_call_method(&c_thread, "currentThread", ()).unwrap();
+                    let o_name: LocalObject<JavaString> = env.typed_call_method(&o_thread, "getName", ()).unwrap();
+
+                    env.typed_get_string(&o_name)
+                })
+                .unwrap()
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+
+        assert_eq!(name, "rust-worker-\u{1F980}");
+    })
+}
+
+#[test]
+fn test_attach_with_name_and_group() {
+    define_java_class!(JavaThread, "java/lang/Thread");
+
+    with_java_vm(|env| {
+        let vm = env.vm();
+
+        let c_group: LocalClass<JavaThreadGroup> = env.typed_find_class().unwrap();
+        let o_group: LocalObject<JavaThreadGroup> =
+            env.typed_new_object(&c_group, (env.typed_new_string("rust-group"),)).unwrap();
+        let o_group: GlobalObject<JavaThreadGroup> = env.typed_new_global_ref(&o_group);
+
+        let (name, group_name) = std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let options = AttachOptions {
+                        as_daemon: true,
+                        name: Some(c"explicit-name"),
+                        group: Some(&o_group),
+                    };
+
+                    vm.with_attached_thread(options, |env| {
+                        let c_thread: LocalClass<JavaThread> = env.typed_find_class().unwrap();
+                        let o_thread: LocalObject<JavaThread> = env.typed_call_method(&c_thread, "currentThread", ()).unwrap();
+                        let o_name: LocalObject<JavaString> = env.typed_call_method(&o_thread, "getName", ()).unwrap();
+                        let o_group: LocalObject<JavaThreadGroup> =
+                            env.typed_call_method(&o_thread, "getThreadGroup", ()).unwrap();
+                        let o_group_name: LocalObject<JavaString> = env.typed_call_method(&o_group, "getName", ()).unwrap();
+
+                        (env.typed_get_string(&o_name), env.typed_get_string(&o_group_name))
+                    })
+                    .unwrap()
+                })
+                .join()
+                .unwrap()
+        });
+
+        assert_eq!(name, "explicit-name");
+        assert_eq!(group_name, "rust-group");
+    })
+}
+
+#[test]
+fn test_multiple_hooks() {
+    with_java_vm(|env| {
+        let vm = env.vm();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let hooked_thread = Arc::new(Mutex::new(None));
+
+        let record = |event: &'static str| {
+            let events = events.clone();
+            let hooked_thread = hooked_thread.clone();
+
+            move |_: &JavaVM| {
+                if *hooked_thread.lock().unwrap() == Some(std::thread::current().id()) {
+                    events.lock().unwrap().push(event);
+                }
+            }
+        };
+
+        let run_attached = || {
+            std::thread::scope(|scope| {
+                scope
+                    .spawn(|| {
+                        *hooked_thread.lock().unwrap() = Some(std::thread::current().id());
+
+                        vm.with_attached_thread(false, |_| {}).unwrap();
+                    })
+                    .join()
+                    .unwrap();
+            })
+        };
+
+        let attach_1 = JavaVM::add_attach_hook(record("attach 1"));
+        let detach_1 = JavaVM::add_detach_hook(record("detach 1"));
+        let attach_2 = JavaVM::add_attach_hook(record("attach 2"));
+        let detach_2 = JavaVM::add_detach_hook(record("detach 2"));
+
+        run_attached();
+
+        assert_eq!(*events.lock().unwrap(), ["attach 1", "attach 2", "detach 2", "detach 1"]);
+
+        drop(attach_1);
+        drop(detach_2);
+        events.lock().unwrap().clear();
+
+        run_attached();
+
+        assert_eq!(*events.lock().unwrap(), ["attach 2", "detach 1"]);
+
+        drop(attach_2);
+        drop(detach_1);
+        events.lock().unwrap().clear();
+
+        run_attached();
+
+        assert!(events.lock().unwrap().is_empty());
+    })
+}